@@ -317,6 +317,7 @@ fn main() {
         .file("rocks/db.cc")
         .file("rocks/db_dump_tool.cc")
         .file("rocks/env.cc")
+        .file("rocks/file_checksum.cc")
         .file("rocks/filter_policy.cc")
         .file("rocks/iostats_context.cc")
         .file("rocks/iterator.cc")
@@ -328,8 +329,11 @@ fn main() {
         .file("rocks/rate_limiter.cc")
         .file("rocks/slice.cc")
         .file("rocks/snapshot.cc")
+        .file("rocks/sst_file_manager.cc")
+        .file("rocks/sst_file_reader.cc")
         .file("rocks/sst_file_writer.cc")
         .file("rocks/statistics.cc")
+        .file("rocks/stats_history.cc")
         .file("rocks/status.cc")
         .file("rocks/table.cc")
         .file("rocks/table_properties.cc")
@@ -38,6 +38,13 @@ typedef struct rocks_status_t rocks_status_t;
 /* rate_limiter.h */
 typedef struct rocks_ratelimiter_t rocks_ratelimiter_t;
 
+/* sst_file_manager.h */
+typedef struct rocks_sst_file_manager_t rocks_sst_file_manager_t;
+
+/* file_checksum.h */
+typedef struct rocks_file_checksum_gen_factory_t rocks_file_checksum_gen_factory_t;
+typedef struct rocks_file_checksum_list_t rocks_file_checksum_list_t;
+
 /* env */
 typedef struct rocks_envoptions_t rocks_envoptions_t;
 typedef struct rocks_logger_t rocks_logger_t;
@@ -76,6 +83,9 @@ typedef struct rocks_mergeoperator_t rocks_mergeoperator_t;
 typedef struct rocks_comparator_t rocks_comparator_t;     /* for rust trait object */
 typedef struct rocks_c_comparator_t rocks_c_comparator_t; /* for c */
 
+/* sst_file_reader.h */
+typedef struct rocks_sst_file_reader_t rocks_sst_file_reader_t;
+
 /* sst_file_writer.h */
 typedef struct rocks_sst_file_writer_t rocks_sst_file_writer_t;
 typedef struct rocks_external_sst_file_info_t rocks_external_sst_file_info_t;
@@ -105,6 +115,9 @@ typedef struct rocks_universal_compaction_options_t rocks_universal_compaction_o
 typedef struct rocks_logfiles_t rocks_logfiles_t;
 typedef struct rocks_transaction_log_iterator_t rocks_transaction_log_iterator_t;
 
+/* stats_history */
+typedef struct rocks_stats_history_iterator_t rocks_stats_history_iterator_t;
+
 /* table_properties */
 typedef struct rocks_table_props_collection_t rocks_table_props_collection_t;
 
@@ -140,6 +153,7 @@ typedef struct rocks_table_file_creation_info_t rocks_table_file_creation_info_t
 typedef struct rocks_table_file_creation_brief_info_t rocks_table_file_creation_brief_info_t;
 typedef struct rocks_mem_table_info_t rocks_mem_table_info_t;
 typedef struct rocks_external_file_ingestion_info_t rocks_external_file_ingestion_info_t;
+typedef struct rocks_write_stall_info_t rocks_write_stall_info_t;
 
 /* thread_status */
 typedef struct rocks_thread_status_t rocks_thread_status_t;
@@ -210,15 +224,17 @@ void rocks_cfoptions_set_comparator_by_trait(rocks_cfoptions_t* opt, void* cp_tr
 
 void rocks_cfoptions_set_compaction_filter_by_trait(rocks_cfoptions_t* opt, void* filter_trait_obj);
 
+void rocks_cfoptions_set_compaction_filter_factory_by_trait(rocks_cfoptions_t* opt, void* factory_trait_obj);
+
 void rocks_cfoptions_set_bitwise_comparator(rocks_cfoptions_t* opt, unsigned char reversed);
 
+void rocks_cfoptions_set_bitwise_comparator_with_timestamp(rocks_cfoptions_t* opt, size_t ts_sz,
+                                                           unsigned char reversed);
+
 /*
 void rocks_cfoptions_set_compaction_filter(
                                        rocks_options_t* opt,
                                        rocks_compactionfilter_t* filter);
-
-void rocks_cfoptions_set_compaction_filter_factory(rocks_options_t* opt,
-rocks_compactionfilterfactory_t* factory);
 */
 
 void rocks_cfoptions_set_write_buffer_size(rocks_cfoptions_t* opt, size_t s);
@@ -230,6 +246,11 @@ void rocks_cfoptions_set_bottommost_compression(rocks_cfoptions_t* opt, int t);
 void rocks_cfoptions_set_compression_options(rocks_cfoptions_t* opt, int w_bits, int level, int strategy,
                                              uint32_t max_dict_bytes);
 
+void rocks_cfoptions_set_bottommost_compression_options(rocks_cfoptions_t* opt, int w_bits, int level, int strategy,
+                                                        uint32_t max_dict_bytes);
+
+void rocks_cfoptions_set_sample_for_compression(rocks_cfoptions_t* opt, uint64_t v);
+
 void rocks_cfoptions_set_level0_file_num_compaction_trigger(rocks_cfoptions_t* opt, int n);
 
 void rocks_cfoptions_set_prefix_extractor_by_trait(rocks_cfoptions_t* opt, void* trans_trait_obj);
@@ -254,6 +275,7 @@ void rocks_cfoptions_set_max_write_buffer_number(rocks_cfoptions_t* opt, int n);
 void rocks_cfoptions_set_min_write_buffer_number_to_merge(rocks_cfoptions_t* opt, int n);
 
 void rocks_cfoptions_set_max_write_buffer_number_to_maintain(rocks_cfoptions_t* opt, int n);
+void rocks_cfoptions_set_max_write_buffer_size_to_maintain(rocks_cfoptions_t* opt, int64_t n);
 
 void rocks_cfoptions_set_inplace_update_support(rocks_cfoptions_t* opt, unsigned char v);
 
@@ -263,6 +285,8 @@ void rocks_cfoptions_set_inplace_update_num_locks(rocks_cfoptions_t* opt, size_t
 
 void rocks_cfoptions_set_memtable_prefix_bloom_size_ratio(rocks_cfoptions_t* opt, double v);
 
+void rocks_cfoptions_set_memtable_whole_key_filtering(rocks_cfoptions_t* opt, unsigned char v);
+
 void rocks_cfoptions_set_memtable_huge_page_size(rocks_cfoptions_t* opt, size_t v);
 
 void rocks_cfoptions_set_memtable_insert_with_hint_prefix_extractor_by_trait(rocks_cfoptions_t* opt,
@@ -298,6 +322,8 @@ void rocks_cfoptions_set_max_bytes_for_level_multiplier_additional(rocks_cfoptio
 
 void rocks_cfoptions_set_max_compaction_bytes(rocks_cfoptions_t* opt, uint64_t n);
 
+uint64_t rocks_cfoptions_get_effective_max_compaction_bytes(const rocks_cfoptions_t* opt);
+
 void rocks_cfoptions_set_soft_pending_compaction_bytes_limit(rocks_cfoptions_t* opt, uint64_t v);
 
 void rocks_cfoptions_set_hard_pending_compaction_bytes_limit(rocks_cfoptions_t* opt, uint64_t v);
@@ -314,6 +340,8 @@ void rocks_cfoptions_set_fifo_compaction_options(rocks_cfoptions_t* opt, rocks_f
 void rocks_cfoptions_set_max_sequential_skip_in_iterations(rocks_cfoptions_t* opt, uint64_t v);
 
 // memtable_factory
+void rocks_cfoptions_set_memtable_skip_list_rep(rocks_cfoptions_t* opt, size_t lookahead);
+
 void rocks_cfoptions_set_memtable_vector_rep(rocks_cfoptions_t* opt, size_t count);
 
 void rocks_cfoptions_set_hash_skip_list_rep(rocks_cfoptions_t* opt, size_t bucket_count, int32_t skiplist_height,
@@ -336,6 +364,16 @@ void rocks_cfoptions_set_force_consistency_checks(rocks_cfoptions_t* opt, unsign
 
 void rocks_cfoptions_set_report_bg_io_stats(rocks_cfoptions_t* opt, unsigned char v);
 
+void rocks_cfoptions_set_enable_blob_files(rocks_cfoptions_t* opt, unsigned char v);
+
+void rocks_cfoptions_set_min_blob_size(rocks_cfoptions_t* opt, uint64_t v);
+
+void rocks_cfoptions_set_blob_file_size(rocks_cfoptions_t* opt, uint64_t v);
+
+void rocks_cfoptions_set_blob_compression_type(rocks_cfoptions_t* opt, int t);
+
+void rocks_cfoptions_set_enable_blob_garbage_collection(rocks_cfoptions_t* opt, unsigned char v);
+
 // dboptions
 
 void rocks_dboptions_optimize_for_small_db(rocks_dboptions_t* opt);
@@ -354,8 +392,9 @@ void rocks_dboptions_set_env(rocks_dboptions_t* opt, rocks_env_t* env);
 
 void rocks_dboptions_set_ratelimiter(rocks_dboptions_t* opt, rocks_ratelimiter_t* limiter);
 
-// void rocks_dboptions_set_sst_file_manager(rocks_dboptions_t* opt,
-// rocks_sst_file_manager_t* manager);
+void rocks_dboptions_set_file_checksum_gen_factory(rocks_dboptions_t* opt, rocks_file_checksum_gen_factory_t* factory);
+
+void rocks_dboptions_set_sst_file_manager(rocks_dboptions_t* opt, rocks_sst_file_manager_t* manager);
 
 void rocks_dboptions_set_info_log(rocks_dboptions_t* opt, rocks_logger_t* l);
 
@@ -381,6 +420,8 @@ void rocks_dboptions_set_wal_dir(rocks_dboptions_t* opt, const char* v, size_t l
 void rocks_dboptions_set_delete_obsolete_files_period_micros(rocks_dboptions_t* opt, uint64_t v);
 
 void rocks_dboptions_set_max_background_jobs(rocks_dboptions_t* opt, int n);
+void rocks_dboptions_set_max_background_compactions(rocks_dboptions_t* opt, int n);
+void rocks_dboptions_set_max_background_flushes(rocks_dboptions_t* opt, int n);
 
 void rocks_dboptions_set_max_subcompactions(rocks_dboptions_t* opt, uint32_t n);
 
@@ -416,6 +457,10 @@ void rocks_dboptions_set_is_fd_close_on_exec(rocks_dboptions_t* opt, unsigned ch
 
 void rocks_dboptions_set_stats_dump_period_sec(rocks_dboptions_t* opt, unsigned int v);
 
+void rocks_dboptions_set_stats_persist_period_sec(rocks_dboptions_t* opt, unsigned int v);
+
+void rocks_dboptions_set_stats_history_buffer_size(rocks_dboptions_t* opt, size_t v);
+
 void rocks_dboptions_set_advise_random_on_open(rocks_dboptions_t* opt, unsigned char v);
 
 void rocks_dboptions_set_db_write_buffer_size(rocks_dboptions_t* opt, size_t s);
@@ -448,8 +493,14 @@ void rocks_dboptions_set_write_thread_slow_yield_usec(rocks_dboptions_t* opt, ui
 
 void rocks_dboptions_set_skip_stats_update_on_db_open(rocks_dboptions_t* opt, unsigned char v);
 
+void rocks_dboptions_set_skip_checking_sst_file_sizes_on_db_open(rocks_dboptions_t* opt, unsigned char v);
+
 void rocks_dboptions_set_wal_recovery_mode(rocks_dboptions_t* opt, int mode);
 
+void rocks_dboptions_set_best_efforts_recovery(rocks_dboptions_t* opt, unsigned char v);
+
+void rocks_dboptions_set_track_and_verify_wals_in_manifest(rocks_dboptions_t* opt, unsigned char v);
+
 void rocks_dboptions_set_allow_2pc(rocks_dboptions_t* opt, unsigned char v);
 
 // FIXME: mem leaks?
@@ -470,6 +521,8 @@ void rocks_dboptions_set_avoid_flush_during_shutdown(rocks_dboptions_t* opt, uns
 
 void rocks_dboptions_set_allow_ingest_behind(rocks_dboptions_t* opt, unsigned char v);
 
+void rocks_dboptions_set_preserve_deletes(rocks_dboptions_t* opt, unsigned char v);
+
 void rocks_dboptions_set_concurrent_prepare(rocks_dboptions_t* opt, unsigned char v);
 
 void rocks_dboptions_set_manual_wal_flush(rocks_dboptions_t* opt, unsigned char v);
@@ -480,6 +533,14 @@ void rocks_options_prepare_for_bulk_load(rocks_options_t* opt);
 
 void rocks_options_optimize_for_small_db(rocks_options_t* opt);
 
+const char* rocks_options_get_comparator_name(rocks_options_t* opt);
+
+const char* rocks_options_get_merge_operator_name(rocks_options_t* opt);
+
+const char* rocks_options_get_prefix_extractor_name(rocks_options_t* opt);
+
+int rocks_options_get_num_levels(rocks_options_t* opt);
+
 /*
   char *rocks_options_statistics_get_string(rocks_options_t *opt) {
   rocksdb::Statistics *statistics = opt->rep.statistics.get();
@@ -507,6 +568,8 @@ void rocks_readoptions_set_snapshot(rocks_readoptions_t* opt, const rocks_snapsh
 
 void rocks_readoptions_set_iterate_upper_bound(rocks_readoptions_t* opt, const char* key, size_t keylen);
 
+void rocks_readoptions_set_timestamp(rocks_readoptions_t* opt, const char* ts, size_t tslen);
+
 void rocks_readoptions_set_read_tier(rocks_readoptions_t* opt, int v);
 
 void rocks_readoptions_set_tailing(rocks_readoptions_t* opt, unsigned char v);
@@ -527,6 +590,14 @@ void rocks_readoptions_set_ignore_range_deletions(rocks_readoptions_t* opt, unsi
 
 void rocks_readoptions_set_background_purge_on_iterator_cleanup(rocks_readoptions_t* opt, unsigned char v);
 
+void rocks_readoptions_set_auto_prefix_mode(rocks_readoptions_t* opt, unsigned char v);
+
+void rocks_readoptions_set_deadline(rocks_readoptions_t* opt, uint64_t microseconds);
+
+void rocks_readoptions_set_io_timeout(rocks_readoptions_t* opt, uint64_t microseconds);
+
+void rocks_readoptions_set_async_io(rocks_readoptions_t* opt, unsigned char v);
+
 /* > writeoptions */
 rocks_writeoptions_t* rocks_writeoptions_create();
 
@@ -542,6 +613,8 @@ void rocks_writeoptions_set_no_slowdown(rocks_writeoptions_t* opt, unsigned char
 
 void rocks_writeoptions_set_low_pri(rocks_writeoptions_t* opt, unsigned char v);
 
+void rocks_writeoptions_set_rate_limiter_priority(rocks_writeoptions_t* opt, int v);
+
 /* > compactrange_options */
 rocks_compactrange_options_t* rocks_compactrange_options_create();
 
@@ -557,6 +630,8 @@ void rocks_compactrange_options_set_target_path_id(rocks_compactrange_options_t*
 
 void rocks_compactrange_options_set_bottommost_level_compaction(rocks_compactrange_options_t* opt, int v);
 
+void rocks_compactrange_options_set_max_subcompactions(rocks_compactrange_options_t* opt, uint32_t v);
+
 /* > ingestexternalfile_options */
 rocks_ingestexternalfile_options_t* rocks_ingestexternalfile_options_create();
 
@@ -569,6 +644,12 @@ void rocks_ingestexternalfile_options_set_allow_global_seqno(rocks_ingestexterna
 void rocks_ingestexternalfile_options_set_allow_blocking_flush(rocks_ingestexternalfile_options_t* opt,
                                                                unsigned char v);
 void rocks_ingestexternalfile_options_set_ingest_behind(rocks_ingestexternalfile_options_t* opt, unsigned char v);
+void rocks_ingestexternalfile_options_set_failed_move_fall_back_to_copy(rocks_ingestexternalfile_options_t* opt,
+                                                                         unsigned char v);
+void rocks_ingestexternalfile_options_set_write_global_seqno(rocks_ingestexternalfile_options_t* opt,
+                                                             unsigned char v);
+void rocks_ingestexternalfile_options_set_verify_checksums_before_ingest(rocks_ingestexternalfile_options_t* opt,
+                                                                         unsigned char v);
 
 /* > flushoptions */
 rocks_flushoptions_t* rocks_flushoptions_create();
@@ -610,14 +691,40 @@ rocks_db_t* rocks_db_open(const rocks_options_t* options, const char* name, rock
 rocks_db_t* rocks_db_open_for_read_only(const rocks_options_t* options, const char* name,
                                         unsigned char error_if_log_file_exist, rocks_status_t** status);
 
+rocks_db_t* rocks_db_open_and_report_recovery(const rocks_options_t* options, const char* name,
+                                             uint64_t* records_replayed, unsigned char* wal_corrupted,
+                                             rocks_status_t** status);
+
+rocks_db_t* rocks_db_open_as_secondary(const rocks_options_t* options, const char* name, const char* secondary_path,
+                                       rocks_status_t** status);
+
+rocks_db_t* rocks_db_open_as_secondary_column_families(const rocks_dboptions_t* db_options, const char* name,
+                                                       const char* secondary_path, int num_column_families,
+                                                       const char* const* column_family_names,
+                                                       const rocks_cfoptions_t* const* column_family_options,
+                                                       rocks_column_family_handle_t** column_family_handles,
+                                                       rocks_status_t** status);
+
 void rocks_db_close(rocks_db_t* db);
 
+void rocks_db_close_with_status(rocks_db_t* db, rocks_status_t** status);
+
 rocks_db_t* rocks_db_open_column_families(const rocks_dboptions_t* db_options, const char* name, int num_column_families,
                                           const char* const* column_family_names,
                                           const rocks_cfoptions_t* const* column_family_options,
                                           rocks_column_family_handle_t** column_family_handles,
                                           rocks_status_t** status);
 
+rocks_db_t* rocks_db_open_with_ttl(const rocks_options_t* options, const char* name, int32_t ttl,
+                                   rocks_status_t** status);
+
+rocks_db_t* rocks_db_open_column_families_with_ttl(const rocks_dboptions_t* db_options, const char* name,
+                                                   int num_column_families, const char* const* column_family_names,
+                                                   const rocks_cfoptions_t* const* column_family_options,
+                                                   const int32_t* ttls,
+                                                   rocks_column_family_handle_t** column_family_handles,
+                                                   rocks_status_t** status);
+
 rocks_db_t* rocks_db_open_for_read_only_column_families(const rocks_options_t* db_options, const char* name,
                                                         int num_column_families, const char** column_family_names,
                                                         const rocks_cfoptions_t** column_family_options,
@@ -641,10 +748,12 @@ void rocks_db_drop_column_family(rocks_db_t* db, rocks_column_family_handle_t* h
 void rocks_db_destroy_column_family_handle(rocks_db_t* db, rocks_column_family_handle_t* handle,
                                            rocks_status_t** status);
 
+void rocks_db_release_default_column_family(rocks_db_t* db, rocks_column_family_handle_t* handle);
+
 void rocks_column_family_handle_destroy(rocks_column_family_handle_t* handle);
 
-void rocks_db_put(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen, const char* val,
-                  size_t vallen, rocks_status_t** status);
+uint64_t rocks_db_put(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
+                      const char* val, size_t vallen, rocks_status_t** status);
 
 /*
 void rocks_db_put_slice(
@@ -656,6 +765,10 @@ void rocks_db_put_slice(
 void rocks_db_put_cf(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_column_family_handle_t* column_family,
                      const char* key, size_t keylen, const char* val, size_t vallen, rocks_status_t** status);
 
+void rocks_db_put_cf_with_ts(rocks_db_t* db, const rocks_writeoptions_t* options,
+                             rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
+                             const char* ts, size_t tslen, const char* val, size_t vallen, rocks_status_t** status);
+
 void rocks_db_delete(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
                      rocks_status_t** status);
 
@@ -680,8 +793,11 @@ void rocks_db_merge(rocks_db_t* db, const rocks_writeoptions_t* options, const c
 void rocks_db_merge_cf(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_column_family_handle_t* column_family,
                        const char* key, size_t keylen, const char* val, size_t vallen, rocks_status_t** status);
 
-void rocks_db_write(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_raw_writebatch_t* batch,
-                    rocks_status_t** status);
+uint64_t rocks_db_write_with_seq(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_raw_writebatch_t* batch,
+                                 rocks_status_t** status);
+
+uint64_t rocks_db_write(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_raw_writebatch_t* batch,
+                        rocks_status_t** status);
 
 void rocks_db_get_pinnable(rocks_db_t* db, const rocks_readoptions_t* options, const char* key, size_t keylen,
                            rocks_pinnable_slice_t* value, rocks_status_t** status);
@@ -690,6 +806,12 @@ void rocks_db_get_cf_pinnable(rocks_db_t* db, const rocks_readoptions_t* options
                               rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
                               rocks_pinnable_slice_t* value, rocks_status_t** status);
 
+char* rocks_db_get(rocks_db_t* db, const rocks_readoptions_t* options, const char* key, size_t keylen, size_t* vallen,
+                   rocks_status_t** status);
+
+char* rocks_db_get_cf(rocks_db_t* db, const rocks_readoptions_t* options, rocks_column_family_handle_t* column_family,
+                      const char* key, size_t keylen, size_t* vallen, rocks_status_t** status);
+
 void rocks_db_multi_get(rocks_db_t* db, const rocks_readoptions_t* options, size_t num_keys,
                         const char* const* keys_list, const size_t* keys_list_sizes, char** values_list,
                         size_t* values_list_sizes, rocks_status_t** status);
@@ -699,6 +821,11 @@ void rocks_db_multi_get_cf(rocks_db_t* db, const rocks_readoptions_t* options,
                            const char* const* keys_list, const size_t* keys_list_sizes, char** values_list,
                            size_t* values_list_sizes, rocks_status_t** status);
 
+void rocks_db_batched_multi_get_cf(rocks_db_t* db, const rocks_readoptions_t* options,
+                                   const rocks_column_family_handle_t* column_family, size_t num_keys,
+                                   const char* const* keys_list, const size_t* keys_list_sizes, char** values_list,
+                                   size_t* values_list_sizes, rocks_status_t** status, unsigned char sorted_input);
+
 unsigned char rocks_db_key_may_exist(rocks_db_t* db, const rocks_readoptions_t* options, const char* key,
                                      size_t key_len, void* value, unsigned char* value_found);
 
@@ -706,6 +833,14 @@ unsigned char rocks_db_key_may_exist_cf(rocks_db_t* db, const rocks_readoptions_
                                         const rocks_column_family_handle_t* column_family, const char* key,
                                         size_t key_len, void* value, unsigned char* value_found);
 
+unsigned char rocks_db_key_exists_cf(rocks_db_t* db, const rocks_readoptions_t* options,
+                                     const rocks_column_family_handle_t* column_family, const char* key,
+                                     size_t key_len);
+
+cxx_string_vector_t* rocks_db_get_merge_operands(rocks_db_t* db, const rocks_readoptions_t* options,
+                                                 const rocks_column_family_handle_t* column_family, const char* key,
+                                                 size_t key_len, int max_operands, rocks_status_t** status);
+
 rocks_iterator_t* rocks_db_create_iterator(rocks_db_t* db, const rocks_readoptions_t* options);
 
 rocks_iterator_t* rocks_db_create_iterator_cf(rocks_db_t* db, const rocks_readoptions_t* options,
@@ -725,6 +860,14 @@ unsigned char rocks_db_get_property(rocks_db_t* db, const char* prop, const size
 unsigned char rocks_db_get_property_cf(rocks_db_t* db, rocks_column_family_handle_t* cf, const char* prop,
                                        const size_t prop_len, void* value);
 
+unsigned char rocks_db_get_map_property(rocks_db_t* db, const char* prop, const size_t prop_len, char*** keys,
+                                        char*** values, size_t* len);
+
+unsigned char rocks_db_get_map_property_cf(rocks_db_t* db, rocks_column_family_handle_t* cf, const char* prop,
+                                           const size_t prop_len, char*** keys, char*** values, size_t* len);
+
+void rocks_db_get_map_property_destroy(char** keys, char** values, size_t len);
+
 unsigned char rocks_db_get_int_property(rocks_db_t* db, const char* prop, const size_t prop_len, uint64_t* value);
 
 unsigned char rocks_db_get_int_property_cf(rocks_db_t* db, rocks_column_family_handle_t* cf, const char* prop,
@@ -733,6 +876,8 @@ unsigned char rocks_db_get_int_property_cf(rocks_db_t* db, rocks_column_family_h
 unsigned char rocks_db_get_aggregated_int_property(rocks_db_t* db, const char* prop, const size_t prop_len,
                                                    uint64_t* value);
 
+void rocks_db_reset_stats(rocks_db_t* db, rocks_status_t** status);
+
 void rocks_db_compact_range(rocks_db_t* db, const char* start_key, size_t start_key_len, const char* limit_key,
                             size_t limit_key_len);
 
@@ -759,6 +904,12 @@ void rocks_db_compact_files(rocks_db_t* db, rocks_compaction_options_t* opt, siz
                             const char* const* file_names, const size_t* file_name_lens, const int output_level,
                             const int output_path_id, rocks_status_t** status);
 
+void rocks_db_promote_l0(rocks_db_t* db, rocks_column_family_handle_t* column_family, int target_level,
+                         rocks_status_t** status);
+
+void rocks_db_suggest_compact_range(rocks_db_t* db, rocks_column_family_handle_t* column_family, const char* begin,
+                                    size_t begin_len, const char* end, size_t end_len, rocks_status_t** status);
+
 void rocks_db_pause_background_work(rocks_db_t* db, rocks_status_t** status);
 void rocks_db_continue_background_work(rocks_db_t* db, rocks_status_t** status);
 
@@ -786,14 +937,27 @@ void rocks_db_get_approximate_memtable_stats_cf(rocks_db_t* db, rocks_column_fam
 
 void rocks_db_get_name(rocks_db_t* db, void* s);
 
+rocks_cfoptions_t* rocks_db_get_options_cf(rocks_db_t* db, rocks_column_family_handle_t* column_family);
+
+rocks_dboptions_t* rocks_db_get_db_options(rocks_db_t* db);
+
+/* destroy with rocks_env_get_thread_list_destroy */
+rocks_thread_status_t** rocks_db_get_thread_list(rocks_db_t* db, size_t* len);
+
 void rocks_db_flush(rocks_db_t* db, rocks_flushoptions_t* options, rocks_status_t** status);
 void rocks_db_flush_cf(rocks_db_t* db, rocks_flushoptions_t* options, rocks_column_family_handle_t* column_family,
                        rocks_status_t** status);
 
+void rocks_db_flush_wal(rocks_db_t* db, unsigned char sync, rocks_status_t** status);
+
 void rocks_db_sync_wal(rocks_db_t* db, rocks_status_t** status);
 
 uint64_t rocks_db_get_latest_sequence_number(rocks_db_t* db);
 
+unsigned char rocks_db_set_preserve_deletes_sequence_number(rocks_db_t* db, uint64_t seqnum);
+
+void rocks_db_try_catch_up_with_primary(rocks_db_t* db, rocks_status_t** status);
+
 void rocks_db_disable_file_deletions(rocks_db_t* db, rocks_status_t** status);
 
 void rocks_db_enable_file_deletions(rocks_db_t* db, unsigned char force, rocks_status_t** status);
@@ -803,12 +967,18 @@ cxx_string_vector_t* rocks_db_get_live_files(rocks_db_t* db, unsigned char flush
 
 rocks_logfiles_t* rocks_db_get_sorted_wal_files(rocks_db_t* db, rocks_status_t** status);
 
-rocks_transaction_log_iterator_t* rocks_db_get_update_since(rocks_db_t* db, uint64_t seq_no, rocks_status_t** status);
+rocks_transaction_log_iterator_t* rocks_db_get_update_since(rocks_db_t* db, uint64_t seq_no,
+                                                            unsigned char verify_checksums, rocks_status_t** status);
+
+rocks_stats_history_iterator_t* rocks_db_get_stats_history(rocks_db_t* db, uint64_t start_time, uint64_t end_time,
+                                                           rocks_status_t** status);
 
 void rocks_db_delete_file(rocks_db_t* db, const char* name, size_t name_len, rocks_status_t** status);
 
 const rocks_livefiles_t* rocks_db_get_livefiles_metadata(rocks_db_t* db);
 
+rocks_file_checksum_list_t* rocks_db_get_live_files_checksum_info(rocks_db_t* db, rocks_status_t** status);
+
 const rocks_column_family_metadata_t* rocks_db_get_column_family_metadata(rocks_db_t* db,
                                                                           rocks_column_family_handle_t* column_family);
 
@@ -820,10 +990,22 @@ void rocks_db_ingest_external_file_cf(rocks_db_t* db, rocks_column_family_handle
                                       const char* const* file_list, const size_t* file_list_sizes, size_t file_len,
                                       const rocks_ingestexternalfile_options_t* options, rocks_status_t** status);
 
+void rocks_db_ingest_external_files(rocks_db_t* db, size_t num_args,
+                                    rocks_column_family_handle_t* const* column_families,
+                                    const size_t* num_files_per_arg, const char* const* file_list,
+                                    const size_t* file_list_sizes, const rocks_ingestexternalfile_options_t* options,
+                                    rocks_status_t** status);
+
+void rocks_db_verify_checksum(rocks_db_t* db, const rocks_readoptions_t* options, rocks_status_t** status);
+
 void rocks_db_get_db_identity(rocks_db_t* db,
                               void* identity,  // *mut String
                               rocks_status_t** status);
 
+void rocks_db_get_db_session_id(rocks_db_t* db,
+                                void* session_id,  // *mut String
+                                rocks_status_t** status);
+
 rocks_table_props_collection_t* rocks_db_get_properties_of_all_tables(rocks_db_t* db, rocks_column_family_handle_t* cf,
                                                                       rocks_status_t** status);
 
@@ -843,14 +1025,74 @@ void rocks_repair_db(const rocks_options_t* options, const char* name, size_t le
 /* rate_limiter.h */
 rocks_ratelimiter_t* rocks_ratelimiter_create(int64_t rate_bytes_per_sec, int64_t refill_period_us, int32_t fairness);
 
+rocks_ratelimiter_t* rocks_ratelimiter_copy(rocks_ratelimiter_t* limiter);
+
 void rocks_ratelimiter_destroy(rocks_ratelimiter_t* limiter);
 
+int64_t rocks_ratelimiter_get_single_burst_bytes(rocks_ratelimiter_t* limiter);
+
+void rocks_ratelimiter_request(rocks_ratelimiter_t* limiter, int64_t bytes, int pri, rocks_status_t** status);
+
+int64_t rocks_ratelimiter_get_total_bytes_through(rocks_ratelimiter_t* limiter, int pri);
+
+int64_t rocks_ratelimiter_get_total_requests(rocks_ratelimiter_t* limiter, int pri);
+
+/* sst_file_manager.h */
+rocks_sst_file_manager_t* rocks_sst_file_manager_create(rocks_env_t* env, rocks_logger_t* info_log,
+                                                        const char* trash_dir, size_t trash_dir_len,
+                                                        int64_t rate_bytes_per_sec, unsigned char delete_existing_trash,
+                                                        rocks_status_t** status);
+
+rocks_sst_file_manager_t* rocks_sst_file_manager_copy(rocks_sst_file_manager_t* manager);
+
+void rocks_sst_file_manager_destroy(rocks_sst_file_manager_t* manager);
+
+void rocks_sst_file_manager_set_max_allowed_space_usage(rocks_sst_file_manager_t* manager, uint64_t max_allowed_space);
+
+unsigned char rocks_sst_file_manager_is_max_allowed_space_reached(rocks_sst_file_manager_t* manager);
+
+uint64_t rocks_sst_file_manager_get_total_size(rocks_sst_file_manager_t* manager);
+
+int64_t rocks_sst_file_manager_get_delete_rate_bytes_per_second(rocks_sst_file_manager_t* manager);
+
+void rocks_sst_file_manager_set_delete_rate_bytes_per_second(rocks_sst_file_manager_t* manager, int64_t delete_rate);
+
+/* file_checksum.h */
+rocks_file_checksum_gen_factory_t* rocks_file_checksum_gen_crc32c_factory_create();
+
+void rocks_file_checksum_gen_factory_destroy(rocks_file_checksum_gen_factory_t* factory);
+
+size_t rocks_file_checksum_list_size(const rocks_file_checksum_list_t* list);
+
+uint64_t rocks_file_checksum_list_file_number(const rocks_file_checksum_list_t* list, size_t index);
+
+const char* rocks_file_checksum_list_checksum(const rocks_file_checksum_list_t* list, size_t index, size_t* len);
+
+const char* rocks_file_checksum_list_func_name(const rocks_file_checksum_list_t* list, size_t index, size_t* len);
+
+void rocks_file_checksum_list_destroy(rocks_file_checksum_list_t* list);
+
 /* env.h */
 rocks_env_t* rocks_create_default_env();
 
 rocks_env_t* rocks_create_mem_env();
 rocks_env_t* rocks_create_timed_env();
 
+/* takes ownership of base_env */
+rocks_env_t* rocks_create_encrypted_env(rocks_env_t* base_env, void* cipher_trait_obj);
+rocks_env_t* rocks_create_encrypted_env_with_keys(rocks_env_t* base_env, uint32_t current_key_id,
+                                                  const uint32_t* key_ids, void* const* cipher_trait_objs,
+                                                  size_t num_keys);
+
+/* takes ownership of base_env */
+rocks_env_t* rocks_create_fault_injection_env(rocks_env_t* base_env);
+
+void rocks_env_set_filesystem_active(rocks_env_t* env, unsigned char active);
+
+void rocks_env_set_read_delay(rocks_env_t* env, uint64_t micros);
+
+void rocks_env_set_direct_io_supported(rocks_env_t* env, unsigned char supported);
+
 void rocks_env_destroy(rocks_env_t* env);
 
 void rocks_env_set_background_threads(rocks_env_t* env, int n);
@@ -886,6 +1128,16 @@ void rocks_env_get_thread_list_destroy(rocks_thread_status_t** p);
 
 uint64_t rocks_env_get_thread_id(rocks_env_t* env);
 
+void rocks_env_file_exists(rocks_env_t* env, const char* fname, size_t fname_len, rocks_status_t** status);
+
+char** rocks_env_get_children(rocks_env_t* env, const char* dir, size_t dir_len, size_t* len, rocks_status_t** status);
+void rocks_env_get_children_destroy(char** list, size_t len);
+
+void rocks_env_delete_file(rocks_env_t* env, const char* fname, size_t fname_len, rocks_status_t** status);
+
+void rocks_env_create_dir_if_missing(rocks_env_t* env, const char* dirname, size_t dirname_len,
+                                     rocks_status_t** status);
+
 rocks_envoptions_t* rocks_envoptions_create();
 void rocks_envoptions_destroy(rocks_envoptions_t* opt);
 
@@ -901,6 +1153,8 @@ void rocks_envoptions_set_compaction_readahead_size(rocks_envoptions_t* opt, siz
 void rocks_envoptions_set_random_access_max_buffer_size(rocks_envoptions_t* opt, size_t val);
 void rocks_envoptions_set_writable_file_max_buffer_size(rocks_envoptions_t* opt, size_t val);
 
+rocks_logger_t* rocks_logger_create_from_rust(void* fn_obj, int log_level);
+
 void rocks_logger_destroy(rocks_logger_t* logger);
 
 void rocks_logger_log(rocks_logger_t* logger, int log_level, const char* msg_ptr, size_t msg_len);
@@ -928,12 +1182,18 @@ rocks_writebatch_t* rocks_writebatch_create();
 
 rocks_writebatch_t* rocks_writebatch_create_with_reserved_bytes(size_t size);
 
+rocks_writebatch_t* rocks_writebatch_create_with_max_bytes(size_t reserved_bytes, size_t max_bytes);
+
+rocks_writebatch_t* rocks_writebatch_create_from(const char* rep, size_t size);
+
 void rocks_writebatch_destroy(rocks_writebatch_t* b);
 
 void rocks_writebatch_clear(rocks_writebatch_t* b);
 
 int rocks_writebatch_count(rocks_writebatch_t* b);
 
+size_t rocks_writebatch_get_data_size(rocks_writebatch_t* b);
+
 void rocks_writebatch_put(rocks_writebatch_t* b, const char* key, size_t klen, const char* val, size_t vlen);
 
 void rocks_writebatch_put_cf(rocks_writebatch_t* b, rocks_column_family_handle_t* column_family, const char* key,
@@ -1012,6 +1272,7 @@ unsigned char rocks_writebatch_has_commit(rocks_writebatch_t* b);
 unsigned char rocks_writebatch_has_rollback(rocks_writebatch_t* b);
 
 rocks_writebatch_t* rocks_writebatch_copy(rocks_writebatch_t* b);
+void rocks_writebatch_append(rocks_writebatch_t* dst, rocks_writebatch_t* src, rocks_status_t** status);
 rocks_raw_writebatch_t* rocks_writebatch_get_writebatch(rocks_writebatch_t* b);
 
 /* table */
@@ -1042,6 +1303,9 @@ void rocks_block_based_table_options_set_cache_index_and_filter_blocks_with_high
 void rocks_block_based_table_options_set_pin_l0_filter_and_index_blocks_in_cache(
     rocks_block_based_table_options_t* options, unsigned char v);
 void rocks_block_based_table_options_set_index_type(rocks_block_based_table_options_t* options, int v);
+void rocks_block_based_table_options_set_data_block_index_type(rocks_block_based_table_options_t* options, int v);
+void rocks_block_based_table_options_set_data_block_hash_table_util_ratio(rocks_block_based_table_options_t* options,
+                                                                         double v);
 void rocks_block_based_table_options_set_hash_index_allow_collision(rocks_block_based_table_options_t* options,
                                                                     unsigned char v);
 // checksum
@@ -1113,6 +1377,8 @@ const char* rocks_iter_value(const rocks_iterator_t* iter, size_t* vlen);
 
 void rocks_iter_get_status(const rocks_iterator_t* iter, rocks_status_t** status);
 
+void rocks_iter_refresh(rocks_iterator_t* iter, rocks_status_t** status);
+
 void rocks_iter_get_property(const rocks_iterator_t* iter, const char* prop, size_t prop_len, void* value,
                              rocks_status_t** status);
 
@@ -1131,6 +1397,8 @@ rocks_cache_t* rocks_cache_create_clock(size_t capacity, int num_shard_bits, cha
 
 void rocks_cache_destroy(rocks_cache_t* cache);
 
+rocks_cache_t* rocks_cache_clone(rocks_cache_t* cache);
+
 void rocks_cache_set_capacity(rocks_cache_t* cache, size_t capacity);
 
 size_t rocks_cache_get_capacity(rocks_cache_t* cache);
@@ -1141,6 +1409,8 @@ size_t rocks_cache_get_pinned_usage(rocks_cache_t* cache);
 
 const char* rocks_cache_name(rocks_cache_t* cache);
 
+void rocks_cache_apply_to_all_entries(rocks_cache_t* cache, void* f);
+
 /* persistent_cache */
 rocks_persistent_cache_t* rocks_new_persistent_cache(const rocks_env_t* env, const char* path, size_t path_len,
                                                      uint64_t size, const rocks_logger_t* log,
@@ -1174,6 +1444,12 @@ rocks_sst_file_writer_t* rocks_sst_file_writer_create_from_rust_comparator(const
                                                                            rocks_column_family_handle_t* column_family,
                                                                            unsigned char invalidate_page_cache);
 
+/* use options->comparator */
+rocks_sst_file_writer_t* rocks_sst_file_writer_create(const rocks_envoptions_t* env_options,
+                                                      const rocks_options_t* options,
+                                                      rocks_column_family_handle_t* column_family,
+                                                      unsigned char invalidate_page_cache);
+
 void rocks_sst_file_writer_destroy(rocks_sst_file_writer_t* writer);
 
 void rocks_sst_file_writer_open(rocks_sst_file_writer_t* writer, const char* file_path, const size_t file_path_len,
@@ -1188,11 +1464,31 @@ void rocks_sst_file_writer_merge(rocks_sst_file_writer_t* writer, const char* ke
 void rocks_sst_file_writer_delete(rocks_sst_file_writer_t* writer, const char* key, const size_t key_len,
                                   rocks_status_t** status);
 
+void rocks_sst_file_writer_delete_range(rocks_sst_file_writer_t* writer, const char* begin_key,
+                                        const size_t begin_key_len, const char* end_key, const size_t end_key_len,
+                                        rocks_status_t** status);
+
 void rocks_sst_file_writer_finish(rocks_sst_file_writer_t* writer, rocks_external_sst_file_info_t* info,
                                   rocks_status_t** status);
 
 uint64_t rocks_sst_file_writer_file_size(rocks_sst_file_writer_t* writer);
 
+/* sst_file_reader */
+rocks_sst_file_reader_t* rocks_sst_file_reader_create(const rocks_options_t* options);
+
+void rocks_sst_file_reader_destroy(rocks_sst_file_reader_t* reader);
+
+void rocks_sst_file_reader_open(rocks_sst_file_reader_t* reader, const char* file_path, const size_t file_path_len,
+                                rocks_status_t** status);
+
+rocks_iterator_t* rocks_sst_file_reader_new_iterator(rocks_sst_file_reader_t* reader,
+                                                     const rocks_readoptions_t* options);
+
+rocks_table_props_t* rocks_sst_file_reader_get_table_properties(rocks_sst_file_reader_t* reader);
+
+void rocks_sst_file_reader_verify_checksum(rocks_sst_file_reader_t* reader, const rocks_readoptions_t* options,
+                                           rocks_status_t** status);
+
 /* comparator */
 /* avoid export rocksdb::Comparator type */
 const rocks_c_comparator_t* rocks_comparator_bytewise();
@@ -1269,6 +1565,10 @@ void rocks_statistics_to_string(rocks_statistics_t* stat, void* str); /* *mut St
 
 unsigned char rocks_statistics_hist_enabled_for_type(rocks_statistics_t* stat, uint32_t type);
 
+void rocks_statistics_reset(rocks_statistics_t* stat, rocks_status_t** status);
+
+unsigned char rocks_statistics_histogram_type_by_name(const char* name, size_t len, uint32_t* type);
+
 /* metadata */
 int rocks_livefiles_count(const rocks_livefiles_t* lf);
 
@@ -1357,6 +1657,17 @@ void rocks_transaction_log_iterator_next(rocks_transaction_log_iterator_t* it);
 void rocks_transaction_log_iterator_status(rocks_transaction_log_iterator_t* it, rocks_status_t** status);
 rocks_writebatch_t* rocks_transaction_log_iterator_get_batch(rocks_transaction_log_iterator_t* it, uint64_t* seq_no);
 
+/* stats_history */
+void rocks_stats_history_iterator_destroy(rocks_stats_history_iterator_t* it);
+unsigned char rocks_stats_history_iterator_valid(rocks_stats_history_iterator_t* it);
+void rocks_stats_history_iterator_next(rocks_stats_history_iterator_t* it);
+void rocks_stats_history_iterator_status(rocks_stats_history_iterator_t* it, rocks_status_t** status);
+uint64_t rocks_stats_history_iterator_get_stats_time(rocks_stats_history_iterator_t* it);
+size_t rocks_stats_history_iterator_load_stats_map(rocks_stats_history_iterator_t* it);
+const char* rocks_stats_history_iterator_stats_map_nth_key(rocks_stats_history_iterator_t* it, size_t nth,
+                                                           size_t* len);
+uint64_t rocks_stats_history_iterator_stats_map_nth_value(rocks_stats_history_iterator_t* it, size_t nth);
+
 /* convenience */
 int* rocks_get_supported_compressions(size_t* len);
 void rocks_get_supported_compressions_destroy(int* ptr);
@@ -1366,6 +1677,10 @@ void rocks_db_delete_files_in_range(rocks_db_t* db, rocks_column_family_handle_t
 // cxx_string_destroy must be called for following
 cxx_string_t* rocks_get_string_from_dboptions(rocks_dboptions_t* opts);
 cxx_string_t* rocks_get_string_from_cfoptions(rocks_cfoptions_t* opts);
+rocks_dboptions_t* rocks_get_dboptions_from_string(rocks_dboptions_t* base, const char* opts_str, size_t opts_len,
+                                                   rocks_status_t** status);
+rocks_cfoptions_t* rocks_get_cfoptions_from_string(rocks_cfoptions_t* base, const char* opts_str, size_t opts_len,
+                                                   rocks_status_t** status);
 
 /* table_properties */
 void rocks_table_props_collection_destroy(rocks_table_props_collection_t* coll);
@@ -1396,8 +1711,11 @@ uint64_t rocks_table_props_get_raw_key_size(rocks_table_props_t* prop);
 uint64_t rocks_table_props_get_raw_value_size(rocks_table_props_t* prop);
 uint64_t rocks_table_props_get_num_data_blocks(rocks_table_props_t* prop);
 uint64_t rocks_table_props_get_num_entries(rocks_table_props_t* prop);
+uint64_t rocks_table_props_get_num_deletions(rocks_table_props_t* prop);
 uint64_t rocks_table_props_get_format_version(rocks_table_props_t* prop);
 uint64_t rocks_table_props_get_fixed_key_len(rocks_table_props_t* prop);
+uint64_t rocks_table_props_get_slow_compression_estimated_data_size(rocks_table_props_t* prop);
+uint64_t rocks_table_props_get_fast_compression_estimated_data_size(rocks_table_props_t* prop);
 uint32_t rocks_table_props_get_column_family_id(rocks_table_props_t* prop);
 const char* rocks_table_props_get_column_family_name(rocks_table_props_t* prop, size_t* len);
 const char* rocks_table_props_get_filter_policy_name(rocks_table_props_t* prop, size_t* len);
@@ -1441,7 +1759,8 @@ size_t rocks_write_buffer_manager_buffer_size(rocks_write_buffer_manager_t* mana
 /* debug */
 rocks_key_version_collection_t* rocks_db_get_all_key_versions(rocks_db_t* db, const char* begin_key,
                                                               size_t begin_keylen, const char* end_key,
-                                                              size_t end_keylen, rocks_status_t** status);
+                                                              size_t end_keylen, size_t max_num_ikeys,
+                                                              rocks_status_t** status);
 
 void rocks_key_version_collection_destroy(rocks_key_version_collection_t* coll);
 size_t rocks_key_version_collection_size(rocks_key_version_collection_t* coll);
@@ -1472,6 +1791,8 @@ uint64_t rocks_flush_job_info_get_largest_seqno(const rocks_flush_job_info_t* in
 
 rocks_table_props_t* rocks_flush_job_info_get_table_properties(const rocks_flush_job_info_t* info);
 
+int rocks_flush_job_info_get_flush_reason(const rocks_flush_job_info_t* info);
+
 const char* rocks_table_file_deletion_info_get_db_name(const rocks_table_file_deletion_info_t* info, size_t* len);
 
 const char* rocks_table_file_deletion_info_get_file_path(const rocks_table_file_deletion_info_t* info, size_t* len);
@@ -1550,6 +1871,10 @@ uint64_t rocks_mem_table_info_get_earliest_seqno(const rocks_mem_table_info_t* i
 uint64_t rocks_mem_table_info_get_num_entries(const rocks_mem_table_info_t* info);
 uint64_t rocks_mem_table_info_get_num_deletes(const rocks_mem_table_info_t* info);
 
+const char* rocks_write_stall_info_get_cf_name(const rocks_write_stall_info_t* info, size_t* len);
+int rocks_write_stall_info_get_cur_condition(const rocks_write_stall_info_t* info);
+int rocks_write_stall_info_get_prev_condition(const rocks_write_stall_info_t* info);
+
 const char* rocks_external_file_ingestion_info_get_cf_name(const rocks_external_file_ingestion_info_t* info,
                                                            size_t* len);
 const char* rocks_external_file_ingestion_info_get_external_file_path(const rocks_external_file_ingestion_info_t* info,
@@ -2,6 +2,7 @@
 #include "rocksdb/persistent_cache.h"
 
 #include "rocks/ctypes.hpp"
+#include "rocks/rust_export.h"
 
 using namespace rocksdb;
 
@@ -27,6 +28,8 @@ rocks_cache_t* rocks_cache_create_clock(size_t capacity, int num_shard_bits, cha
 
 void rocks_cache_destroy(rocks_cache_t* cache) { delete cache; }
 
+rocks_cache_t* rocks_cache_clone(rocks_cache_t* cache) { return new rocks_cache_t{cache->rep}; }
+
 void rocks_cache_set_capacity(rocks_cache_t* cache, size_t capacity) { cache->rep->SetCapacity(capacity); }
 
 size_t rocks_cache_get_capacity(rocks_cache_t* cache) { return cache->rep->GetCapacity(); }
@@ -36,6 +39,14 @@ size_t rocks_cache_get_usage(rocks_cache_t* cache) { return cache->rep->GetUsage
 size_t rocks_cache_get_pinned_usage(rocks_cache_t* cache) { return cache->rep->GetPinnedUsage(); }
 
 const char* rocks_cache_name(rocks_cache_t* cache) { return cache->rep->Name(); }
+
+void rocks_cache_apply_to_all_entries(rocks_cache_t* cache, void* f) {
+  cache->rep->ApplyToAllEntries(
+      [f](const Slice& key, void* /* value */, size_t charge, Cache::DeleterFn /* deleter */) {
+        rust_cache_entry_call(f, key.data(), key.size(), charge);
+      },
+      Cache::ApplyToAllEntriesOptions());
+}
 }
 
 // persistent_cache
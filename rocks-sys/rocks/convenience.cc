@@ -50,4 +50,26 @@ cxx_string_t* rocks_get_string_from_cfoptions(rocks_cfoptions_t* opts) {
     return nullptr;
   }
 }
+
+rocks_dboptions_t* rocks_get_dboptions_from_string(rocks_dboptions_t* base, const char* opts_str, size_t opts_len,
+                                                   rocks_status_t** status) {
+  auto opts = new rocks_dboptions_t;
+  auto st = GetDBOptionsFromString(base->rep, std::string(opts_str, opts_len), &opts->rep);
+  if (SaveError(status, std::move(st))) {
+    delete opts;
+    return nullptr;
+  }
+  return opts;
+}
+
+rocks_cfoptions_t* rocks_get_cfoptions_from_string(rocks_cfoptions_t* base, const char* opts_str, size_t opts_len,
+                                                   rocks_status_t** status) {
+  auto opts = new rocks_cfoptions_t;
+  auto st = GetColumnFamilyOptionsFromString(base->rep, std::string(opts_str, opts_len), &opts->rep);
+  if (SaveError(status, std::move(st))) {
+    delete opts;
+    return nullptr;
+  }
+  return opts;
+}
 }
@@ -3,13 +3,17 @@
 #ifndef __RUST_ROCSK_SYS_H____
 #define __RUST_ROCSK_SYS_H____
 
+#include <atomic>
 #include <iostream>
+#include <mutex>
 
 #include "rocksdb/cache.h"
 #include "rocksdb/compaction_filter.h"
 #include "rocksdb/db.h"
 #include "rocksdb/db_dump_tool.h"
 #include "rocksdb/env.h"
+#include "rocksdb/env_encryption.h"
+#include "rocksdb/file_checksum.h"
 #include "rocksdb/filter_policy.h"
 #include "rocksdb/iterator.h"
 #include "rocksdb/listener.h"
@@ -19,12 +23,16 @@
 #include "rocksdb/persistent_cache.h"
 #include "rocksdb/rate_limiter.h"
 #include "rocksdb/slice_transform.h"
+#include "rocksdb/sst_file_manager.h"
+#include "rocksdb/sst_file_reader.h"
 #include "rocksdb/sst_file_writer.h"
 #include "rocksdb/status.h"
 #include "rocksdb/table.h"
 #include "rocksdb/table_properties.h"
+#include "rocksdb/stats_history.h"
 #include "rocksdb/transaction_log.h"
 #include "rocksdb/utilities/debug.h"
+#include "rocksdb/wal_filter.h"
 #include "rocksdb/write_buffer_manager.h"
 #include "rust_export.h"
 
@@ -86,6 +94,13 @@ struct rocks_column_family_handle_t {
 };
 struct rocks_db_t {
   DB* rep;
+  // serializes writes that report a sequence number on stacked DBs
+  std::mutex write_mu;
+  // referenced by the options of the DB, when opened with one
+  std::unique_ptr<WalFilter> wal_filter;
+  // column family handles given out and not released yet, the DB can't be
+  // closed while any is alive
+  std::atomic<size_t> live_handles{0};
 };
 
 /* options */
@@ -104,6 +119,7 @@ struct rocks_options_t {
 struct rocks_readoptions_t {
   ReadOptions rep;
   Slice upper_bound;  // stack variable to set pointer to in ReadOptions
+  Slice timestamp;
 };
 struct rocks_writeoptions_t {
   WriteOptions rep;
@@ -195,6 +211,11 @@ struct rocks_ratelimiter_t {
   shared_ptr<RateLimiter> rep;
 };
 
+/* sst_file_manager */
+struct rocks_sst_file_manager_t {
+  shared_ptr<SstFileManager> rep;
+};
+
 /* env */
 struct rocks_envoptions_t {
   EnvOptions rep;
@@ -206,6 +227,7 @@ struct rocks_logger_t {
 struct rocks_env_t {
   Env* rep;
   bool is_default;
+  rocks_env_t* base_env = nullptr;  // owned, for wrapping envs
 };
 
 /* snapshot*/
@@ -221,6 +243,9 @@ struct rocks_iterator_t {
 /* write_batch */
 struct rocks_writebatch_t {
   std::unique_ptr<WriteBatch> rep;
+  // WriteBatch has no getter for it, kept to carry it over when the batch is rebuilt,
+  // 0 (left out of the initializer) means no limit
+  size_t max_bytes;
 };
 typedef struct rocks_raw_writebatch_t rocks_raw_writebatch_t;
 
@@ -301,6 +326,11 @@ struct rocks_cache_t {
   shared_ptr<Cache> rep;
 };
 
+/* sst_file_reader */
+struct rocks_sst_file_reader_t {
+  SstFileReader* rep;
+};
+
 /* sst_file_writer */
 struct rocks_sst_file_writer_t {
   SstFileWriter* rep;
@@ -312,10 +342,17 @@ struct rocks_external_sst_file_info_t {
 /* compaction_filter */
 struct rocks_compaction_filter_t : public CompactionFilter {
   void* obj;  // rust Box<trait obj>
+  bool owned;
 
-  rocks_compaction_filter_t(void* trait_obj) : obj(trait_obj) {}
+  rocks_compaction_filter_t(void* trait_obj, bool owned = false) : obj(trait_obj), owned(owned) {}
 
-  ~rocks_compaction_filter_t() { rust_compaction_filter_drop(this->obj); }
+  ~rocks_compaction_filter_t() {
+    if (owned) {
+      rust_compaction_filter_drop_owned(this->obj);
+    } else {
+      rust_compaction_filter_drop(this->obj);
+    }
+  }
 
   Decision FilterV2(int level, const Slice& key, ValueType value_type, const Slice& existing_value,
                     std::string* new_value, std::string* skip_until) const override {
@@ -328,6 +365,22 @@ struct rocks_compaction_filter_t : public CompactionFilter {
   const char* Name() const override { return rust_compaction_filter_name(this->obj); }
 };
 
+struct rocks_compaction_filter_factory_t : public CompactionFilterFactory {
+  void* obj;  // rust Box<Arc<trait obj>>
+
+  rocks_compaction_filter_factory_t(void* trait_obj) : obj(trait_obj) {}
+
+  ~rocks_compaction_filter_factory_t() { rust_compaction_filter_factory_drop(this->obj); }
+
+  std::unique_ptr<CompactionFilter> CreateCompactionFilter(const CompactionFilter::Context& context) override {
+    auto filter = rust_compaction_filter_factory_create(this->obj, context.is_full_compaction,
+                                                        context.is_manual_compaction, context.column_family_id);
+    return std::unique_ptr<CompactionFilter>(new rocks_compaction_filter_t(filter, true));
+  }
+
+  const char* Name() const override { return rust_compaction_filter_factory_name(this->obj); }
+};
+
 /* slice_transform */
 struct rocks_slice_transform_t : public SliceTransform {
   void* obj;  // rust Box<trait obj>
@@ -351,6 +404,28 @@ struct rocks_slice_transform_t : public SliceTransform {
   bool InRange(const Slice& dst) const override { return false; }
 };
 
+struct rocks_block_cipher_t : public BlockCipher {
+  void* obj;  // rust Box<trait obj>
+
+  rocks_block_cipher_t(void* trait_obj) : obj(trait_obj) {}
+
+  ~rocks_block_cipher_t() { rust_block_cipher_drop(this->obj); }
+
+  const char* Name() const override { return rust_block_cipher_name(this->obj); }
+
+  size_t BlockSize() override { return rust_block_cipher_block_size(this->obj); }
+
+  Status Encrypt(char* data) override {
+    rust_block_cipher_encrypt(this->obj, data);
+    return Status::OK();
+  }
+
+  Status Decrypt(char* data) override {
+    rust_block_cipher_decrypt(this->obj, data);
+    return Status::OK();
+  }
+};
+
 /* db_dump_tool */
 struct rocks_dump_options_t {
   DumpOptions rep;
@@ -375,6 +450,17 @@ typedef struct rocks_histogram_data_t rocks_histogram_data_t;
 struct rocks_livefiles_t {
   std::vector<LiveFileMetaData> rep;
 };
+
+/* file_checksum */
+struct rocks_file_checksum_gen_factory_t {
+  shared_ptr<FileChecksumGenFactory> rep;
+};
+
+struct rocks_file_checksum_list_t {
+  std::vector<uint64_t> file_numbers;
+  std::vector<std::string> checksums;
+  std::vector<std::string> func_names;
+};
 struct rocks_column_family_metadata_t {
   ColumnFamilyMetaData rep;
 };
@@ -393,6 +479,12 @@ struct rocks_transaction_log_iterator_t {
   std::unique_ptr<TransactionLogIterator> rep;
 };
 
+/* stats_history */
+struct rocks_stats_history_iterator_t {
+  std::unique_ptr<StatsHistoryIterator> rep;
+  std::vector<std::pair<std::string, uint64_t>> stats_map;
+};
+
 /* table_properties */
 struct rocks_table_props_collection_t {
   // std::unordered_map<std::string, std::shared_ptr<const TableProperties>>
@@ -520,6 +612,10 @@ struct rocks_event_listener_t : public EventListener {
 
   void OnMemTableSealed(const MemTableInfo& info) override { rust_event_listener_on_memtable_sealed(this->obj, &info); }
 
+  void OnStallConditionsChanged(const WriteStallInfo& info) override {
+    rust_event_listener_on_stall_conditions_changed(this->obj, &info);
+  }
+
   void OnColumnFamilyHandleDeletionStarted(ColumnFamilyHandle* handle) override {
     rust_event_listener_on_column_family_handle_deletion_started(this->obj, new rocks_column_family_handle_t{handle});
   }
@@ -1,16 +1,288 @@
 #include "rocksdb/db.h"
 
 #include <iostream>
+#include <map>
+#include <memory>
 #include <unordered_map>
 
 #include "rocks/ctypes.hpp"
 #include "rocks/rust_export.h"
+#include "rocksdb/experimental.h"
+#include "rocksdb/slice_transform.h"
+#include "rocksdb/table_properties.h"
+#include "rocksdb/utilities/db_ttl.h"
 #include "rocksdb/utilities/info_log_finder.h"
+#include "rocksdb/utilities/options_util.h"
+#include "rocksdb/wal_filter.h"
 
 using namespace rocksdb;
 
 using std::shared_ptr;
 
+// Counts the WAL records replayed during recovery, chaining to the wal_filter
+// of the options, if any.
+struct RecoveryCountingWalFilter : public WalFilter {
+  WalFilter* base;
+  uint64_t records = 0;
+
+  explicit RecoveryCountingWalFilter(WalFilter* base_filter) : base(base_filter) {}
+
+  void ColumnFamilyLogNumberMap(const std::map<uint32_t, uint64_t>& cf_lognumber_map,
+                                const std::map<std::string, uint32_t>& cf_name_id_map) override {
+    if (base != nullptr) {
+      base->ColumnFamilyLogNumberMap(cf_lognumber_map, cf_name_id_map);
+    }
+  }
+
+  WalProcessingOption LogRecordFound(unsigned long long log_number, const std::string& log_file_name,
+                                     const WriteBatch& batch, WriteBatch* new_batch, bool* batch_changed) override {
+    auto option = WalProcessingOption::kContinueProcessing;
+    if (base != nullptr) {
+      option = base->LogRecordFound(log_number, log_file_name, batch, new_batch, batch_changed);
+    }
+    if (option == WalProcessingOption::kContinueProcessing) {
+      records++;
+    }
+    return option;
+  }
+
+  const char* Name() const override { return base != nullptr ? base->Name() : "RecoveryCountingWalFilter"; }
+};
+
+// RocksDB silently raises level0_slowdown_writes_trigger and
+// level0_stop_writes_trigger when they are out of order, reject them at open
+// instead. Negative values disable a trigger and are not checked.
+static Status ValidateLevel0Triggers(const std::string& cf_name, const ColumnFamilyOptions& opts) {
+  int compaction = opts.level0_file_num_compaction_trigger;
+  int slowdown = opts.level0_slowdown_writes_trigger;
+  int stop = opts.level0_stop_writes_trigger;
+  if ((compaction >= 0 && slowdown >= 0 && slowdown < compaction) || (slowdown >= 0 && stop >= 0 && stop < slowdown)) {
+    return Status::InvalidArgument("column family " + cf_name + ": level0_stop_writes_trigger (" +
+                                   std::to_string(stop) + ") >= level0_slowdown_writes_trigger (" +
+                                   std::to_string(slowdown) + ") >= level0_file_num_compaction_trigger (" +
+                                   std::to_string(compaction) + ") must hold");
+  }
+  return Status::OK();
+}
+
+static Status ValidateLevel0Triggers(const std::vector<ColumnFamilyDescriptor>& column_families) {
+  for (const auto& cf : column_families) {
+    auto st = ValidateLevel0Triggers(cf.name, cf.options);
+    if (!st.ok()) {
+      return st;
+    }
+  }
+  return Status::OK();
+}
+
+// Not every filesystem supports O_DIRECT, e.g. tmpfs on older kernels, and
+// RocksDB then fails with an opaque "Invalid argument" IO error on the first
+// file it opens. Probe the DB directories up front for a descriptive error.
+static Status ProbeDirectIO(Env* env, const std::string& dir, bool create_if_missing) {
+  if (!env->FileExists(dir).ok()) {
+    // nothing to probe, DB::Open reports the missing directory
+    if (!create_if_missing || !env->CreateDirIfMissing(dir).ok()) {
+      return Status::OK();
+    }
+  }
+  std::string probe = dir + "/DIRECT_IO_PROBE";
+  EnvOptions env_opts;
+  env_opts.use_direct_writes = true;
+  std::unique_ptr<WritableFile> file;
+  auto st = env->NewWritableFile(probe, &file, env_opts);
+  file.reset();
+  env->DeleteFile(probe);
+  if (!st.ok()) {
+    return Status::NotSupported("direct IO is not supported by the filesystem of " + dir +
+                                    ", disable use_direct_reads and use_direct_io_for_flush_and_compaction",
+                                st.ToString());
+  }
+  return Status::OK();
+}
+
+static Status ValidateDirectIO(const DBOptions& opts, const std::string& name) {
+  if (!opts.use_direct_reads && !opts.use_direct_io_for_flush_and_compaction) {
+    return Status::OK();
+  }
+  auto st = ProbeDirectIO(opts.env, name, opts.create_if_missing);
+  // DB::Open creates the WAL and data directories unconditionally
+  if (st.ok() && !opts.wal_dir.empty()) {
+    st = ProbeDirectIO(opts.env, opts.wal_dir, true);
+  }
+  for (size_t i = 0; st.ok() && i < opts.db_paths.size(); i++) {
+    st = ProbeDirectIO(opts.env, opts.db_paths[i].path, true);
+  }
+  return st;
+}
+
+// The deprecated max_background_compactions/max_background_flushes take
+// precedence over max_background_jobs whenever either is set, see
+// DBImpl::GetBGJobLimits(). Warn when max_background_jobs is also changed
+// from its default, since it is then silently ignored.
+static void WarnLegacyBackgroundLimits(DB* db) {
+  auto opts = db->GetDBOptions();
+  bool legacy_set = opts.max_background_compactions != -1 || opts.max_background_flushes != -1;
+  if (legacy_set && opts.max_background_jobs != DBOptions().max_background_jobs) {
+    Log(InfoLogLevel::WARN_LEVEL, opts.info_log,
+        "max_background_jobs=%d is ignored, since the deprecated max_background_compactions=%d / "
+        "max_background_flushes=%d are set and take precedence",
+        opts.max_background_jobs, opts.max_background_compactions, opts.max_background_flushes);
+  }
+}
+
+// level_compaction_dynamic_level_bytes of each column family, as persisted in
+// the latest OPTIONS file. Read before DB::Open, which rewrites that file.
+static std::map<std::string, bool> PersistedDynamicLevelBytes(const DBOptions& opts, const std::string& name) {
+  std::map<std::string, bool> persisted;
+  DBOptions db_opts;
+  std::vector<ColumnFamilyDescriptor> cf_descs;
+  if (LoadLatestOptions(name, opts.env, &db_opts, &cf_descs, true).ok()) {
+    for (const auto& cf : cf_descs) {
+      persisted[cf.name] = cf.options.level_compaction_dynamic_level_bytes;
+    }
+  }
+  return persisted;
+}
+
+// Flipping level_compaction_dynamic_level_bytes on an existing DB is accepted
+// silently, but the LSM tree keeps its old shape and only converges slowly,
+// e.g. with data stuck in the middle levels. Make the change visible.
+static void WarnDynamicLevelBytesChange(DB* db, const std::map<std::string, bool>& persisted,
+                                        const std::vector<ColumnFamilyDescriptor>& column_families) {
+  auto info_log = db->GetDBOptions().info_log;
+  for (const auto& cf : column_families) {
+    auto it = persisted.find(cf.name);
+    if (it != persisted.end() && it->second != cf.options.level_compaction_dynamic_level_bytes) {
+      Log(InfoLogLevel::WARN_LEVEL, info_log,
+          "[%s] level_compaction_dynamic_level_bytes changed from %s to %s for an existing DB, the LSM tree "
+          "keeps its current shape until compactions rebalance it",
+          cf.name.c_str(), it->second ? "true" : "false",
+          cf.options.level_compaction_dynamic_level_bytes ? "true" : "false");
+    }
+  }
+}
+
+static void WarnDynamicLevelBytesChange(DB* db, const std::map<std::string, bool>& persisted,
+                                        const Options& options) {
+  WarnDynamicLevelBytesChange(db, persisted, {ColumnFamilyDescriptor(kDefaultColumnFamilyName, options)});
+}
+
+// The name of the prefix extractor is persisted in the properties of every
+// table file. Opening with a different one is accepted silently, but the prefix
+// bloom filters of the old files are no longer used. Make the change visible.
+static void WarnPrefixExtractorMismatch(DB* db, const std::vector<ColumnFamilyHandle*>& handles) {
+  auto info_log = db->GetDBOptions().info_log;
+  for (auto cf : handles) {
+    auto extractor = db->GetOptions(cf).prefix_extractor;
+    std::string configured = extractor != nullptr ? extractor->Name() : "nullptr";
+    TablePropertiesCollection props;
+    if (!db->GetPropertiesOfAllTables(cf, &props).ok()) {
+      continue;
+    }
+    size_t mismatches = 0;
+    for (const auto& prop : props) {
+      if (prop.second->prefix_extractor_name != configured) {
+        mismatches++;
+      }
+    }
+    if (mismatches > 0) {
+      Log(InfoLogLevel::WARN_LEVEL, info_log,
+          "[%s] %zu of %zu table files were written with a prefix extractor other than %s, their prefix bloom "
+          "filters are not used",
+          cf->GetName().c_str(), mismatches, props.size(), configured.c_str());
+    }
+  }
+}
+
+static void WarnPrefixExtractorMismatch(DB* db) { WarnPrefixExtractorMismatch(db, {db->DefaultColumnFamily()}); }
+
+// A user-defined timestamp must be exactly as wide as the comparator of the
+// column family expects, a missing one counts as empty.
+static Status CheckTimestampSize(ColumnFamilyHandle* column_family, const Slice* ts) {
+  const size_t expected = column_family->GetComparator()->timestamp_size();
+  const size_t size = ts == nullptr ? 0 : ts->size();
+  if (size != expected) {
+    return Status::InvalidArgument("timestamp of " + std::to_string(size) + " bytes for column family " +
+                                   column_family->GetName() + ", its comparator expects " +
+                                   std::to_string(expected));
+  }
+  return Status::OK();
+}
+
+// The memtable inserter stamps the batch header with the first sequence
+// number it assigned, a fixed64 in the first 8 bytes of the batch.
+static uint64_t FirstSequenceOf(const WriteBatch& batch) {
+  const unsigned char* header = reinterpret_cast<const unsigned char*>(batch.Data().data());
+  uint64_t seq = 0;
+  for (int i = 7; i >= 0; i--) {
+    seq = (seq << 8) | header[i];
+  }
+  return seq;
+}
+
+// Stacked DBs such as DBWithTTL write a copy of each batch, so the sequence
+// number of a write can only be read back afterwards. Every write to them holds
+// this lock, which keeps that number exact. Writes to the root DB don't lock.
+static std::unique_lock<std::mutex> LockStackedWrites(rocks_db_t* db) {
+  if (db->rep->GetRootDB() == db->rep) {
+    return std::unique_lock<std::mutex>();
+  }
+  return std::unique_lock<std::mutex>(db->write_mu);
+}
+
+// Writes the batch and sets "*seq" to the sequence number of its first update.
+//
+// Only the root DB stamps the batch it was given, for stacked DBs the latest
+// sequence number is read back under LockStackedWrites(). An empty batch
+// consumes no sequence number, "*seq" is the latest one then.
+static Status WriteAndGetSequence(rocks_db_t* db, const WriteOptions& options, WriteBatch* batch, uint64_t* seq) {
+  const uint32_t count = batch->Count();
+  if (count > 0 && db->rep->GetRootDB() == db->rep) {
+    auto st = db->rep->Write(options, batch);
+    if (st.ok()) {
+      *seq = FirstSequenceOf(*batch);
+    }
+    return st;
+  }
+  auto lock = LockStackedWrites(db);
+  auto st = db->rep->Write(options, batch);
+  if (st.ok()) {
+    *seq = db->rep->GetLatestSequenceNumber() - (count > 0 ? count - 1 : 0);
+  }
+  return st;
+}
+
+static uint64_t WriteWithSequence(rocks_db_t* db, const WriteOptions& options, WriteBatch* batch, Status st,
+                                  rocks_status_t** status) {
+  uint64_t seq = 0;
+  if (st.ok()) {
+    st = WriteAndGetSequence(db, options, batch, &seq);
+  }
+  if (SaveError(status, std::move(st))) {
+    return 0;
+  }
+  return seq;
+}
+
+// Whether the WAL holds corruption other than a torn last record, which
+// PointInTimeRecovery and SkipAnyCorruptedRecords recover from silently.
+//
+// Replays the WAL in a read only DB with TolerateCorruptedTailRecords, the
+// strictest mode a crash doesn't trip, which fails on such corruption.
+static bool WalHasCorruption(const Options& options, const std::string& name) {
+  if (options.wal_recovery_mode == WALRecoveryMode::kAbsoluteConsistency ||
+      options.wal_recovery_mode == WALRecoveryMode::kTolerateCorruptedTailRecords) {
+    // opening fails on corruption anyway
+    return false;
+  }
+  Options probe(options);
+  probe.wal_recovery_mode = WALRecoveryMode::kTolerateCorruptedTailRecords;
+  probe.wal_filter = nullptr;
+  DB* db = nullptr;
+  auto st = DB::OpenForReadOnly(probe, name, &db);
+  delete db;
+  return st.IsCorruption();
+}
 extern "C" {
 const char* rocks_column_family_descriptor_get_name(const rocks_column_family_descriptor_t* desc) {
   return desc->rep.name.c_str();
@@ -34,22 +306,126 @@ uint32_t rocks_column_family_handle_get_id(const rocks_column_family_handle_t* h
 extern "C" {
 // DB
 rocks_db_t* rocks_db_open(const rocks_options_t* options, const char* name, rocks_status_t** status) {
+  if (SaveError(status, ValidateLevel0Triggers(kDefaultColumnFamilyName, options->rep))) {
+    return nullptr;
+  }
+  if (SaveError(status, ValidateDirectIO(options->rep, std::string(name)))) {
+    return nullptr;
+  }
+  auto persisted = PersistedDynamicLevelBytes(options->rep, std::string(name));
   DB* db = nullptr;
   Status st = DB::Open(options->rep, std::string(name), &db);
   if (SaveError(status, std::move(st))) {
     return nullptr;
   } else {
+    WarnLegacyBackgroundLimits(db);
+    WarnDynamicLevelBytesChange(db, persisted, options->rep);
+    WarnPrefixExtractorMismatch(db);
     rocks_db_t* result = new rocks_db_t;
     result->rep = db;
     return result;
   }
 }
 
+rocks_db_t* rocks_db_open_and_report_recovery(const rocks_options_t* options, const char* name,
+                                             uint64_t* records_replayed, unsigned char* wal_corrupted,
+                                             rocks_status_t** status) {
+  if (SaveError(status, ValidateLevel0Triggers(kDefaultColumnFamilyName, options->rep))) {
+    return nullptr;
+  }
+  if (SaveError(status, ValidateDirectIO(options->rep, std::string(name)))) {
+    return nullptr;
+  }
+  Options opts(options->rep);
+  if (opts.info_log == nullptr) {
+    // shared by both opens, so the probe doesn't roll the LOG file
+    auto st = CreateLoggerFromOptions(std::string(name), opts, &opts.info_log);
+    if (!st.ok()) {
+      // same as DB::Open, fall back to no logging
+      opts.info_log = nullptr;
+    }
+  }
+  *wal_corrupted = WalHasCorruption(opts, std::string(name));
+
+  // the DB keeps a pointer to its wal_filter, the filter lives as long as the handle
+  std::unique_ptr<RecoveryCountingWalFilter> filter(new RecoveryCountingWalFilter(opts.wal_filter));
+  opts.wal_filter = filter.get();
+
+  auto persisted = PersistedDynamicLevelBytes(opts, std::string(name));
+  DB* db = nullptr;
+  Status st = DB::Open(opts, std::string(name), &db);
+  *records_replayed = filter->records;
+  if (SaveError(status, std::move(st))) {
+    return nullptr;
+  } else {
+    WarnLegacyBackgroundLimits(db);
+    WarnDynamicLevelBytesChange(db, persisted, options->rep);
+    WarnPrefixExtractorMismatch(db);
+    rocks_db_t* result = new rocks_db_t;
+    result->rep = db;
+    result->wal_filter = std::move(filter);
+    return result;
+  }
+}
+
+rocks_db_t* rocks_db_open_as_secondary(const rocks_options_t* options, const char* name, const char* secondary_path,
+                                       rocks_status_t** status) {
+  DB* db = nullptr;
+  auto st = DB::OpenAsSecondary(options->rep, std::string(name), std::string(secondary_path), &db);
+  if (SaveError(status, std::move(st))) {
+    return nullptr;
+  } else {
+    rocks_db_t* result = new rocks_db_t;
+    result->rep = db;
+    return result;
+  }
+}
+
+rocks_db_t* rocks_db_open_as_secondary_column_families(const rocks_dboptions_t* db_options, const char* name,
+                                                       const char* secondary_path, int num_column_families,
+                                                       const char* const* column_family_names,
+                                                       const rocks_cfoptions_t* const* column_family_options,
+                                                       rocks_column_family_handle_t** column_family_handles,
+                                                       rocks_status_t** status) {
+  std::vector<ColumnFamilyDescriptor> column_families;
+  for (int i = 0; i < num_column_families; i++) {
+    column_families.push_back(ColumnFamilyDescriptor(std::string(column_family_names[i]),
+                                                     ColumnFamilyOptions(column_family_options[i]->rep)));
+  }
+
+  DB* db = nullptr;
+  std::vector<ColumnFamilyHandle*> handles;
+  if (SaveError(status, DB::OpenAsSecondary(db_options->rep, std::string(name), std::string(secondary_path),
+                                            column_families, &handles, &db))) {
+    return nullptr;
+  }
+
+  for (size_t i = 0; i < handles.size(); i++) {
+    rocks_column_family_handle_t* c_handle = new rocks_column_family_handle_t;
+    c_handle->rep = handles[i];
+    column_family_handles[i] = c_handle;
+  }
+  rocks_db_t* result = new rocks_db_t;
+  result->rep = db;
+  result->live_handles = handles.size();
+  return result;
+}
+
 void rocks_db_close(rocks_db_t* db) {
   delete db->rep;
   delete db;
 }
 
+void rocks_db_close_with_status(rocks_db_t* db, rocks_status_t** status) {
+  // closing frees the column family data the handles refer to
+  const size_t live_handles = db->live_handles;
+  if (live_handles > 0) {
+    SaveError(status, Status::Busy(std::to_string(live_handles) + " column family handles are still alive"));
+    return;
+  }
+  SaveError(status, db->rep->Close());
+}
+
 rocks_db_t* rocks_db_open_for_read_only(const rocks_options_t* options, const char* name,
                                         unsigned char error_if_log_file_exist, rocks_status_t** status) {
   DB* db = nullptr;
@@ -74,6 +450,14 @@ rocks_db_t* rocks_db_open_column_families(const rocks_dboptions_t* db_options, c
                                                      ColumnFamilyOptions(column_family_options[i]->rep)));
   }
 
+  if (SaveError(status, ValidateLevel0Triggers(column_families))) {
+    return nullptr;
+  }
+  if (SaveError(status, ValidateDirectIO(db_options->rep, std::string(name)))) {
+    return nullptr;
+  }
+
+  auto persisted = PersistedDynamicLevelBytes(db_options->rep, std::string(name));
   DB* db = nullptr;
   std::vector<ColumnFamilyHandle*> handles;
   if (SaveError(status, DB::Open(db_options->rep, std::string(name), column_families, &handles, &db))) {
@@ -85,8 +469,77 @@ rocks_db_t* rocks_db_open_column_families(const rocks_dboptions_t* db_options, c
     c_handle->rep = handles[i];
     column_family_handles[i] = c_handle;
   }
+  WarnLegacyBackgroundLimits(db);
+  WarnDynamicLevelBytesChange(db, persisted, column_families);
+  WarnPrefixExtractorMismatch(db, handles);
+  rocks_db_t* result = new rocks_db_t;
+  result->rep = db;
+  result->live_handles = handles.size();
+  return result;
+}
+
+rocks_db_t* rocks_db_open_with_ttl(const rocks_options_t* options, const char* name, int32_t ttl,
+                                   rocks_status_t** status) {
+  if (SaveError(status, ValidateLevel0Triggers(kDefaultColumnFamilyName, options->rep))) {
+    return nullptr;
+  }
+  if (SaveError(status, ValidateDirectIO(options->rep, std::string(name)))) {
+    return nullptr;
+  }
+  auto persisted = PersistedDynamicLevelBytes(options->rep, std::string(name));
+  DBWithTTL* db = nullptr;
+  Status st = DBWithTTL::Open(options->rep, std::string(name), &db, ttl);
+  if (SaveError(status, std::move(st))) {
+    return nullptr;
+  } else {
+    WarnLegacyBackgroundLimits(db);
+    WarnDynamicLevelBytesChange(db, persisted, options->rep);
+    WarnPrefixExtractorMismatch(db);
+    rocks_db_t* result = new rocks_db_t;
+    result->rep = db;
+    return result;
+  }
+}
+
+rocks_db_t* rocks_db_open_column_families_with_ttl(const rocks_dboptions_t* db_options, const char* name,
+                                                   int num_column_families, const char* const* column_family_names,
+                                                   const rocks_cfoptions_t* const* column_family_options,
+                                                   const int32_t* ttls,
+                                                   rocks_column_family_handle_t** column_family_handles,
+                                                   rocks_status_t** status) {
+  std::vector<ColumnFamilyDescriptor> column_families;
+  std::vector<int32_t> ttl_list;
+  for (int i = 0; i < num_column_families; i++) {
+    column_families.push_back(ColumnFamilyDescriptor(std::string(column_family_names[i]),
+                                                     ColumnFamilyOptions(column_family_options[i]->rep)));
+    ttl_list.push_back(ttls[i]);
+  }
+
+  if (SaveError(status, ValidateLevel0Triggers(column_families))) {
+    return nullptr;
+  }
+  if (SaveError(status, ValidateDirectIO(db_options->rep, std::string(name)))) {
+    return nullptr;
+  }
+
+  auto persisted = PersistedDynamicLevelBytes(db_options->rep, std::string(name));
+  DBWithTTL* db = nullptr;
+  std::vector<ColumnFamilyHandle*> handles;
+  if (SaveError(status, DBWithTTL::Open(db_options->rep, std::string(name), column_families, &handles, &db, ttl_list))) {
+    return nullptr;
+  }
+
+  for (size_t i = 0; i < handles.size(); i++) {
+    rocks_column_family_handle_t* c_handle = new rocks_column_family_handle_t;
+    c_handle->rep = handles[i];
+    column_family_handles[i] = c_handle;
+  }
+  WarnLegacyBackgroundLimits(db);
+  WarnDynamicLevelBytesChange(db, persisted, column_families);
+  WarnPrefixExtractorMismatch(db, handles);
   rocks_db_t* result = new rocks_db_t;
   result->rep = db;
+  result->live_handles = handles.size();
   return result;
 }
 
@@ -116,6 +569,7 @@ rocks_db_t* rocks_db_open_for_read_only_column_families(const rocks_options_t* d
   }
   rocks_db_t* result = new rocks_db_t;
   result->rep = db;
+  result->live_handles = handles.size();
   return result;
 }
 
@@ -153,11 +607,14 @@ rocks_column_family_handle_t* rocks_db_create_column_family(rocks_db_t* db,
   if (SaveError(status, std::move(st))) {
     delete handle;
     handle = nullptr;
+  } else {
+    db->live_handles++;
   }
   return handle;
 }
 
 rocks_column_family_handle_t* rocks_db_default_column_family(rocks_db_t* db) {
+  db->live_handles++;
   return new rocks_column_family_handle_t{db->rep->DefaultColumnFamily()};
 }
 
@@ -169,6 +626,13 @@ void rocks_db_destroy_column_family_handle(rocks_db_t* db, rocks_column_family_h
                                            rocks_status_t** status) {
   SaveError(status, db->rep->DestroyColumnFamilyHandle(handle->rep));
   delete handle;
+  db->live_handles--;
+}
+
+void rocks_db_release_default_column_family(rocks_db_t* db, rocks_column_family_handle_t* handle) {
+  // owned by the DB, only the wrapper is freed
+  delete handle;
+  db->live_handles--;
 }
 
 // NOTE: never delete handle-rep, since it'll be deleted by DestroyColumnFamilyHandle
@@ -180,9 +644,12 @@ void rocks_column_family_handle_destroy(rocks_column_family_handle_t* handle) {
   delete handle;
 }
 
-void rocks_db_put(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen, const char* val,
-                  size_t vallen, rocks_status_t** status) {
-  SaveError(status, db->rep->Put(options->rep, Slice(key, keylen), Slice(val, vallen)));
+uint64_t rocks_db_put(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
+                      const char* val, size_t vallen, rocks_status_t** status) {
+  // same as DB::Put(), which builds a single entry batch as well
+  WriteBatch batch(keylen + vallen + 24);
+  auto st = batch.Put(Slice(key, keylen), Slice(val, vallen));
+  return WriteWithSequence(db, options->rep, &batch, std::move(st), status);
 }
 
 /*
@@ -197,57 +664,86 @@ void rocks_db_put_slice(
 
 void rocks_db_put_cf(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_column_family_handle_t* column_family,
                      const char* key, size_t keylen, const char* val, size_t vallen, rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->Put(options->rep, column_family->rep, Slice(key, keylen), Slice(val, vallen)));
 }
 
+void rocks_db_put_cf_with_ts(rocks_db_t* db, const rocks_writeoptions_t* options,
+                             rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
+                             const char* ts, size_t tslen, const char* val, size_t vallen, rocks_status_t** status) {
+  // RocksDB 6.29 takes the timestamp of a write from WriteOptions
+  WriteOptions wopts = options->rep;
+  Slice timestamp(ts, tslen);
+  wopts.timestamp = &timestamp;
+  if (SaveError(status, CheckTimestampSize(column_family->rep, &timestamp))) {
+    return;
+  }
+  auto lock = LockStackedWrites(db);
+  SaveError(status, db->rep->Put(wopts, column_family->rep, Slice(key, keylen), Slice(val, vallen)));
+}
+
 void rocks_db_delete(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
                      rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->Delete(options->rep, Slice(key, keylen)));
 }
 
 void rocks_db_delete_cf(rocks_db_t* db, const rocks_writeoptions_t* options,
                         rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
                         rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->Delete(options->rep, column_family->rep, Slice(key, keylen)));
 }
 
 void rocks_db_single_delete(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
                             rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->SingleDelete(options->rep, Slice(key, keylen)));
 }
 
 void rocks_db_single_delete_cf(rocks_db_t* db, const rocks_writeoptions_t* options,
                                rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
                                rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->SingleDelete(options->rep, column_family->rep, Slice(key, keylen)));
 }
 
 void rocks_db_delete_range_cf(rocks_db_t* db, const rocks_writeoptions_t* options,
                               rocks_column_family_handle_t* column_family, const char* begin_key, size_t begin_keylen,
                               const char* end_key, size_t end_keylen, rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->DeleteRange(options->rep, column_family->rep, Slice(begin_key, begin_keylen),
                                          Slice(end_key, end_keylen)));
 }
 
 void rocks_db_merge(rocks_db_t* db, const rocks_writeoptions_t* options, const char* key, size_t keylen,
                     const char* val, size_t vallen, rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->Merge(options->rep, Slice(key, keylen), Slice(val, vallen)));
 }
 
 void rocks_db_merge_cf(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_column_family_handle_t* column_family,
                        const char* key, size_t keylen, const char* val, size_t vallen, rocks_status_t** status) {
+  auto lock = LockStackedWrites(db);
   SaveError(status, db->rep->Merge(options->rep, column_family->rep, Slice(key, keylen), Slice(val, vallen)));
 }
 
-void rocks_db_write(rocks_db_t* db, const rocks_writeoptions_t* options,
-                    rocks_raw_writebatch_t* batch,  // raw is pointer, non-raw is a wrapper
-                    rocks_status_t** status) {
-  auto st = db->rep->Write(options->rep, reinterpret_cast<WriteBatch*>(batch));
-  SaveError(status, std::move(st));
+uint64_t rocks_db_write_with_seq(rocks_db_t* db, const rocks_writeoptions_t* options, rocks_raw_writebatch_t* batch,
+                                 rocks_status_t** status) {
+  return WriteWithSequence(db, options->rep, reinterpret_cast<WriteBatch*>(batch), Status::OK(), status);
+}
+
+uint64_t rocks_db_write(rocks_db_t* db, const rocks_writeoptions_t* options,
+                        rocks_raw_writebatch_t* batch,  // raw is pointer, non-raw is a wrapper
+                        rocks_status_t** status) {
+  return WriteWithSequence(db, options->rep, reinterpret_cast<WriteBatch*>(batch), Status::OK(), status);
 }
 
 void rocks_db_get_pinnable(rocks_db_t* db, const rocks_readoptions_t* options, const char* key, size_t keylen,
                            rocks_pinnable_slice_t* value, rocks_status_t** status) {
+  if (SaveError(status, CheckTimestampSize(db->rep->DefaultColumnFamily(), options->rep.timestamp))) {
+    return;
+  }
   Status st = db->rep->Get(options->rep, db->rep->DefaultColumnFamily(), Slice(key, keylen), &value->rep);
   SaveError(status, std::move(st));
 }
@@ -255,10 +751,38 @@ void rocks_db_get_pinnable(rocks_db_t* db, const rocks_readoptions_t* options, c
 void rocks_db_get_cf_pinnable(rocks_db_t* db, const rocks_readoptions_t* options,
                               rocks_column_family_handle_t* column_family, const char* key, size_t keylen,
                               rocks_pinnable_slice_t* value, rocks_status_t** status) {
+  if (SaveError(status, CheckTimestampSize(column_family->rep, options->rep.timestamp))) {
+    return;
+  }
   Status st = db->rep->Get(options->rep, column_family->rep, Slice(key, keylen), &value->rep);
   SaveError(status, std::move(st));
 }
 
+char* rocks_db_get_cf(rocks_db_t* db, const rocks_readoptions_t* options, rocks_column_family_handle_t* column_family,
+                      const char* key, size_t keylen, size_t* vallen, rocks_status_t** status) {
+  // read through a PinnableSlice, so the value is copied only once: from the
+  // pinned block (or memtable) into the malloc()ed buffer owned by the caller
+  PinnableSlice value;
+  Status st = CheckTimestampSize(column_family->rep, options->rep.timestamp);
+  if (st.ok()) {
+    st = db->rep->Get(options->rep, column_family->rep, Slice(key, keylen), &value);
+  }
+  if (SaveError(status, std::move(st))) {
+    *vallen = 0;
+    return nullptr;
+  }
+  *vallen = value.size();
+  char* result = reinterpret_cast<char*>(malloc(sizeof(char) * value.size()));
+  memcpy(result, value.data(), sizeof(char) * value.size());
+  return result;
+}
+
+char* rocks_db_get(rocks_db_t* db, const rocks_readoptions_t* options, const char* key, size_t keylen, size_t* vallen,
+                   rocks_status_t** status) {
+  rocks_column_family_handle_t default_cf = {db->rep->DefaultColumnFamily()};
+  return rocks_db_get_cf(db, options, &default_cf, key, keylen, vallen, status);
+}
+
 void rocks_db_multi_get(rocks_db_t* db, const rocks_readoptions_t* options, size_t num_keys,
                         const char* const* keys_list, const size_t* keys_list_sizes, char** values_list,
                         size_t* values_list_sizes, rocks_status_t** status) {
@@ -267,7 +791,13 @@ void rocks_db_multi_get(rocks_db_t* db, const rocks_readoptions_t* options, size
     keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
   }
   std::vector<std::string> values(num_keys);
-  std::vector<Status> statuses = db->rep->MultiGet(options->rep, keys, &values);
+  std::vector<Status> statuses;
+  auto st = CheckTimestampSize(db->rep->DefaultColumnFamily(), options->rep.timestamp);
+  if (st.ok()) {
+    statuses = db->rep->MultiGet(options->rep, keys, &values);
+  } else {
+    statuses.assign(num_keys, st);
+  }
   for (size_t i = 0; i < num_keys; i++) {
     if (SaveError(status + i, std::move(Status(statuses[i])))) {
       values_list[i] = nullptr;
@@ -285,12 +815,21 @@ void rocks_db_multi_get_cf(rocks_db_t* db, const rocks_readoptions_t* options,
                            size_t* values_list_sizes, rocks_status_t** status) {
   std::vector<Slice> keys(num_keys);
   std::vector<ColumnFamilyHandle*> cfs(num_keys);
+  Status st;
   for (size_t i = 0; i < num_keys; i++) {
     keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
     cfs[i] = column_families[i]->rep;
+    if (st.ok()) {
+      st = CheckTimestampSize(cfs[i], options->rep.timestamp);
+    }
   }
   std::vector<std::string> values(num_keys);
-  std::vector<Status> statuses = db->rep->MultiGet(options->rep, cfs, keys, &values);
+  std::vector<Status> statuses;
+  if (st.ok()) {
+    statuses = db->rep->MultiGet(options->rep, cfs, keys, &values);
+  } else {
+    statuses.assign(num_keys, st);
+  }
   for (size_t i = 0; i < num_keys; i++) {
     if (SaveError(status + i, std::move(Status(statuses[i])))) {
       values_list[i] = nullptr;
@@ -302,6 +841,34 @@ void rocks_db_multi_get_cf(rocks_db_t* db, const rocks_readoptions_t* options,
   }
 }
 
+void rocks_db_batched_multi_get_cf(rocks_db_t* db, const rocks_readoptions_t* options,
+                                   const rocks_column_family_handle_t* column_family, size_t num_keys,
+                                   const char* const* keys_list, const size_t* keys_list_sizes, char** values_list,
+                                   size_t* values_list_sizes, rocks_status_t** status, unsigned char sorted_input) {
+  std::vector<Slice> keys(num_keys);
+  for (size_t i = 0; i < num_keys; i++) {
+    keys[i] = Slice(keys_list[i], keys_list_sizes[i]);
+  }
+  std::vector<PinnableSlice> values(num_keys);
+  std::vector<Status> statuses(num_keys);
+  auto st = CheckTimestampSize(column_family->rep, options->rep.timestamp);
+  if (st.ok()) {
+    db->rep->MultiGet(options->rep, column_family->rep, num_keys, keys.data(), values.data(), statuses.data(),
+                      sorted_input);
+  } else {
+    statuses.assign(num_keys, st);
+  }
+  for (size_t i = 0; i < num_keys; i++) {
+    if (SaveError(status + i, std::move(statuses[i]))) {
+      values_list[i] = nullptr;
+      values_list_sizes[i] = 0;
+    } else {
+      values_list[i] = CopyString(values[i].ToString());
+      values_list_sizes[i] = values[i].size();
+    }
+  }
+}
+
 unsigned char rocks_db_key_may_exist(rocks_db_t* db, const rocks_readoptions_t* options, const char* key,
                                      size_t key_len,
                                      void* value,  // *mut Vec<u8>
@@ -336,16 +903,54 @@ unsigned char rocks_db_key_may_exist_cf(rocks_db_t* db, const rocks_readoptions_
   }
 }
 
+unsigned char rocks_db_key_exists_cf(rocks_db_t* db, const rocks_readoptions_t* options,
+                                     const rocks_column_family_handle_t* column_family, const char* key,
+                                     size_t key_len) {
+  std::string unused;
+  // cheap negative answer from bloom filters and memtables
+  if (!db->rep->KeyMayExist(options->rep, column_family->rep, Slice(key, key_len), &unused)) {
+    return false;
+  }
+  PinnableSlice val;
+  return db->rep->Get(options->rep, column_family->rep, Slice(key, key_len), &val).ok();
+}
+
+cxx_string_vector_t* rocks_db_get_merge_operands(rocks_db_t* db, const rocks_readoptions_t* options,
+                                                 const rocks_column_family_handle_t* column_family, const char* key,
+                                                 size_t key_len, int max_operands, rocks_status_t** status) {
+  // bounds the array the operands are read into
+  const int max_allowed = 1 << 16;
+  if (max_operands <= 0 || max_operands > max_allowed) {
+    SaveError(status, Status::InvalidArgument("max_operands must be between 1 and " + std::to_string(max_allowed)));
+    return nullptr;
+  }
+  std::vector<PinnableSlice> values(max_operands);
+  GetMergeOperandsOptions merge_opts;
+  merge_opts.expected_max_number_of_operands = max_operands;
+  int num_operands = 0;
+  auto st = db->rep->GetMergeOperands(options->rep, column_family->rep, Slice(key, key_len), values.data(),
+                                      &merge_opts, &num_operands);
+  if (SaveError(status, std::move(st))) {
+    return nullptr;
+  }
+  cxx_string_vector_t* operands = new cxx_string_vector_t;
+  for (int i = 0; i < num_operands; i++) {
+    operands->rep.push_back(values[i].ToString());
+  }
+  return operands;
+}
+
 rocks_iterator_t* rocks_db_create_iterator(rocks_db_t* db, const rocks_readoptions_t* options) {
-  rocks_iterator_t* result = new rocks_iterator_t;
-  result->rep = db->rep->NewIterator(options->rep);
-  return result;
+  rocks_column_family_handle_t default_cf = {db->rep->DefaultColumnFamily()};
+  return rocks_db_create_iterator_cf(db, options, &default_cf);
 }
 
 rocks_iterator_t* rocks_db_create_iterator_cf(rocks_db_t* db, const rocks_readoptions_t* options,
                                               rocks_column_family_handle_t* column_family) {
   rocks_iterator_t* result = new rocks_iterator_t;
-  result->rep = db->rep->NewIterator(options->rep, column_family->rep);
+  // a mismatched timestamp surfaces through the iterator's status
+  auto st = CheckTimestampSize(column_family->rep, options->rep.timestamp);
+  result->rep = st.ok() ? db->rep->NewIterator(options->rep, column_family->rep) : NewErrorIterator(st);
   return result;
 }
 
@@ -355,6 +960,9 @@ void rocks_db_create_iterators(rocks_db_t* db, rocks_readoptions_t* opts,
   std::vector<ColumnFamilyHandle*> column_families_vec;
   for (size_t i = 0; i < size; i++) {
     column_families_vec.push_back(column_families[i]->rep);
+    if (SaveError(status, CheckTimestampSize(column_families[i]->rep, opts->rep.timestamp))) {
+      return;
+    }
   }
 
   std::vector<Iterator*> res;
@@ -403,6 +1011,43 @@ unsigned char rocks_db_get_property_cf(rocks_db_t* db, rocks_column_family_handl
   return has;
 }
 
+static unsigned char rocks_db_copy_map_property(bool has, const std::map<std::string, std::string>& cval,
+                                                char*** keys, char*** values, size_t* len) {
+  if (!has) {
+    *len = 0;
+    return has;
+  }
+  *len = cval.size();
+  *keys = static_cast<char**>(malloc(sizeof(char*) * cval.size()));
+  *values = static_cast<char**>(malloc(sizeof(char*) * cval.size()));
+  size_t i = 0;
+  for (const auto& kv : cval) {
+    (*keys)[i] = strdup(kv.first.c_str());
+    (*values)[i] = strdup(kv.second.c_str());
+    i++;
+  }
+  return has;
+}
+
+unsigned char rocks_db_get_map_property(rocks_db_t* db, const char* prop, const size_t prop_len, char*** keys,
+                                        char*** values, size_t* len) {
+  std::map<std::string, std::string> cval;
+  auto has = db->rep->GetMapProperty(Slice(prop, prop_len), &cval);
+  return rocks_db_copy_map_property(has, cval, keys, values, len);
+}
+
+unsigned char rocks_db_get_map_property_cf(rocks_db_t* db, rocks_column_family_handle_t* cf, const char* prop,
+                                           const size_t prop_len, char*** keys, char*** values, size_t* len) {
+  std::map<std::string, std::string> cval;
+  auto has = db->rep->GetMapProperty(cf->rep, Slice(prop, prop_len), &cval);
+  return rocks_db_copy_map_property(has, cval, keys, values, len);
+}
+
+void rocks_db_get_map_property_destroy(char** keys, char** values, size_t len) {
+  rocks_db_list_column_families_destroy(keys, len);
+  rocks_db_list_column_families_destroy(values, len);
+}
+
 unsigned char rocks_db_get_int_property(rocks_db_t* db, const char* prop, const size_t prop_len, uint64_t* value) {
   auto has = db->rep->GetIntProperty(Slice(prop, prop_len), value);
   return has;
@@ -420,6 +1065,8 @@ unsigned char rocks_db_get_aggregated_int_property(rocks_db_t* db, const char* p
   return has;
 }
 
+void rocks_db_reset_stats(rocks_db_t* db, rocks_status_t** status) { SaveError(status, db->rep->ResetStats()); }
+
 void rocks_db_compact_range(rocks_db_t* db, const char* start_key, size_t start_key_len, const char* limit_key,
                             size_t limit_key_len) {
   Slice a, b;
@@ -493,6 +1140,20 @@ void rocks_db_compact_files(rocks_db_t* db, rocks_compaction_options_t* opt, siz
   SaveError(status, std::move(st));
 }
 
+void rocks_db_promote_l0(rocks_db_t* db, rocks_column_family_handle_t* column_family, int target_level,
+                         rocks_status_t** status) {
+  SaveError(status, db->rep->PromoteL0(column_family->rep, target_level));
+}
+
+void rocks_db_suggest_compact_range(rocks_db_t* db, rocks_column_family_handle_t* column_family, const char* begin,
+                                    size_t begin_len, const char* end, size_t end_len, rocks_status_t** status) {
+  Slice a, b;
+  auto st = experimental::SuggestCompactRange(db->rep, column_family->rep,
+                                              (begin ? (a = Slice(begin, begin_len), &a) : nullptr),
+                                              (end ? (b = Slice(end, end_len), &b) : nullptr));
+  SaveError(status, std::move(st));
+}
+
 void rocks_db_pause_background_work(rocks_db_t* db, rocks_status_t** status) {
   SaveError(status, std::move(db->rep->PauseBackgroundWork()));
 }
@@ -564,6 +1225,31 @@ void rocks_db_get_name(rocks_db_t* db, void* s) {
   rust_string_assign(s, name.data(), name.size());
 }
 
+rocks_cfoptions_t* rocks_db_get_options_cf(rocks_db_t* db, rocks_column_family_handle_t* column_family) {
+  return new rocks_cfoptions_t{ColumnFamilyOptions(db->rep->GetOptions(column_family->rep))};
+}
+
+rocks_dboptions_t* rocks_db_get_db_options(rocks_db_t* db) { return new rocks_dboptions_t{db->rep->GetDBOptions()}; }
+
+rocks_thread_status_t** rocks_db_get_thread_list(rocks_db_t* db, size_t* len) {
+  std::vector<ThreadStatus> all_threads;
+  db->rep->GetEnv()->GetThreadList(&all_threads);
+  // only threads working on this DB, ThreadStatus can't be assigned so copy them
+  std::vector<ThreadStatus> thread_list;
+  for (const auto& ts : all_threads) {
+    if (ts.db_name == db->rep->GetName()) {
+      thread_list.push_back(ts);
+    }
+  }
+  *len = thread_list.size();
+
+  auto ptrs = new rocks_thread_status_t*[*len];
+  for (auto i = 0; i < *len; i++) {
+    ptrs[i] = new rocks_thread_status_t{thread_list[i]};
+  }
+  return ptrs;
+}
+
 void rocks_db_flush(rocks_db_t* db, rocks_flushoptions_t* options, rocks_status_t** status) {
   SaveError(status, std::move(db->rep->Flush(options->rep)));
 }
@@ -573,10 +1259,29 @@ void rocks_db_flush_cf(rocks_db_t* db, rocks_flushoptions_t* options, rocks_colu
   SaveError(status, std::move(db->rep->Flush(options->rep, column_family->rep)));
 }
 
-void rocks_db_sync_wal(rocks_db_t* db, rocks_status_t** status) { SaveError(status, std::move(db->rep->SyncWAL())); }
+void rocks_db_flush_wal(rocks_db_t* db, unsigned char sync, rocks_status_t** status) {
+  SaveError(status, std::move(db->rep->FlushWAL(sync)));
+}
+
+void rocks_db_sync_wal(rocks_db_t* db, rocks_status_t** status) {
+  // the mmap-ed WAL can't be synced this way, don't let it pass silently
+  if (db->rep->GetDBOptions().allow_mmap_writes) {
+    SaveError(status, Status::NotSupported("SyncWAL() is not supported when allow_mmap_writes = true"));
+    return;
+  }
+  SaveError(status, std::move(db->rep->SyncWAL()));
+}
 
 uint64_t rocks_db_get_latest_sequence_number(rocks_db_t* db) { return db->rep->GetLatestSequenceNumber(); }
 
+unsigned char rocks_db_set_preserve_deletes_sequence_number(rocks_db_t* db, uint64_t seqnum) {
+  return db->rep->SetPreserveDeletesSequenceNumber(seqnum);
+}
+
+void rocks_db_try_catch_up_with_primary(rocks_db_t* db, rocks_status_t** status) {
+  SaveError(status, std::move(db->rep->TryCatchUpWithPrimary()));
+}
+
 void rocks_db_disable_file_deletions(rocks_db_t* db, rocks_status_t** status) {
   SaveError(status, std::move(db->rep->DisableFileDeletions()));
 }
@@ -607,9 +1312,55 @@ rocks_logfiles_t* rocks_db_get_sorted_wal_files(rocks_db_t* db, rocks_status_t**
   return files;
 }
 
-rocks_transaction_log_iterator_t* rocks_db_get_update_since(rocks_db_t* db, uint64_t seq_no, rocks_status_t** status) {
+// GetUpdatesSince() silently starts at the next available batch (or reports a
+// Corruption) when the WAL files holding `seq_no` have been purged. Report that
+// as Expired so callers can tell it apart from real corruption.
+static Status CheckSequenceStillInWal(DB* db, uint64_t seq_no) {
+  if (seq_no == 0 || seq_no > db->GetLatestSequenceNumber()) {
+    return Status::OK();  // GetUpdatesSince() handles these
+  }
+  // with manual_wal_flush the latest writes may still be buffered, leaving the
+  // live WAL empty on disk
+  auto st = db->FlushWAL(false);
+  if (!st.ok()) {
+    return st;
+  }
+  VectorLogPtr files;
+  st = db->GetSortedWalFiles(files);
+  if (!st.ok()) {
+    return st;
+  }
+  for (const auto& file : files) {
+    if (file->SizeFileBytes() == 0) {
+      continue;
+    }
+    if (file->StartSequence() > seq_no) {
+      break;
+    }
+    return Status::OK();
+  }
+  return Status::Expired("Requested sequence is no longer in the WAL");
+}
+
+rocks_transaction_log_iterator_t* rocks_db_get_update_since(rocks_db_t* db, uint64_t seq_no,
+                                                            unsigned char verify_checksums, rocks_status_t** status) {
+  auto st = CheckSequenceStillInWal(db->rep, seq_no);
+  if (SaveError(status, std::move(st))) {
+    return nullptr;
+  }
   auto iter = new rocks_transaction_log_iterator_t;
-  auto st = db->rep->GetUpdatesSince(seq_no, &iter->rep);
+  st = db->rep->GetUpdatesSince(seq_no, &iter->rep, TransactionLogIterator::ReadOptions(verify_checksums != 0));
+  if (SaveError(status, std::move(st))) {
+    delete iter;
+    return nullptr;
+  }
+  return iter;
+}
+
+rocks_stats_history_iterator_t* rocks_db_get_stats_history(rocks_db_t* db, uint64_t start_time, uint64_t end_time,
+                                                           rocks_status_t** status) {
+  auto iter = new rocks_stats_history_iterator_t;
+  auto st = db->rep->GetStatsHistory(start_time, end_time, &iter->rep);
   if (SaveError(status, std::move(st))) {
     delete iter;
     return nullptr;
@@ -627,6 +1378,16 @@ const rocks_livefiles_t* rocks_db_get_livefiles_metadata(rocks_db_t* db) {
   return result;
 }
 
+rocks_file_checksum_list_t* rocks_db_get_live_files_checksum_info(rocks_db_t* db, rocks_status_t** status) {
+  std::unique_ptr<FileChecksumList> checksum_list(NewFileChecksumList());
+  if (SaveError(status, db->rep->GetLiveFilesChecksumInfo(checksum_list.get()))) {
+    return nullptr;
+  }
+  rocks_file_checksum_list_t* result = new rocks_file_checksum_list_t;
+  SaveError(status, checksum_list->GetAllFileChecksums(&result->file_numbers, &result->checksums, &result->func_names));
+  return result;
+}
+
 const rocks_column_family_metadata_t* rocks_db_get_column_family_metadata(rocks_db_t* db,
                                                                           rocks_column_family_handle_t* column_family) {
   rocks_column_family_metadata_t* result = new rocks_column_family_metadata_t;
@@ -641,6 +1402,7 @@ void rocks_db_ingest_external_file(rocks_db_t* db, const char* const* file_list,
   for (auto i = 0; i < file_len; i++) {
     external_files.push_back(std::string(file_list[i], file_list_sizes[i]));
   }
+  auto lock = LockStackedWrites(db);
   auto st = db->rep->IngestExternalFile(external_files, options->rep);
   SaveError(status, std::move(st));
 }
@@ -652,10 +1414,34 @@ void rocks_db_ingest_external_file_cf(rocks_db_t* db, rocks_column_family_handle
   for (auto i = 0; i < file_len; i++) {
     external_files.push_back(std::string(file_list[i], file_list_sizes[i]));
   }
+  auto lock = LockStackedWrites(db);
   auto st = db->rep->IngestExternalFile(column_family->rep, external_files, options->rep);
   SaveError(status, std::move(st));
 }
 
+void rocks_db_ingest_external_files(rocks_db_t* db, size_t num_args,
+                                    rocks_column_family_handle_t* const* column_families,
+                                    const size_t* num_files_per_arg, const char* const* file_list,
+                                    const size_t* file_list_sizes, const rocks_ingestexternalfile_options_t* options,
+                                    rocks_status_t** status) {
+  std::vector<IngestExternalFileArg> args(num_args);
+  size_t file_idx = 0;
+  for (size_t i = 0; i < num_args; i++) {
+    args[i].column_family = column_families[i]->rep;
+    args[i].options = options->rep;
+    for (size_t j = 0; j < num_files_per_arg[i]; j++, file_idx++) {
+      args[i].external_files.push_back(std::string(file_list[file_idx], file_list_sizes[file_idx]));
+    }
+  }
+  auto lock = LockStackedWrites(db);
+  auto st = db->rep->IngestExternalFiles(args);
+  SaveError(status, std::move(st));
+}
+
+void rocks_db_verify_checksum(rocks_db_t* db, const rocks_readoptions_t* options, rocks_status_t** status) {
+  SaveError(status, std::move(db->rep->VerifyChecksum(options->rep)));
+}
+
 void rocks_db_get_db_identity(rocks_db_t* db,
                               void* identity,  // *mut String
                               rocks_status_t** status) {
@@ -666,6 +1452,16 @@ void rocks_db_get_db_identity(rocks_db_t* db,
   }
 }
 
+void rocks_db_get_db_session_id(rocks_db_t* db,
+                                void* session_id,  // *mut String
+                                rocks_status_t** status) {
+  std::string id;
+  auto st = db->rep->GetDbSessionId(id);
+  if (!SaveError(status, std::move(st))) {
+    rust_string_assign(session_id, id.data(), id.size());
+  }
+}
+
 rocks_table_props_collection_t* rocks_db_get_properties_of_all_tables(rocks_db_t* db, rocks_column_family_handle_t* cf,
                                                                       rocks_status_t** status) {
   auto coll = new rocks_table_props_collection_t;
@@ -1,3 +1,9 @@
+#include <atomic>
+#include <chrono>
+#include <cstring>
+#include <map>
+#include <thread>
+
 #include "rocksdb/env.h"
 
 #include "rocks/ctypes.hpp"
@@ -6,6 +12,152 @@ using namespace rocksdb;
 
 using std::shared_ptr;
 
+namespace {
+// Fails all file writes while the filesystem is deactivated. Used to test
+// how the DB reacts to write errors, e.g. `paranoid_checks`.
+class FaultInjectionWritableFile : public WritableFileWrapper {
+ public:
+  FaultInjectionWritableFile(std::unique_ptr<WritableFile>&& target, const std::atomic<bool>* active)
+      : WritableFileWrapper(target.get()), target_(std::move(target)), active_(active) {}
+
+  Status Append(const Slice& data) override {
+    if (!active_->load()) return Status::IOError("fault injection: filesystem inactive");
+    return WritableFileWrapper::Append(data);
+  }
+
+  Status Flush() override {
+    if (!active_->load()) return Status::IOError("fault injection: filesystem inactive");
+    return WritableFileWrapper::Flush();
+  }
+
+  Status Sync() override {
+    if (!active_->load()) return Status::IOError("fault injection: filesystem inactive");
+    return WritableFileWrapper::Sync();
+  }
+
+ private:
+  std::unique_ptr<WritableFile> target_;
+  const std::atomic<bool>* active_;
+};
+
+// Delays every read by the env's current read delay. Used to test read
+// deadlines and IO timeouts.
+class FaultInjectionRandomAccessFile : public RandomAccessFileWrapper {
+ public:
+  FaultInjectionRandomAccessFile(std::unique_ptr<RandomAccessFile>&& target, const std::atomic<uint64_t>* delay)
+      : RandomAccessFileWrapper(target.get()), target_(std::move(target)), delay_(delay) {}
+
+  Status Read(uint64_t offset, size_t n, Slice* result, char* scratch) const override {
+    Delay();
+    return RandomAccessFileWrapper::Read(offset, n, result, scratch);
+  }
+
+  Status MultiRead(ReadRequest* reqs, size_t num_reqs) override {
+    Delay();
+    return RandomAccessFileWrapper::MultiRead(reqs, num_reqs);
+  }
+
+ private:
+  void Delay() const {
+    auto micros = delay_->load();
+    if (micros > 0) std::this_thread::sleep_for(std::chrono::microseconds(micros));
+  }
+
+  std::unique_ptr<RandomAccessFile> target_;
+  const std::atomic<uint64_t>* delay_;
+};
+
+class FaultInjectionEnv : public EnvWrapper {
+ public:
+  explicit FaultInjectionEnv(Env* base)
+      : EnvWrapper(base), active_(true), read_delay_micros_(0), direct_io_supported_(true) {}
+
+  Status NewWritableFile(const std::string& fname, std::unique_ptr<WritableFile>* result,
+                         const EnvOptions& options) override {
+    if (!active_.load()) return Status::IOError("fault injection: filesystem inactive");
+    if (options.use_direct_writes && !direct_io_supported_.load()) {
+      return Status::IOError("fault injection: direct IO not supported", fname);
+    }
+    std::unique_ptr<WritableFile> file;
+    auto st = target()->NewWritableFile(fname, &file, options);
+    if (st.ok()) result->reset(new FaultInjectionWritableFile(std::move(file), &active_));
+    return st;
+  }
+
+  Status NewRandomAccessFile(const std::string& fname, std::unique_ptr<RandomAccessFile>* result,
+                             const EnvOptions& options) override {
+    if (options.use_direct_reads && !direct_io_supported_.load()) {
+      return Status::IOError("fault injection: direct IO not supported", fname);
+    }
+    std::unique_ptr<RandomAccessFile> file;
+    auto st = target()->NewRandomAccessFile(fname, &file, options);
+    if (st.ok()) result->reset(new FaultInjectionRandomAccessFile(std::move(file), &read_delay_micros_));
+    return st;
+  }
+
+  void SetFilesystemActive(bool active) { active_.store(active); }
+
+  void SetReadDelay(uint64_t micros) { read_delay_micros_.store(micros); }
+
+  void SetDirectIOSupported(bool supported) { direct_io_supported_.store(supported); }
+
+ private:
+  std::atomic<bool> active_;
+  std::atomic<uint64_t> read_delay_micros_;
+  std::atomic<bool> direct_io_supported_;
+};
+
+// Puts the id of the key a file is written with in front of the CTR prefix, so
+// new files use the current key while old files keep decrypting with theirs.
+class KeyedEncryptionProvider : public EncryptionProvider {
+ public:
+  // keeps the CTR prefix, and so the data, page aligned
+  static const size_t kKeyIdLength = 4096;
+
+  KeyedEncryptionProvider(uint32_t current_key_id, std::map<uint32_t, std::shared_ptr<EncryptionProvider>>&& providers)
+      : current_key_id_(current_key_id), providers_(std::move(providers)) {}
+
+  const char* Name() const override { return "RustKeyedEncryptionProvider"; }
+
+  // all keys use a CTR provider, so the prefix length does not depend on the key
+  size_t GetPrefixLength() const override { return kKeyIdLength + providers_.at(current_key_id_)->GetPrefixLength(); }
+
+  Status CreateNewPrefix(const std::string& fname, char* prefix, size_t prefixLength) const override {
+    memset(prefix, 0, kKeyIdLength);
+    for (int i = 0; i < 4; i++) {
+      prefix[i] = static_cast<char>((current_key_id_ >> (8 * i)) & 0xff);
+    }
+    return providers_.at(current_key_id_)->CreateNewPrefix(fname, prefix + kKeyIdLength, prefixLength - kKeyIdLength);
+  }
+
+  Status AddCipher(const std::string& /* descriptor */, const char* /* cipher */, size_t /* len */,
+                   bool /* for_write */) override {
+    return Status::NotSupported("keys are fixed when the env is created");
+  }
+
+  Status CreateCipherStream(const std::string& fname, const EnvOptions& options, Slice& prefix,
+                            std::unique_ptr<BlockAccessCipherStream>* result) override {
+    if (prefix.size() < kKeyIdLength) {
+      return Status::Corruption("encryption prefix too short", fname);
+    }
+    uint32_t key_id = 0;
+    for (int i = 0; i < 4; i++) {
+      key_id |= static_cast<uint32_t>(static_cast<unsigned char>(prefix[i])) << (8 * i);
+    }
+    auto it = providers_.find(key_id);
+    if (it == providers_.end()) {
+      return Status::NotFound("no cipher for key id " + std::to_string(key_id), fname);
+    }
+    Slice ctr_prefix(prefix.data() + kKeyIdLength, prefix.size() - kKeyIdLength);
+    return it->second->CreateCipherStream(fname, options, ctr_prefix, result);
+  }
+
+ private:
+  uint32_t current_key_id_;
+  std::map<uint32_t, std::shared_ptr<EncryptionProvider>> providers_;
+};
+}  // namespace
+
 extern "C" {
 rocks_env_t* rocks_create_default_env() {
   rocks_env_t* result = new rocks_env_t;
@@ -28,8 +180,57 @@ rocks_env_t* rocks_create_timed_env() {
   return result;
 }
 
+rocks_env_t* rocks_create_encrypted_env(rocks_env_t* base_env, void* cipher_trait_obj) {
+  auto cipher = std::make_shared<rocks_block_cipher_t>(cipher_trait_obj);
+  rocks_env_t* result = new rocks_env_t;
+  result->rep = NewEncryptedEnv(base_env->rep, EncryptionProvider::NewCTRProvider(cipher));
+  result->is_default = false;
+  result->base_env = base_env;
+  return result;
+}
+
+rocks_env_t* rocks_create_encrypted_env_with_keys(rocks_env_t* base_env, uint32_t current_key_id,
+                                                  const uint32_t* key_ids, void* const* cipher_trait_objs,
+                                                  size_t num_keys) {
+  std::map<uint32_t, std::shared_ptr<EncryptionProvider>> providers;
+  for (size_t i = 0; i < num_keys; i++) {
+    auto cipher = std::make_shared<rocks_block_cipher_t>(cipher_trait_objs[i]);
+    providers[key_ids[i]] = EncryptionProvider::NewCTRProvider(cipher);
+  }
+  rocks_env_t* result = new rocks_env_t;
+  result->rep = NewEncryptedEnv(base_env->rep,
+                                std::make_shared<KeyedEncryptionProvider>(current_key_id, std::move(providers)));
+  result->is_default = false;
+  result->base_env = base_env;
+  return result;
+}
+
+rocks_env_t* rocks_create_fault_injection_env(rocks_env_t* base_env) {
+  rocks_env_t* result = new rocks_env_t;
+  result->rep = new FaultInjectionEnv(base_env->rep);
+  result->is_default = false;
+  result->base_env = base_env;
+  return result;
+}
+
+void rocks_env_set_filesystem_active(rocks_env_t* env, unsigned char active) {
+  auto fault_env = dynamic_cast<FaultInjectionEnv*>(env->rep);
+  if (fault_env != nullptr) fault_env->SetFilesystemActive(active);
+}
+
+void rocks_env_set_read_delay(rocks_env_t* env, uint64_t micros) {
+  auto fault_env = dynamic_cast<FaultInjectionEnv*>(env->rep);
+  if (fault_env != nullptr) fault_env->SetReadDelay(micros);
+}
+
+void rocks_env_set_direct_io_supported(rocks_env_t* env, unsigned char supported) {
+  auto fault_env = dynamic_cast<FaultInjectionEnv*>(env->rep);
+  if (fault_env != nullptr) fault_env->SetDirectIOSupported(supported);
+}
+
 void rocks_env_destroy(rocks_env_t* env) {
   if (!env->is_default) delete env->rep;
+  if (env->base_env != nullptr) rocks_env_destroy(env->base_env);
   delete env;
 }
 
@@ -114,6 +315,43 @@ void rocks_env_get_thread_list_destroy(rocks_thread_status_t** p) {
 }
 
 uint64_t rocks_env_get_thread_id(rocks_env_t* env) { return env->rep->GetThreadID(); }
+
+void rocks_env_file_exists(rocks_env_t* env, const char* fname, size_t fname_len, rocks_status_t** status) {
+  SaveError(status, env->rep->FileExists(std::string(fname, fname_len)));
+}
+
+char** rocks_env_get_children(rocks_env_t* env, const char* dir, size_t dir_len, size_t* len, rocks_status_t** status) {
+  std::vector<std::string> children;
+  auto st = env->rep->GetChildren(std::string(dir, dir_len), &children);
+  if (SaveError(status, std::move(st))) {
+    *len = 0;
+    return nullptr;
+  }
+
+  *len = children.size();
+  char** result = static_cast<char**>(malloc(sizeof(char*) * children.size()));
+  for (size_t i = 0; i < children.size(); i++) {
+    result[i] = strdup(children[i].c_str());
+  }
+  return result;
+}
+
+void rocks_env_get_children_destroy(char** list, size_t len) {
+  if (list == nullptr) return;
+  for (size_t i = 0; i < len; ++i) {
+    free(list[i]);
+  }
+  free(list);
+}
+
+void rocks_env_delete_file(rocks_env_t* env, const char* fname, size_t fname_len, rocks_status_t** status) {
+  SaveError(status, env->rep->DeleteFile(std::string(fname, fname_len)));
+}
+
+void rocks_env_create_dir_if_missing(rocks_env_t* env, const char* dirname, size_t dirname_len,
+                                     rocks_status_t** status) {
+  SaveError(status, env->rep->CreateDirIfMissing(std::string(dirname, dirname_len)));
+}
 }
 
 extern "C" {
@@ -162,14 +400,52 @@ void rocks_envoptions_set_rate_limiter(rocks_envoptions_t* opt, ....) {
 */
 }
 
+// Formats lines for a Rust closure. Lines below the logger's level are
+// dropped here, before paying for the formatting.
+struct RustCallbackLogger : public Logger {
+  void* obj;  // rust Box<Box<Fn>>
+
+  RustCallbackLogger(void* fn_obj, InfoLogLevel log_level) : Logger(log_level), obj(fn_obj) {}
+
+  ~RustCallbackLogger() override { rust_logger_drop(this->obj); }
+
+  using Logger::Logv;
+
+  void Logv(const char* format, va_list ap) override { Logv(InfoLogLevel::INFO_LEVEL, format, ap); }
+
+  void Logv(const InfoLogLevel log_level, const char* format, va_list ap) override {
+    if (log_level < GetInfoLogLevel()) {
+      return;
+    }
+    char buf[512];
+    va_list copy;
+    va_copy(copy, ap);
+    int len = vsnprintf(buf, sizeof(buf), format, copy);
+    va_end(copy);
+    if (len < 0) {
+      return;
+    }
+    if (static_cast<size_t>(len) < sizeof(buf)) {
+      rust_logger_call(this->obj, static_cast<int>(log_level), buf, len);
+    } else {
+      std::string msg(len + 1, '\0');
+      vsnprintf(&msg[0], msg.size(), format, ap);
+      rust_logger_call(this->obj, static_cast<int>(log_level), msg.data(), len);
+    }
+  }
+};
+
 extern "C" {
+rocks_logger_t* rocks_logger_create_from_rust(void* fn_obj, int log_level) {
+  return new rocks_logger_t{std::make_shared<RustCallbackLogger>(fn_obj, static_cast<InfoLogLevel>(log_level))};
+}
+
 void rocks_logger_destroy(rocks_logger_t* logger) { delete logger; }
 
 void rocks_logger_log(rocks_logger_t* logger, int log_level, const char* msg_ptr, size_t msg_len) {
   if (logger->rep) {
     auto msg = std::string(msg_ptr, msg_len);
-    va_list ap;
-    logger->rep->Logv(static_cast<InfoLogLevel>(log_level), msg.c_str(), ap);
+    Log(static_cast<InfoLogLevel>(log_level), logger->rep, "%s", msg.c_str());
   }
 }
 
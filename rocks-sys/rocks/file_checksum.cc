@@ -0,0 +1,36 @@
+#include "rocksdb/file_checksum.h"
+
+#include "rocks/ctypes.hpp"
+
+using namespace rocksdb;
+
+using std::shared_ptr;
+
+extern "C" {
+
+rocks_file_checksum_gen_factory_t* rocks_file_checksum_gen_crc32c_factory_create() {
+  rocks_file_checksum_gen_factory_t* factory = new rocks_file_checksum_gen_factory_t;
+  factory->rep = GetFileChecksumGenCrc32cFactory();
+  return factory;
+}
+
+void rocks_file_checksum_gen_factory_destroy(rocks_file_checksum_gen_factory_t* factory) { delete factory; }
+
+size_t rocks_file_checksum_list_size(const rocks_file_checksum_list_t* list) { return list->file_numbers.size(); }
+
+uint64_t rocks_file_checksum_list_file_number(const rocks_file_checksum_list_t* list, size_t index) {
+  return list->file_numbers[index];
+}
+
+const char* rocks_file_checksum_list_checksum(const rocks_file_checksum_list_t* list, size_t index, size_t* len) {
+  *len = list->checksums[index].size();
+  return list->checksums[index].data();
+}
+
+const char* rocks_file_checksum_list_func_name(const rocks_file_checksum_list_t* list, size_t index, size_t* len) {
+  *len = list->func_names[index].size();
+  return list->func_names[index].data();
+}
+
+void rocks_file_checksum_list_destroy(rocks_file_checksum_list_t* list) { delete list; }
+}
@@ -45,6 +45,8 @@ void rocks_iter_get_status(const rocks_iterator_t* iter, rocks_status_t** status
   SaveError(status, std::move(iter->rep->status()));
 }
 
+void rocks_iter_refresh(rocks_iterator_t* iter, rocks_status_t** status) { SaveError(status, iter->rep->Refresh()); }
+
 void rocks_iter_get_property(const rocks_iterator_t* iter, const char* prop, size_t prop_len, void* value,
                              rocks_status_t** status) {
   std::string cval;
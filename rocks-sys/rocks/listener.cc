@@ -30,6 +30,8 @@ uint64_t rocks_flush_job_info_get_smallest_seqno(const FlushJobInfo* info) { ret
 
 uint64_t rocks_flush_job_info_get_largest_seqno(const FlushJobInfo* info) { return info->largest_seqno; }
 
+int rocks_flush_job_info_get_flush_reason(const FlushJobInfo* info) { return static_cast<int>(info->flush_reason); }
+
 rocks_table_props_t* rocks_flush_job_info_get_table_properties(const FlushJobInfo* info) {
   // deleter does nothing, this is a borrowed pointer.
   // since rocks_table_props_t use non-const pointer, const_cast here.
@@ -173,6 +175,33 @@ uint64_t rocks_mem_table_info_get_earliest_seqno(const rocks_mem_table_info_t* i
 uint64_t rocks_mem_table_info_get_num_entries(const rocks_mem_table_info_t* info) { return info->num_entries; }
 uint64_t rocks_mem_table_info_get_num_deletes(const rocks_mem_table_info_t* info) { return info->num_deletes; }
 
+// WriteStallInfo
+typedef WriteStallInfo rocks_write_stall_info_t;
+
+static int WriteStallConditionToInt(WriteStallCondition cond) {
+  switch (cond) {
+    case WriteStallCondition::kNormal:
+      return 0;
+    case WriteStallCondition::kDelayed:
+      return 1;
+    case WriteStallCondition::kStopped:
+      return 2;
+    default:
+      return 0;
+  }
+}
+
+const char* rocks_write_stall_info_get_cf_name(const rocks_write_stall_info_t* info, size_t* len) {
+  *len = info->cf_name.size();
+  return info->cf_name.data();
+}
+int rocks_write_stall_info_get_cur_condition(const rocks_write_stall_info_t* info) {
+  return WriteStallConditionToInt(info->condition.cur);
+}
+int rocks_write_stall_info_get_prev_condition(const rocks_write_stall_info_t* info) {
+  return WriteStallConditionToInt(info->condition.prev);
+}
+
 // ExternalFileIngestionInfo
 typedef ExternalFileIngestionInfo rocks_external_file_ingestion_info_t;
 
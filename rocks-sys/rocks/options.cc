@@ -1,7 +1,11 @@
 #include <iostream>
+#include <map>
+#include <memory>
+#include <mutex>
 
 #include "rocksdb/options.h"
 #include "rocksdb/table.h"
+#include "rocksdb/version.h"
 
 #include "rocks/ctypes.hpp"
 
@@ -9,10 +13,91 @@ using namespace rocksdb;
 
 using std::shared_ptr;
 
+namespace {
+// Bytewise ordering of user keys, each followed by a fixed-size timestamp.
+// Timestamps are little-endian unsigned integers, newer ones sort first.
+// RocksDB 6.29 only ships such a comparator in its test utilities.
+class BytewiseComparatorWithTimestamp : public Comparator {
+ public:
+  BytewiseComparatorWithTimestamp(size_t ts_sz, bool reversed)
+      : Comparator(ts_sz),
+        reversed_(reversed),
+        name_(std::string(reversed ? "rust-rocks.ReverseBytewiseComparatorWithTimestamp."
+                                   : "rust-rocks.BytewiseComparatorWithTimestamp.") +
+              std::to_string(ts_sz)) {}
+
+  const char* Name() const override { return name_.c_str(); }
+
+  int Compare(const Slice& a, const Slice& b) const override {
+    int ret = CompareWithoutTimestamp(a, true, b, true);
+    if (ret != 0) {
+      return ret;
+    }
+    return -CompareTimestamp(ExtractTimestamp(a), ExtractTimestamp(b));
+  }
+
+  int CompareWithoutTimestamp(const Slice& a, bool a_has_ts, const Slice& b, bool b_has_ts) const override {
+    Slice lhs = a_has_ts ? StripTimestamp(a) : a;
+    Slice rhs = b_has_ts ? StripTimestamp(b) : b;
+    int ret = lhs.compare(rhs);
+    return reversed_ ? -ret : ret;
+  }
+
+  int CompareTimestamp(const Slice& ts1, const Slice& ts2) const override {
+    assert(ts1.size() == timestamp_size() && ts2.size() == timestamp_size());
+    // little-endian, compare from the most significant byte
+    for (size_t i = timestamp_size(); i > 0; i--) {
+      unsigned char x = static_cast<unsigned char>(ts1[i - 1]);
+      unsigned char y = static_cast<unsigned char>(ts2[i - 1]);
+      if (x != y) {
+        return x < y ? -1 : 1;
+      }
+    }
+    return 0;
+  }
+
+  void FindShortestSeparator(std::string*, const Slice&) const override {}
+
+  void FindShortSuccessor(std::string*) const override {}
+
+ private:
+  Slice ExtractTimestamp(const Slice& key) const {
+    assert(key.size() >= timestamp_size());
+    return Slice(key.data() + key.size() - timestamp_size(), timestamp_size());
+  }
+
+  Slice StripTimestamp(const Slice& key) const {
+    assert(key.size() >= timestamp_size());
+    return Slice(key.data(), key.size() - timestamp_size());
+  }
+
+  bool reversed_;
+  std::string name_;
+};
+
+// Options only keep a raw pointer to the comparator, so instances live as
+// long as the process, one per timestamp size and direction.
+const Comparator* GetBytewiseComparatorWithTimestamp(size_t ts_sz, bool reversed) {
+  static std::mutex mu;
+  static std::map<std::pair<size_t, bool>, std::unique_ptr<BytewiseComparatorWithTimestamp>> comparators;
+  std::lock_guard<std::mutex> lock(mu);
+  auto& cmp = comparators[std::make_pair(ts_sz, reversed)];
+  if (!cmp) {
+    cmp.reset(new BytewiseComparatorWithTimestamp(ts_sz, reversed));
+  }
+  return cmp.get();
+}
+}  // namespace
+
 // rocks_options_t
 extern "C" {
 
-rocks_options_t* rocks_options_create() { return new rocks_options_t; }
+rocks_options_t* rocks_options_create() {
+  auto opt = new rocks_options_t;
+  // the default since RocksDB 6.14, set anyway so older releases match
+  opt->rep.force_consistency_checks = true;
+  return opt;
+}
 
 void rocks_options_destroy(rocks_options_t* options) { delete options; }
 
@@ -20,7 +105,12 @@ rocks_dboptions_t* rocks_dboptions_create() { return new rocks_dboptions_t; }
 
 void rocks_dboptions_destroy(rocks_dboptions_t* options) { delete options; }
 
-rocks_cfoptions_t* rocks_cfoptions_create() { return new rocks_cfoptions_t; }
+rocks_cfoptions_t* rocks_cfoptions_create() {
+  auto opt = new rocks_cfoptions_t;
+  // the default since RocksDB 6.14, set anyway so older releases match
+  opt->rep.force_consistency_checks = true;
+  return opt;
+}
 
 void rocks_cfoptions_destroy(rocks_cfoptions_t* options) {
   if (options->rep.compaction_filter != nullptr) {
@@ -73,6 +163,15 @@ void rocks_cfoptions_set_bitwise_comparator(rocks_cfoptions_t* opt, unsigned cha
   }
 }
 
+void rocks_cfoptions_set_bitwise_comparator_with_timestamp(rocks_cfoptions_t* opt, size_t ts_sz,
+                                                           unsigned char reversed) {
+  if (ts_sz == 0) {
+    rocks_cfoptions_set_bitwise_comparator(opt, reversed);
+  } else {
+    opt->rep.comparator = GetBytewiseComparatorWithTimestamp(ts_sz, reversed);
+  }
+}
+
 void rocks_cfoptions_set_merge_operator_by_assoc_op_trait(rocks_cfoptions_t* opt, void* op_trait_obj) {
   opt->rep.merge_operator = std::shared_ptr<MergeOperator>(new rocks_associative_mergeoperator_t{op_trait_obj});
 }
@@ -86,13 +185,10 @@ void rocks_cfoptions_set_compaction_filter_by_trait(rocks_cfoptions_t* opt, void
   // FIXME: will leaks
   opt->rep.compaction_filter = new rocks_compaction_filter_t{filter_trait_obj};
 }
-/*
-void rocks_cfoptions_set_compaction_filter_factory(
-                                                 rocks_options_t* opt,
-rocks_compactionfilterfactory_t* factory) { opt->rep.compaction_filter_factory =
-    std::shared_ptr<CompactionFilterFactory>(factory);
+void rocks_cfoptions_set_compaction_filter_factory_by_trait(rocks_cfoptions_t* opt, void* factory_trait_obj) {
+  opt->rep.compaction_filter_factory =
+      std::shared_ptr<CompactionFilterFactory>(new rocks_compaction_filter_factory_t(factory_trait_obj));
 }
-*/
 
 void rocks_cfoptions_set_write_buffer_size(rocks_cfoptions_t* opt, size_t s) { opt->rep.write_buffer_size = s; }
 
@@ -112,6 +208,19 @@ void rocks_cfoptions_set_compression_options(rocks_cfoptions_t* opt, int w_bits,
   opt->rep.compression_opts.max_dict_bytes = max_dict_bytes;
 }
 
+void rocks_cfoptions_set_bottommost_compression_options(rocks_cfoptions_t* opt, int w_bits, int level, int strategy,
+                                                        uint32_t max_dict_bytes) {
+  opt->rep.bottommost_compression_opts.window_bits = w_bits;
+  opt->rep.bottommost_compression_opts.level = level;
+  opt->rep.bottommost_compression_opts.strategy = strategy;
+  opt->rep.bottommost_compression_opts.max_dict_bytes = max_dict_bytes;
+  opt->rep.bottommost_compression_opts.enabled = true;
+}
+
+void rocks_cfoptions_set_sample_for_compression(rocks_cfoptions_t* opt, uint64_t v) {
+  opt->rep.sample_for_compression = v;
+}
+
 void rocks_cfoptions_set_level0_file_num_compaction_trigger(rocks_cfoptions_t* opt, int n) {
   opt->rep.level0_file_num_compaction_trigger = n;
 }
@@ -188,6 +297,10 @@ void rocks_cfoptions_set_max_write_buffer_number_to_maintain(rocks_cfoptions_t*
   opt->rep.max_write_buffer_number_to_maintain = n;
 }
 
+void rocks_cfoptions_set_max_write_buffer_size_to_maintain(rocks_cfoptions_t* opt, int64_t n) {
+  opt->rep.max_write_buffer_size_to_maintain = n;
+}
+
 void rocks_cfoptions_set_inplace_update_support(rocks_cfoptions_t* opt, unsigned char v) {
   opt->rep.inplace_update_support = v;
 }
@@ -202,6 +315,10 @@ void rocks_cfoptions_set_memtable_prefix_bloom_size_ratio(rocks_cfoptions_t* opt
   opt->rep.memtable_prefix_bloom_size_ratio = v;
 }
 
+void rocks_cfoptions_set_memtable_whole_key_filtering(rocks_cfoptions_t* opt, unsigned char v) {
+  opt->rep.memtable_whole_key_filtering = v;
+}
+
 void rocks_cfoptions_set_memtable_huge_page_size(rocks_cfoptions_t* opt, size_t v) {
   opt->rep.memtable_huge_page_size = v;
 }
@@ -270,6 +387,14 @@ void rocks_cfoptions_set_max_bytes_for_level_multiplier_additional(rocks_cfoptio
 
 void rocks_cfoptions_set_max_compaction_bytes(rocks_cfoptions_t* opt, uint64_t n) { opt->rep.max_compaction_bytes = n; }
 
+// Same as the sanitization at DB open, see SanitizeOptions() in column_family.cc
+uint64_t rocks_cfoptions_get_effective_max_compaction_bytes(const rocks_cfoptions_t* opt) {
+  if (opt->rep.max_compaction_bytes == 0) {
+    return opt->rep.target_file_size_base * 25;
+  }
+  return opt->rep.max_compaction_bytes;
+}
+
 void rocks_cfoptions_set_soft_pending_compaction_bytes_limit(rocks_cfoptions_t* opt, uint64_t v) {
   opt->rep.soft_pending_compaction_bytes_limit = v;
 }
@@ -300,6 +425,10 @@ void rocks_cfoptions_set_max_sequential_skip_in_iterations(rocks_cfoptions_t* op
 }
 
 // memtable_factory
+void rocks_cfoptions_set_memtable_skip_list_rep(rocks_cfoptions_t* opt, size_t lookahead) {
+  opt->rep.memtable_factory.reset(new rocksdb::SkipListFactory(lookahead));
+}
+
 void rocks_cfoptions_set_memtable_vector_rep(rocks_cfoptions_t* opt, size_t count) {
   opt->rep.memtable_factory.reset(new rocksdb::VectorRepFactory(count));
 }
@@ -348,6 +477,22 @@ void rocks_cfoptions_set_report_bg_io_stats(rocks_cfoptions_t* opt, unsigned cha
   opt->rep.report_bg_io_stats = v;
 }
 
+// blob files
+
+void rocks_cfoptions_set_enable_blob_files(rocks_cfoptions_t* opt, unsigned char v) { opt->rep.enable_blob_files = v; }
+
+void rocks_cfoptions_set_min_blob_size(rocks_cfoptions_t* opt, uint64_t v) { opt->rep.min_blob_size = v; }
+
+void rocks_cfoptions_set_blob_file_size(rocks_cfoptions_t* opt, uint64_t v) { opt->rep.blob_file_size = v; }
+
+void rocks_cfoptions_set_blob_compression_type(rocks_cfoptions_t* opt, int t) {
+  opt->rep.blob_compression_type = static_cast<CompressionType>(t);
+}
+
+void rocks_cfoptions_set_enable_blob_garbage_collection(rocks_cfoptions_t* opt, unsigned char v) {
+  opt->rep.enable_blob_garbage_collection = v;
+}
+
 // dboptions
 
 void rocks_dboptions_optimize_for_small_db(rocks_dboptions_t* opt) { opt->rep.OptimizeForSmallDb(); }
@@ -376,12 +521,21 @@ void rocks_dboptions_set_ratelimiter(rocks_dboptions_t* opt, rocks_ratelimiter_t
   }
 }
 
-// sst_file_manager
-/*
-void rocks_dboptions_set_sst_file_manager(rocks_dboptions_t* opt,
-rocks_sst_file_manager_t* manager) { opt->rep.sst_file_manager = manager->rep;
+void rocks_dboptions_set_file_checksum_gen_factory(rocks_dboptions_t* opt, rocks_file_checksum_gen_factory_t* factory) {
+  if (factory != nullptr) {
+    opt->rep.file_checksum_gen_factory = factory->rep;
+  } else {
+    opt->rep.file_checksum_gen_factory.reset();
+  }
+}
+
+void rocks_dboptions_set_sst_file_manager(rocks_dboptions_t* opt, rocks_sst_file_manager_t* manager) {
+  if (manager != nullptr) {
+    opt->rep.sst_file_manager = manager->rep;
+  } else {
+    opt->rep.sst_file_manager.reset();
+  }
 }
-*/
 
 void rocks_dboptions_set_info_log(rocks_dboptions_t* opt, rocks_logger_t* l) {
   if (l) {
@@ -433,6 +587,14 @@ void rocks_dboptions_set_delete_obsolete_files_period_micros(rocks_dboptions_t*
 }
 void rocks_dboptions_set_max_background_jobs(rocks_dboptions_t* opt, int n) { opt->rep.max_background_jobs = n; }
 
+void rocks_dboptions_set_max_background_compactions(rocks_dboptions_t* opt, int n) {
+  opt->rep.max_background_compactions = n;
+}
+
+void rocks_dboptions_set_max_background_flushes(rocks_dboptions_t* opt, int n) {
+  opt->rep.max_background_flushes = n;
+}
+
 void rocks_dboptions_set_max_subcompactions(rocks_dboptions_t* opt, uint32_t n) { opt->rep.max_subcompactions = n; }
 
 void rocks_dboptions_set_max_log_file_size(rocks_dboptions_t* opt, size_t v) { opt->rep.max_log_file_size = v; }
@@ -481,6 +643,14 @@ void rocks_dboptions_set_stats_dump_period_sec(rocks_dboptions_t* opt, unsigned
   opt->rep.stats_dump_period_sec = v;
 }
 
+void rocks_dboptions_set_stats_persist_period_sec(rocks_dboptions_t* opt, unsigned int v) {
+  opt->rep.stats_persist_period_sec = v;
+}
+
+void rocks_dboptions_set_stats_history_buffer_size(rocks_dboptions_t* opt, size_t v) {
+  opt->rep.stats_history_buffer_size = v;
+}
+
 void rocks_dboptions_set_advise_random_on_open(rocks_dboptions_t* opt, unsigned char v) {
   opt->rep.advise_random_on_open = v;
 }
@@ -562,6 +732,18 @@ void rocks_dboptions_set_skip_stats_update_on_db_open(rocks_dboptions_t* opt, un
   opt->rep.skip_stats_update_on_db_open = v;
 }
 
+void rocks_dboptions_set_skip_checking_sst_file_sizes_on_db_open(rocks_dboptions_t* opt, unsigned char v) {
+  opt->rep.skip_checking_sst_file_sizes_on_db_open = v;
+}
+
+void rocks_dboptions_set_best_efforts_recovery(rocks_dboptions_t* opt, unsigned char v) {
+  opt->rep.best_efforts_recovery = v;
+}
+
+void rocks_dboptions_set_track_and_verify_wals_in_manifest(rocks_dboptions_t* opt, unsigned char v) {
+  opt->rep.track_and_verify_wals_in_manifest = v;
+}
+
 void rocks_dboptions_set_wal_recovery_mode(rocks_dboptions_t* opt, int mode) {
   opt->rep.wal_recovery_mode = static_cast<WALRecoveryMode>(mode);
 }
@@ -569,7 +751,13 @@ void rocks_dboptions_set_wal_recovery_mode(rocks_dboptions_t* opt, int mode) {
 void rocks_dboptions_set_allow_2pc(rocks_dboptions_t* opt, unsigned char v) { opt->rep.allow_2pc = v; }
 
 // FIXME: mem leaks?
-void rocks_dboptions_set_row_cache(rocks_dboptions_t* opt, rocks_cache_t* cache) { opt->rep.row_cache = cache->rep; }
+void rocks_dboptions_set_row_cache(rocks_dboptions_t* opt, rocks_cache_t* cache) {
+  if (cache == nullptr) {
+    opt->rep.row_cache = nullptr;
+  } else {
+    opt->rep.row_cache = cache->rep;
+  }
+}
 
 /*
 void rocks_dboptions_set_wal_filter(rocks_dboptions_t* opt, rocks_wal_filter_t*
@@ -595,6 +783,8 @@ void rocks_dboptions_set_allow_ingest_behind(rocks_dboptions_t* opt, unsigned ch
   opt->rep.allow_ingest_behind = v;
 }
 
+void rocks_dboptions_set_preserve_deletes(rocks_dboptions_t* opt, unsigned char v) { opt->rep.preserve_deletes = v; }
+
 /*
 // Deprecated by two_write_queues
 void rocks_dboptions_set_concurrent_prepare(rocks_dboptions_t* opt, unsigned char v) {
@@ -610,6 +800,20 @@ void rocks_options_prepare_for_bulk_load(rocks_options_t* opt) { opt->rep.Prepar
 
 void rocks_options_optimize_for_small_db(rocks_options_t* opt) { opt->rep.OptimizeForSmallDb(); }
 
+const char* rocks_options_get_comparator_name(rocks_options_t* opt) {
+  return opt->rep.comparator == nullptr ? nullptr : opt->rep.comparator->Name();
+}
+
+const char* rocks_options_get_merge_operator_name(rocks_options_t* opt) {
+  return opt->rep.merge_operator == nullptr ? nullptr : opt->rep.merge_operator->Name();
+}
+
+const char* rocks_options_get_prefix_extractor_name(rocks_options_t* opt) {
+  return opt->rep.prefix_extractor == nullptr ? nullptr : opt->rep.prefix_extractor->Name();
+}
+
+int rocks_options_get_num_levels(rocks_options_t* opt) { return opt->rep.num_levels; }
+
 /*
   char *rocks_options_statistics_get_string(rocks_options_t *opt) {
   rocksdb::Statistics *statistics = opt->rep.statistics.get();
@@ -652,6 +856,16 @@ void rocks_readoptions_set_iterate_upper_bound(rocks_readoptions_t* opt, const c
   }
 }
 
+void rocks_readoptions_set_timestamp(rocks_readoptions_t* opt, const char* ts, size_t tslen) {
+  if (ts == nullptr) {
+    opt->timestamp = Slice();
+    opt->rep.timestamp = nullptr;
+  } else {
+    opt->timestamp = Slice(ts, tslen);
+    opt->rep.timestamp = &opt->timestamp;
+  }
+}
+
 void rocks_readoptions_set_read_tier(rocks_readoptions_t* opt, int v) {
   opt->rep.read_tier = static_cast<rocksdb::ReadTier>(v);
 }
@@ -683,6 +897,28 @@ void rocks_readoptions_set_ignore_range_deletions(rocks_readoptions_t* opt, unsi
 void rocks_readoptions_set_background_purge_on_iterator_cleanup(rocks_readoptions_t* opt, unsigned char v) {
   opt->rep.background_purge_on_iterator_cleanup = v;
 }
+
+void rocks_readoptions_set_auto_prefix_mode(rocks_readoptions_t* opt, unsigned char v) {
+  opt->rep.auto_prefix_mode = v;
+}
+
+void rocks_readoptions_set_deadline(rocks_readoptions_t* opt, uint64_t microseconds) {
+  opt->rep.deadline = std::chrono::microseconds(microseconds);
+}
+
+void rocks_readoptions_set_io_timeout(rocks_readoptions_t* opt, uint64_t microseconds) {
+  opt->rep.io_timeout = std::chrono::microseconds(microseconds);
+}
+
+void rocks_readoptions_set_async_io(rocks_readoptions_t* opt, unsigned char v) {
+#if ROCKSDB_MAJOR >= 7
+  opt->rep.async_io = v;
+#else
+  // ReadOptions::async_io first shipped in RocksDB 7.0, older releases always read synchronously
+  (void)opt;
+  (void)v;
+#endif
+}
 }
 
 extern "C" {
@@ -701,6 +937,16 @@ void rocks_writeoptions_set_ignore_missing_column_families(rocks_writeoptions_t*
 void rocks_writeoptions_set_no_slowdown(rocks_writeoptions_t* opt, unsigned char v) { opt->rep.no_slowdown = v; }
 
 void rocks_writeoptions_set_low_pri(rocks_writeoptions_t* opt, unsigned char v) { opt->rep.low_pri = v; }
+
+void rocks_writeoptions_set_rate_limiter_priority(rocks_writeoptions_t* opt, int v) {
+#if ROCKSDB_MAJOR > 7 || (ROCKSDB_MAJOR == 7 && ROCKSDB_MINOR >= 5)
+  opt->rep.rate_limiter_priority = static_cast<Env::IOPriority>(v);
+#else
+  // WriteOptions::rate_limiter_priority first shipped in RocksDB 7.5, older releases never charge WAL writes
+  (void)opt;
+  (void)v;
+#endif
+}
 }
 
 extern "C" {
@@ -727,6 +973,10 @@ void rocks_compactrange_options_set_target_path_id(rocks_compactrange_options_t*
 void rocks_compactrange_options_set_bottommost_level_compaction(rocks_compactrange_options_t* opt, int v) {
   opt->rep.bottommost_level_compaction = static_cast<BottommostLevelCompaction>(v);
 }
+
+void rocks_compactrange_options_set_max_subcompactions(rocks_compactrange_options_t* opt, uint32_t v) {
+  opt->rep.max_subcompactions = v;
+}
 }
 
 extern "C" {
@@ -754,6 +1004,21 @@ void rocks_ingestexternalfile_options_set_allow_blocking_flush(rocks_ingestexter
 void rocks_ingestexternalfile_options_set_ingest_behind(rocks_ingestexternalfile_options_t* opt, unsigned char v) {
   opt->rep.ingest_behind = v;
 }
+
+void rocks_ingestexternalfile_options_set_failed_move_fall_back_to_copy(rocks_ingestexternalfile_options_t* opt,
+                                                                         unsigned char v) {
+  opt->rep.failed_move_fall_back_to_copy = v;
+}
+
+void rocks_ingestexternalfile_options_set_write_global_seqno(rocks_ingestexternalfile_options_t* opt,
+                                                             unsigned char v) {
+  opt->rep.write_global_seqno = v;
+}
+
+void rocks_ingestexternalfile_options_set_verify_checksums_before_ingest(rocks_ingestexternalfile_options_t* opt,
+                                                                         unsigned char v) {
+  opt->rep.verify_checksums_before_ingest = v;
+}
 }
 
 extern "C" {
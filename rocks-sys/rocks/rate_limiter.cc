@@ -16,5 +16,32 @@ rocks_ratelimiter_t* rocks_ratelimiter_create(int64_t rate_bytes_per_sec, int64_
   return rate_limiter;
 }
 
+rocks_ratelimiter_t* rocks_ratelimiter_copy(rocks_ratelimiter_t* limiter) {
+  return new rocks_ratelimiter_t{limiter->rep};
+}
+
 void rocks_ratelimiter_destroy(rocks_ratelimiter_t* limiter) { delete limiter; }
+
+int64_t rocks_ratelimiter_get_single_burst_bytes(rocks_ratelimiter_t* limiter) {
+  return limiter->rep->GetSingleBurstBytes();
+}
+
+void rocks_ratelimiter_request(rocks_ratelimiter_t* limiter, int64_t bytes, int pri, rocks_status_t** status) {
+  if (pri < Env::IO_LOW || pri >= Env::IO_TOTAL) {
+    SaveError(status, Status::InvalidArgument("invalid IO priority for a rate limiter request"));
+    return;
+  }
+  if (bytes <= 0) {
+    return;  // nothing to wait for, validated only
+  }
+  limiter->rep->Request(bytes, static_cast<Env::IOPriority>(pri), nullptr);
+}
+
+int64_t rocks_ratelimiter_get_total_bytes_through(rocks_ratelimiter_t* limiter, int pri) {
+  return limiter->rep->GetTotalBytesThrough(static_cast<Env::IOPriority>(pri));
+}
+
+int64_t rocks_ratelimiter_get_total_requests(rocks_ratelimiter_t* limiter, int pri) {
+  return limiter->rep->GetTotalRequests(static_cast<Env::IOPriority>(pri));
+}
 }
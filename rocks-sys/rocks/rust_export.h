@@ -36,6 +36,25 @@ extern char rust_compaction_filter_ignore_snapshots(void* f);
 
 extern void rust_compaction_filter_drop(void* f);
 
+// filters created by a factory are owned by the C++ side
+extern void rust_compaction_filter_drop_owned(void* f);
+
+/* cache */
+extern void rust_cache_entry_call(void* f, const char* key, size_t key_len, size_t charge);
+
+/* logger */
+extern void rust_logger_call(void* f, int log_level, const char* msg, size_t len);
+
+extern void rust_logger_drop(void* f);
+
+/* compaction filter factory */
+extern void* rust_compaction_filter_factory_create(void* f, unsigned char is_full_compaction,
+                                                   unsigned char is_manual_compaction, uint32_t column_family_id);
+
+extern const char* rust_compaction_filter_factory_name(void* f);
+
+extern void rust_compaction_filter_factory_drop(void* f);
+
 /* slice transform */
 extern void rust_slice_transform_call(void* t, const Slice* key, char* const* ret, size_t* ret_len);
 
@@ -45,6 +64,17 @@ extern char rust_slice_transform_in_domain(void* t, const Slice* key);
 
 extern void rust_slice_transform_drop(void* t);
 
+/* block cipher */
+extern const char* rust_block_cipher_name(void* c);
+
+extern size_t rust_block_cipher_block_size(void* c);
+
+extern void rust_block_cipher_encrypt(void* c, char* data);
+
+extern void rust_block_cipher_decrypt(void* c, char* data);
+
+extern void rust_block_cipher_drop(void* c);
+
 /* merge operator*/
 
 extern int32_t rust_associative_merge_operator_call(void* op, const Slice* key, const Slice* existing_value,
@@ -141,6 +171,8 @@ extern void rust_event_listener_on_table_file_creation_started(void* l, const Ta
 
 extern void rust_event_listener_on_memtable_sealed(void* l, const MemTableInfo*);
 
+extern void rust_event_listener_on_stall_conditions_changed(void* l, const WriteStallInfo*);
+
 struct rocks_column_family_handle_t;
 
 extern void rust_event_listener_on_column_family_handle_deletion_started(void* l, rocks_column_family_handle_t*);
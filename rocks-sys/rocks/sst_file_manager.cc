@@ -0,0 +1,50 @@
+#include "rocksdb/sst_file_manager.h"
+
+#include "rocks/ctypes.hpp"
+
+using namespace rocksdb;
+
+using std::shared_ptr;
+
+extern "C" {
+rocks_sst_file_manager_t* rocks_sst_file_manager_create(rocks_env_t* env, rocks_logger_t* info_log,
+                                                        const char* trash_dir, size_t trash_dir_len,
+                                                        int64_t rate_bytes_per_sec, unsigned char delete_existing_trash,
+                                                        rocks_status_t** status) {
+  Status st;
+  auto logger = info_log == nullptr ? nullptr : info_log->rep;
+  auto manager = NewSstFileManager(env->rep, logger, std::string(trash_dir, trash_dir_len), rate_bytes_per_sec,
+                                   delete_existing_trash, &st);
+  if (SaveError(status, std::move(st))) {
+    delete manager;
+    return nullptr;
+  }
+  return new rocks_sst_file_manager_t{shared_ptr<SstFileManager>(manager)};
+}
+
+rocks_sst_file_manager_t* rocks_sst_file_manager_copy(rocks_sst_file_manager_t* manager) {
+  return new rocks_sst_file_manager_t{manager->rep};
+}
+
+void rocks_sst_file_manager_destroy(rocks_sst_file_manager_t* manager) { delete manager; }
+
+void rocks_sst_file_manager_set_max_allowed_space_usage(rocks_sst_file_manager_t* manager, uint64_t max_allowed_space) {
+  manager->rep->SetMaxAllowedSpaceUsage(max_allowed_space);
+}
+
+unsigned char rocks_sst_file_manager_is_max_allowed_space_reached(rocks_sst_file_manager_t* manager) {
+  return manager->rep->IsMaxAllowedSpaceReached();
+}
+
+uint64_t rocks_sst_file_manager_get_total_size(rocks_sst_file_manager_t* manager) {
+  return manager->rep->GetTotalSize();
+}
+
+int64_t rocks_sst_file_manager_get_delete_rate_bytes_per_second(rocks_sst_file_manager_t* manager) {
+  return manager->rep->GetDeleteRateBytesPerSecond();
+}
+
+void rocks_sst_file_manager_set_delete_rate_bytes_per_second(rocks_sst_file_manager_t* manager, int64_t delete_rate) {
+  manager->rep->SetDeleteRateBytesPerSecond(delete_rate);
+}
+}
@@ -0,0 +1,39 @@
+#include "rocksdb/sst_file_reader.h"
+
+#include "rocks/ctypes.hpp"
+
+using namespace rocksdb;
+
+using std::shared_ptr;
+
+extern "C" {
+rocks_sst_file_reader_t* rocks_sst_file_reader_create(const rocks_options_t* options) {
+  return new rocks_sst_file_reader_t{new SstFileReader(options->rep)};
+}
+
+void rocks_sst_file_reader_destroy(rocks_sst_file_reader_t* reader) {
+  delete reader->rep;
+  delete reader;
+}
+
+void rocks_sst_file_reader_open(rocks_sst_file_reader_t* reader, const char* file_path, const size_t file_path_len,
+                                rocks_status_t** status) {
+  auto st = reader->rep->Open(std::string(file_path, file_path_len));
+  SaveError(status, std::move(st));
+}
+
+rocks_iterator_t* rocks_sst_file_reader_new_iterator(rocks_sst_file_reader_t* reader,
+                                                     const rocks_readoptions_t* options) {
+  return new rocks_iterator_t{reader->rep->NewIterator(options->rep)};
+}
+
+rocks_table_props_t* rocks_sst_file_reader_get_table_properties(rocks_sst_file_reader_t* reader) {
+  return new rocks_table_props_t{reader->rep->GetTableProperties()};
+}
+
+void rocks_sst_file_reader_verify_checksum(rocks_sst_file_reader_t* reader, const rocks_readoptions_t* options,
+                                           rocks_status_t** status) {
+  auto st = reader->rep->VerifyChecksum(options->rep);
+  SaveError(status, std::move(st));
+}
+}
@@ -67,6 +67,16 @@ rocks_sst_file_writer_t* rocks_sst_file_writer_create_from_rust_comparator(const
   return result;
 }
 
+rocks_sst_file_writer_t* rocks_sst_file_writer_create(const rocks_envoptions_t* env_options,
+                                                      const rocks_options_t* options,
+                                                      rocks_column_family_handle_t* column_family,
+                                                      unsigned char invalidate_page_cache) {
+  rocks_sst_file_writer_t* result = new rocks_sst_file_writer_t;
+  result->rep = new SstFileWriter(env_options->rep, options->rep,
+                                  (column_family != nullptr) ? column_family->rep : nullptr, invalidate_page_cache != 0);
+  return result;
+}
+
 void rocks_sst_file_writer_destroy(rocks_sst_file_writer_t* writer) {
   delete writer->rep;
   delete writer;
@@ -96,6 +106,13 @@ void rocks_sst_file_writer_delete(rocks_sst_file_writer_t* writer, const char* k
   SaveError(status, std::move(st));
 }
 
+void rocks_sst_file_writer_delete_range(rocks_sst_file_writer_t* writer, const char* begin_key,
+                                        const size_t begin_key_len, const char* end_key, const size_t end_key_len,
+                                        rocks_status_t** status) {
+  auto st = writer->rep->DeleteRange(Slice(begin_key, begin_key_len), Slice(end_key, end_key_len));
+  SaveError(status, std::move(st));
+}
+
 void rocks_sst_file_writer_finish(rocks_sst_file_writer_t* writer, rocks_external_sst_file_info_t* info,
                                   rocks_status_t** status) {
   auto info_ptr = (info != nullptr) ? &info->rep : nullptr;
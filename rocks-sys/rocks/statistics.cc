@@ -56,4 +56,21 @@ void rocks_statistics_to_string(rocks_statistics_t* stat,
 unsigned char rocks_statistics_hist_enabled_for_type(rocks_statistics_t* stat, uint32_t type) {
   return stat->rep->HistEnabledForType(type);
 }
+
+void rocks_statistics_reset(rocks_statistics_t* stat, rocks_status_t** status) {
+  SaveError(status, stat->rep->Reset());
+}
+
+// Histogram IDs shift between RocksDB versions as histograms are added, look
+// them up by their stable names instead.
+unsigned char rocks_statistics_histogram_type_by_name(const char* name, size_t len, uint32_t* type) {
+  std::string needle(name, len);
+  for (const auto& entry : HistogramsNameMap) {
+    if (entry.second == needle) {
+      *type = entry.first;
+      return 1;
+    }
+  }
+  return 0;
+}
 }
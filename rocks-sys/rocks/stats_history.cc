@@ -0,0 +1,39 @@
+#include "rocksdb/stats_history.h"
+
+#include "rocks/ctypes.hpp"
+
+using namespace rocksdb;
+
+extern "C" {
+void rocks_stats_history_iterator_destroy(rocks_stats_history_iterator_t* it) { delete it; }
+
+unsigned char rocks_stats_history_iterator_valid(rocks_stats_history_iterator_t* it) { return it->rep->Valid(); }
+
+void rocks_stats_history_iterator_next(rocks_stats_history_iterator_t* it) { it->rep->Next(); }
+
+void rocks_stats_history_iterator_status(rocks_stats_history_iterator_t* it, rocks_status_t** status) {
+  SaveError(status, it->rep->status());
+}
+
+uint64_t rocks_stats_history_iterator_get_stats_time(rocks_stats_history_iterator_t* it) {
+  return it->rep->GetStatsTime();
+}
+
+// copies the stats map of the current position, to be read by index
+size_t rocks_stats_history_iterator_load_stats_map(rocks_stats_history_iterator_t* it) {
+  const auto& stats_map = it->rep->GetStatsMap();
+  it->stats_map.assign(stats_map.begin(), stats_map.end());
+  return it->stats_map.size();
+}
+
+const char* rocks_stats_history_iterator_stats_map_nth_key(rocks_stats_history_iterator_t* it, size_t nth,
+                                                           size_t* len) {
+  auto& key = it->stats_map[nth].first;
+  *len = key.size();
+  return key.data();
+}
+
+uint64_t rocks_stats_history_iterator_stats_map_nth_value(rocks_stats_history_iterator_t* it, size_t nth) {
+  return it->stats_map[nth].second;
+}
+}
@@ -65,6 +65,15 @@ void rocks_block_based_table_options_set_index_type(rocks_block_based_table_opti
   options->rep.index_type = static_cast<BlockBasedTableOptions::IndexType>(v);
 }
 
+void rocks_block_based_table_options_set_data_block_index_type(rocks_block_based_table_options_t* options, int v) {
+  options->rep.data_block_index_type = static_cast<BlockBasedTableOptions::DataBlockIndexType>(v);
+}
+
+void rocks_block_based_table_options_set_data_block_hash_table_util_ratio(rocks_block_based_table_options_t* options,
+                                                                         double v) {
+  options->rep.data_block_hash_table_util_ratio = v;
+}
+
 void rocks_block_based_table_options_set_hash_index_allow_collision(rocks_block_based_table_options_t* options,
                                                                     unsigned char v) {
   options->rep.hash_index_allow_collision = v;
@@ -79,7 +88,9 @@ void rocks_block_based_table_options_set_no_block_cache(rocks_block_based_table_
 
 void rocks_block_based_table_options_set_block_cache(rocks_block_based_table_options_t* options,
                                                      rocks_cache_t* block_cache) {
-  if (block_cache) {
+  if (block_cache == nullptr) {
+    options->rep.block_cache = nullptr;
+  } else {
     options->rep.block_cache = block_cache->rep;
   }
 }
@@ -95,7 +106,9 @@ void rocks_block_based_table_options_set_persistent_cache(rocks_block_based_tabl
 
 void rocks_block_based_table_options_set_block_cache_compressed(rocks_block_based_table_options_t* options,
                                                                 rocks_cache_t* block_cache_compressed) {
-  if (block_cache_compressed) {
+  if (block_cache_compressed == nullptr) {
+    options->rep.block_cache_compressed = nullptr;
+  } else {
     options->rep.block_cache_compressed = block_cache_compressed->rep;
   }
 }
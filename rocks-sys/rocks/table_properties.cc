@@ -58,8 +58,15 @@ uint64_t rocks_table_props_get_raw_key_size(rocks_table_props_t* prop) { return
 uint64_t rocks_table_props_get_raw_value_size(rocks_table_props_t* prop) { return prop->rep->raw_value_size; }
 uint64_t rocks_table_props_get_num_data_blocks(rocks_table_props_t* prop) { return prop->rep->num_data_blocks; }
 uint64_t rocks_table_props_get_num_entries(rocks_table_props_t* prop) { return prop->rep->num_entries; }
+uint64_t rocks_table_props_get_num_deletions(rocks_table_props_t* prop) { return prop->rep->num_deletions; }
 uint64_t rocks_table_props_get_format_version(rocks_table_props_t* prop) { return prop->rep->format_version; }
 uint64_t rocks_table_props_get_fixed_key_len(rocks_table_props_t* prop) { return prop->rep->fixed_key_len; }
+uint64_t rocks_table_props_get_slow_compression_estimated_data_size(rocks_table_props_t* prop) {
+  return prop->rep->slow_compression_estimated_data_size;
+}
+uint64_t rocks_table_props_get_fast_compression_estimated_data_size(rocks_table_props_t* prop) {
+  return prop->rep->fast_compression_estimated_data_size;
+}
 uint32_t rocks_table_props_get_column_family_id(rocks_table_props_t* prop) { return prop->rep->column_family_id; }
 const char* rocks_table_props_get_column_family_name(rocks_table_props_t* prop, size_t* len) {
   *len = prop->rep->column_family_name.size();
@@ -13,6 +13,10 @@ rocks_writebatch_t* rocks_writebatch_create_with_reserved_bytes(size_t size) {
   return new rocks_writebatch_t{std::unique_ptr<WriteBatch>(new WriteBatch(size))};
 }
 
+rocks_writebatch_t* rocks_writebatch_create_with_max_bytes(size_t reserved_bytes, size_t max_bytes) {
+  return new rocks_writebatch_t{std::unique_ptr<WriteBatch>(new WriteBatch(reserved_bytes, max_bytes)), max_bytes};
+}
+
 rocks_writebatch_t* rocks_writebatch_create_from(const char* rep, size_t size) {
   return new rocks_writebatch_t{std::unique_ptr<WriteBatch>(new WriteBatch(std::string(rep, size)))};
 }
@@ -23,6 +27,8 @@ void rocks_writebatch_clear(rocks_writebatch_t* b) { b->rep->Clear(); }
 
 int rocks_writebatch_count(rocks_writebatch_t* b) { return b->rep->Count(); }
 
+size_t rocks_writebatch_get_data_size(rocks_writebatch_t* b) { return b->rep->GetDataSize(); }
+
 void rocks_writebatch_put(rocks_writebatch_t* b, const char* key, size_t klen, const char* val, size_t vlen) {
   b->rep->Put(Slice(key, klen), Slice(val, vlen));
 }
@@ -191,7 +197,30 @@ void rocks_writebatch_rollback_to_save_point(rocks_writebatch_t* b, rocks_status
 }
 
 rocks_writebatch_t* rocks_writebatch_copy(rocks_writebatch_t* b) {
-  return new rocks_writebatch_t{std::unique_ptr<WriteBatch>(new WriteBatch(*b->rep))};
+  return new rocks_writebatch_t{std::unique_ptr<WriteBatch>(new WriteBatch(*b->rep)), b->max_bytes};
+}
+
+// Same as WriteBatchInternal::Append, which is not part of the public headers:
+// concatenate the records after the 12-byte header (8-byte sequence, 4-byte count)
+// and add up the counts. Save points recorded on `dst` do not survive, its size limit does.
+void rocks_writebatch_append(rocks_writebatch_t* dst, rocks_writebatch_t* src, rocks_status_t** status) {
+  const size_t kHeader = 12;
+  const std::string& src_rep = src->rep->Data();
+  if (src_rep.size() <= kHeader) {
+    return;
+  }
+  std::string rep = dst->rep->Data();
+  rep.append(src_rep, kHeader, std::string::npos);
+  if (dst->max_bytes > 0 && rep.size() > dst->max_bytes) {
+    SaveError(status, Status::MemoryLimit());
+    return;
+  }
+  uint32_t count = static_cast<uint32_t>(dst->rep->Count() + src->rep->Count());
+  for (int i = 0; i < 4; i++) {
+    rep[8 + i] = static_cast<char>((count >> (8 * i)) & 0xff);
+  }
+  dst->rep.reset(new WriteBatch(rep));
+  dst->rep->SetMaxBytes(dst->max_bytes);
 }
 
 unsigned char rocks_writebatch_has_put(rocks_writebatch_t* b) { return b->rep->HasPut(); }
@@ -87,6 +87,21 @@ pub struct rocks_ratelimiter_t {
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
+pub struct rocks_sst_file_manager_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct rocks_file_checksum_gen_factory_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct rocks_file_checksum_list_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
 pub struct rocks_envoptions_t {
     _unused: [u8; 0],
 }
@@ -177,6 +192,11 @@ pub struct rocks_c_comparator_t {
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
+pub struct rocks_sst_file_reader_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
 pub struct rocks_sst_file_writer_t {
     _unused: [u8; 0],
 }
@@ -242,6 +262,11 @@ pub struct rocks_transaction_log_iterator_t {
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
+pub struct rocks_stats_history_iterator_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
 pub struct rocks_table_props_collection_t {
     _unused: [u8; 0],
 }
@@ -337,6 +362,11 @@ pub struct rocks_external_file_ingestion_info_t {
 }
 #[repr(C)]
 #[derive(Copy, Clone)]
+pub struct rocks_write_stall_info_t {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
 pub struct rocks_thread_status_t {
     _unused: [u8; 0],
 }
@@ -456,9 +486,22 @@ extern "C" {
         filter_trait_obj: *mut ::std::os::raw::c_void,
     );
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_compaction_filter_factory_by_trait(
+        opt: *mut rocks_cfoptions_t,
+        factory_trait_obj: *mut ::std::os::raw::c_void,
+    );
+}
 extern "C" {
     pub fn rocks_cfoptions_set_bitwise_comparator(opt: *mut rocks_cfoptions_t, reversed: ::std::os::raw::c_uchar);
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_bitwise_comparator_with_timestamp(
+        opt: *mut rocks_cfoptions_t,
+        ts_sz: usize,
+        reversed: ::std::os::raw::c_uchar,
+    );
+}
 extern "C" {
     pub fn rocks_cfoptions_set_write_buffer_size(opt: *mut rocks_cfoptions_t, s: usize);
 }
@@ -477,6 +520,18 @@ extern "C" {
         max_dict_bytes: u32,
     );
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_bottommost_compression_options(
+        opt: *mut rocks_cfoptions_t,
+        w_bits: ::std::os::raw::c_int,
+        level: ::std::os::raw::c_int,
+        strategy: ::std::os::raw::c_int,
+        max_dict_bytes: u32,
+    );
+}
+extern "C" {
+    pub fn rocks_cfoptions_set_sample_for_compression(opt: *mut rocks_cfoptions_t, v: u64);
+}
 extern "C" {
     pub fn rocks_cfoptions_set_level0_file_num_compaction_trigger(
         opt: *mut rocks_cfoptions_t,
@@ -534,6 +589,9 @@ extern "C" {
         n: ::std::os::raw::c_int,
     );
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_max_write_buffer_size_to_maintain(opt: *mut rocks_cfoptions_t, n: i64);
+}
 extern "C" {
     pub fn rocks_cfoptions_set_inplace_update_support(opt: *mut rocks_cfoptions_t, v: ::std::os::raw::c_uchar);
 }
@@ -543,6 +601,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_cfoptions_set_memtable_prefix_bloom_size_ratio(opt: *mut rocks_cfoptions_t, v: f64);
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_memtable_whole_key_filtering(opt: *mut rocks_cfoptions_t, v: ::std::os::raw::c_uchar);
+}
 extern "C" {
     pub fn rocks_cfoptions_set_memtable_huge_page_size(opt: *mut rocks_cfoptions_t, v: usize);
 }
@@ -614,6 +675,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_cfoptions_set_max_compaction_bytes(opt: *mut rocks_cfoptions_t, n: u64);
 }
+extern "C" {
+    pub fn rocks_cfoptions_get_effective_max_compaction_bytes(opt: *const rocks_cfoptions_t) -> u64;
+}
 extern "C" {
     pub fn rocks_cfoptions_set_soft_pending_compaction_bytes_limit(opt: *mut rocks_cfoptions_t, v: u64);
 }
@@ -641,6 +705,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_cfoptions_set_max_sequential_skip_in_iterations(opt: *mut rocks_cfoptions_t, v: u64);
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_memtable_skip_list_rep(opt: *mut rocks_cfoptions_t, lookahead: usize);
+}
 extern "C" {
     pub fn rocks_cfoptions_set_memtable_vector_rep(opt: *mut rocks_cfoptions_t, count: usize);
 }
@@ -684,6 +751,21 @@ extern "C" {
 extern "C" {
     pub fn rocks_cfoptions_set_report_bg_io_stats(opt: *mut rocks_cfoptions_t, v: ::std::os::raw::c_uchar);
 }
+extern "C" {
+    pub fn rocks_cfoptions_set_enable_blob_files(opt: *mut rocks_cfoptions_t, v: ::std::os::raw::c_uchar);
+}
+extern "C" {
+    pub fn rocks_cfoptions_set_min_blob_size(opt: *mut rocks_cfoptions_t, v: u64);
+}
+extern "C" {
+    pub fn rocks_cfoptions_set_blob_file_size(opt: *mut rocks_cfoptions_t, v: u64);
+}
+extern "C" {
+    pub fn rocks_cfoptions_set_blob_compression_type(opt: *mut rocks_cfoptions_t, t: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn rocks_cfoptions_set_enable_blob_garbage_collection(opt: *mut rocks_cfoptions_t, v: ::std::os::raw::c_uchar);
+}
 extern "C" {
     pub fn rocks_dboptions_optimize_for_small_db(opt: *mut rocks_dboptions_t);
 }
@@ -708,6 +790,15 @@ extern "C" {
 extern "C" {
     pub fn rocks_dboptions_set_ratelimiter(opt: *mut rocks_dboptions_t, limiter: *mut rocks_ratelimiter_t);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_sst_file_manager(opt: *mut rocks_dboptions_t, manager: *mut rocks_sst_file_manager_t);
+}
+extern "C" {
+    pub fn rocks_dboptions_set_file_checksum_gen_factory(
+        opt: *mut rocks_dboptions_t,
+        factory: *mut rocks_file_checksum_gen_factory_t,
+    );
+}
 extern "C" {
     pub fn rocks_dboptions_set_info_log(opt: *mut rocks_dboptions_t, l: *mut rocks_logger_t);
 }
@@ -754,6 +845,12 @@ extern "C" {
 extern "C" {
     pub fn rocks_dboptions_set_max_background_jobs(opt: *mut rocks_dboptions_t, n: ::std::os::raw::c_int);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_max_background_compactions(opt: *mut rocks_dboptions_t, n: ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn rocks_dboptions_set_max_background_flushes(opt: *mut rocks_dboptions_t, n: ::std::os::raw::c_int);
+}
 extern "C" {
     pub fn rocks_dboptions_set_max_subcompactions(opt: *mut rocks_dboptions_t, n: u32);
 }
@@ -808,6 +905,12 @@ extern "C" {
 extern "C" {
     pub fn rocks_dboptions_set_stats_dump_period_sec(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uint);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_stats_persist_period_sec(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uint);
+}
+extern "C" {
+    pub fn rocks_dboptions_set_stats_history_buffer_size(opt: *mut rocks_dboptions_t, v: usize);
+}
 extern "C" {
     pub fn rocks_dboptions_set_advise_random_on_open(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
 }
@@ -874,9 +977,24 @@ extern "C" {
 extern "C" {
     pub fn rocks_dboptions_set_skip_stats_update_on_db_open(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_skip_checking_sst_file_sizes_on_db_open(
+        opt: *mut rocks_dboptions_t,
+        v: ::std::os::raw::c_uchar,
+    );
+}
 extern "C" {
     pub fn rocks_dboptions_set_wal_recovery_mode(opt: *mut rocks_dboptions_t, mode: ::std::os::raw::c_int);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_best_efforts_recovery(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
+}
+extern "C" {
+    pub fn rocks_dboptions_set_track_and_verify_wals_in_manifest(
+        opt: *mut rocks_dboptions_t,
+        v: ::std::os::raw::c_uchar,
+    );
+}
 extern "C" {
     pub fn rocks_dboptions_set_allow_2pc(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
 }
@@ -898,6 +1016,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_dboptions_set_allow_ingest_behind(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
 }
+extern "C" {
+    pub fn rocks_dboptions_set_preserve_deletes(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
+}
 extern "C" {
     pub fn rocks_dboptions_set_concurrent_prepare(opt: *mut rocks_dboptions_t, v: ::std::os::raw::c_uchar);
 }
@@ -910,6 +1031,18 @@ extern "C" {
 extern "C" {
     pub fn rocks_options_optimize_for_small_db(opt: *mut rocks_options_t);
 }
+extern "C" {
+    pub fn rocks_options_get_comparator_name(opt: *mut rocks_options_t) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_options_get_merge_operator_name(opt: *mut rocks_options_t) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_options_get_prefix_extractor_name(opt: *mut rocks_options_t) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_options_get_num_levels(opt: *mut rocks_options_t) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn rocks_readoptions_create() -> *mut rocks_readoptions_t;
 }
@@ -938,6 +1071,13 @@ extern "C" {
         keylen: usize,
     );
 }
+extern "C" {
+    pub fn rocks_readoptions_set_timestamp(
+        opt: *mut rocks_readoptions_t,
+        ts: *const ::std::os::raw::c_char,
+        tslen: usize,
+    );
+}
 extern "C" {
     pub fn rocks_readoptions_set_read_tier(opt: *mut rocks_readoptions_t, v: ::std::os::raw::c_int);
 }
@@ -971,6 +1111,18 @@ extern "C" {
         v: ::std::os::raw::c_uchar,
     );
 }
+extern "C" {
+    pub fn rocks_readoptions_set_auto_prefix_mode(opt: *mut rocks_readoptions_t, v: ::std::os::raw::c_uchar);
+}
+extern "C" {
+    pub fn rocks_readoptions_set_deadline(opt: *mut rocks_readoptions_t, microseconds: u64);
+}
+extern "C" {
+    pub fn rocks_readoptions_set_io_timeout(opt: *mut rocks_readoptions_t, microseconds: u64);
+}
+extern "C" {
+    pub fn rocks_readoptions_set_async_io(opt: *mut rocks_readoptions_t, v: ::std::os::raw::c_uchar);
+}
 extern "C" {
     pub fn rocks_writeoptions_create() -> *mut rocks_writeoptions_t;
 }
@@ -995,6 +1147,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_writeoptions_set_low_pri(opt: *mut rocks_writeoptions_t, v: ::std::os::raw::c_uchar);
 }
+extern "C" {
+    pub fn rocks_writeoptions_set_rate_limiter_priority(opt: *mut rocks_writeoptions_t, v: ::std::os::raw::c_int);
+}
 extern "C" {
     pub fn rocks_compactrange_options_create() -> *mut rocks_compactrange_options_t;
 }
@@ -1025,6 +1180,9 @@ extern "C" {
         v: ::std::os::raw::c_int,
     );
 }
+extern "C" {
+    pub fn rocks_compactrange_options_set_max_subcompactions(opt: *mut rocks_compactrange_options_t, v: u32);
+}
 extern "C" {
     pub fn rocks_ingestexternalfile_options_create() -> *mut rocks_ingestexternalfile_options_t;
 }
@@ -1061,6 +1219,24 @@ extern "C" {
         v: ::std::os::raw::c_uchar,
     );
 }
+extern "C" {
+    pub fn rocks_ingestexternalfile_options_set_failed_move_fall_back_to_copy(
+        opt: *mut rocks_ingestexternalfile_options_t,
+        v: ::std::os::raw::c_uchar,
+    );
+}
+extern "C" {
+    pub fn rocks_ingestexternalfile_options_set_write_global_seqno(
+        opt: *mut rocks_ingestexternalfile_options_t,
+        v: ::std::os::raw::c_uchar,
+    );
+}
+extern "C" {
+    pub fn rocks_ingestexternalfile_options_set_verify_checksums_before_ingest(
+        opt: *mut rocks_ingestexternalfile_options_t,
+        v: ::std::os::raw::c_uchar,
+    );
+}
 extern "C" {
     pub fn rocks_flushoptions_create() -> *mut rocks_flushoptions_t;
 }
@@ -1125,6 +1301,15 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     ) -> *mut rocks_db_t;
 }
+extern "C" {
+    pub fn rocks_db_open_and_report_recovery(
+        options: *const rocks_options_t,
+        name: *const ::std::os::raw::c_char,
+        records_replayed: *mut u64,
+        wal_corrupted: *mut ::std::os::raw::c_uchar,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_db_t;
+}
 extern "C" {
     pub fn rocks_db_open_for_read_only(
         options: *const rocks_options_t,
@@ -1133,9 +1318,32 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     ) -> *mut rocks_db_t;
 }
+extern "C" {
+    pub fn rocks_db_open_as_secondary(
+        options: *const rocks_options_t,
+        name: *const ::std::os::raw::c_char,
+        secondary_path: *const ::std::os::raw::c_char,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_db_t;
+}
+extern "C" {
+    pub fn rocks_db_open_as_secondary_column_families(
+        db_options: *const rocks_dboptions_t,
+        name: *const ::std::os::raw::c_char,
+        secondary_path: *const ::std::os::raw::c_char,
+        num_column_families: ::std::os::raw::c_int,
+        column_family_names: *const *const ::std::os::raw::c_char,
+        column_family_options: *const *const rocks_cfoptions_t,
+        column_family_handles: *mut *mut rocks_column_family_handle_t,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_db_t;
+}
 extern "C" {
     pub fn rocks_db_close(db: *mut rocks_db_t);
 }
+extern "C" {
+    pub fn rocks_db_close_with_status(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
+}
 extern "C" {
     pub fn rocks_db_open_column_families(
         db_options: *const rocks_dboptions_t,
@@ -1147,6 +1355,26 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     ) -> *mut rocks_db_t;
 }
+extern "C" {
+    pub fn rocks_db_open_with_ttl(
+        options: *const rocks_options_t,
+        name: *const ::std::os::raw::c_char,
+        ttl: i32,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_db_t;
+}
+extern "C" {
+    pub fn rocks_db_open_column_families_with_ttl(
+        db_options: *const rocks_dboptions_t,
+        name: *const ::std::os::raw::c_char,
+        num_column_families: ::std::os::raw::c_int,
+        column_family_names: *const *const ::std::os::raw::c_char,
+        column_family_options: *const *const rocks_cfoptions_t,
+        ttls: *const i32,
+        column_family_handles: *mut *mut rocks_column_family_handle_t,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_db_t;
+}
 extern "C" {
     pub fn rocks_db_open_for_read_only_column_families(
         db_options: *const rocks_options_t,
@@ -1195,6 +1423,9 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_release_default_column_family(db: *mut rocks_db_t, handle: *mut rocks_column_family_handle_t);
+}
 extern "C" {
     pub fn rocks_column_family_handle_destroy(handle: *mut rocks_column_family_handle_t);
 }
@@ -1207,7 +1438,7 @@ extern "C" {
         val: *const ::std::os::raw::c_char,
         vallen: usize,
         status: *mut *mut rocks_status_t,
-    );
+    ) -> u64;
 }
 extern "C" {
     pub fn rocks_db_put_cf(
@@ -1221,6 +1452,20 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_put_cf_with_ts(
+        db: *mut rocks_db_t,
+        options: *const rocks_writeoptions_t,
+        column_family: *mut rocks_column_family_handle_t,
+        key: *const ::std::os::raw::c_char,
+        keylen: usize,
+        ts: *const ::std::os::raw::c_char,
+        tslen: usize,
+        val: *const ::std::os::raw::c_char,
+        vallen: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_db_delete(
         db: *mut rocks_db_t,
@@ -1294,13 +1539,21 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_write_with_seq(
+        db: *mut rocks_db_t,
+        options: *const rocks_writeoptions_t,
+        batch: *mut rocks_raw_writebatch_t,
+        status: *mut *mut rocks_status_t,
+    ) -> u64;
+}
 extern "C" {
     pub fn rocks_db_write(
         db: *mut rocks_db_t,
         options: *const rocks_writeoptions_t,
         batch: *mut rocks_raw_writebatch_t,
         status: *mut *mut rocks_status_t,
-    );
+    ) -> u64;
 }
 extern "C" {
     pub fn rocks_db_get_pinnable(
@@ -1323,6 +1576,27 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_get(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        key: *const ::std::os::raw::c_char,
+        keylen: usize,
+        vallen: *mut usize,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_db_get_cf(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        column_family: *mut rocks_column_family_handle_t,
+        key: *const ::std::os::raw::c_char,
+        keylen: usize,
+        vallen: *mut usize,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn rocks_db_multi_get(
         db: *mut rocks_db_t,
@@ -1348,6 +1622,20 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_batched_multi_get_cf(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        column_family: *const rocks_column_family_handle_t,
+        num_keys: usize,
+        keys_list: *const *const ::std::os::raw::c_char,
+        keys_list_sizes: *const usize,
+        values_list: *mut *mut ::std::os::raw::c_char,
+        values_list_sizes: *mut usize,
+        status: *mut *mut rocks_status_t,
+        sorted_input: ::std::os::raw::c_uchar,
+    );
+}
 extern "C" {
     pub fn rocks_db_key_may_exist(
         db: *mut rocks_db_t,
@@ -1369,6 +1657,26 @@ extern "C" {
         value_found: *mut ::std::os::raw::c_uchar,
     ) -> ::std::os::raw::c_uchar;
 }
+extern "C" {
+    pub fn rocks_db_key_exists_cf(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        column_family: *const rocks_column_family_handle_t,
+        key: *const ::std::os::raw::c_char,
+        key_len: usize,
+    ) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_db_get_merge_operands(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        column_family: *const rocks_column_family_handle_t,
+        key: *const ::std::os::raw::c_char,
+        key_len: usize,
+        max_operands: ::std::os::raw::c_int,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut cxx_string_vector_t;
+}
 extern "C" {
     pub fn rocks_db_create_iterator(db: *mut rocks_db_t, options: *const rocks_readoptions_t) -> *mut rocks_iterator_t;
 }
@@ -1412,6 +1720,34 @@ extern "C" {
         value: *mut ::std::os::raw::c_void,
     ) -> ::std::os::raw::c_uchar;
 }
+extern "C" {
+    pub fn rocks_db_get_map_property(
+        db: *mut rocks_db_t,
+        prop: *const ::std::os::raw::c_char,
+        prop_len: usize,
+        keys: *mut *mut *mut ::std::os::raw::c_char,
+        values: *mut *mut *mut ::std::os::raw::c_char,
+        len: *mut usize,
+    ) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_db_get_map_property_cf(
+        db: *mut rocks_db_t,
+        cf: *mut rocks_column_family_handle_t,
+        prop: *const ::std::os::raw::c_char,
+        prop_len: usize,
+        keys: *mut *mut *mut ::std::os::raw::c_char,
+        values: *mut *mut *mut ::std::os::raw::c_char,
+        len: *mut usize,
+    ) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_db_get_map_property_destroy(
+        keys: *mut *mut ::std::os::raw::c_char,
+        values: *mut *mut ::std::os::raw::c_char,
+        len: usize,
+    );
+}
 extern "C" {
     pub fn rocks_db_get_int_property(
         db: *mut rocks_db_t,
@@ -1437,6 +1773,9 @@ extern "C" {
         value: *mut u64,
     ) -> ::std::os::raw::c_uchar;
 }
+extern "C" {
+    pub fn rocks_db_reset_stats(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
+}
 extern "C" {
     pub fn rocks_db_compact_range(
         db: *mut rocks_db_t,
@@ -1514,6 +1853,25 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_promote_l0(
+        db: *mut rocks_db_t,
+        column_family: *mut rocks_column_family_handle_t,
+        target_level: ::std::os::raw::c_int,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_db_suggest_compact_range(
+        db: *mut rocks_db_t,
+        column_family: *mut rocks_column_family_handle_t,
+        begin: *const ::std::os::raw::c_char,
+        begin_len: usize,
+        end: *const ::std::os::raw::c_char,
+        end_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_db_pause_background_work(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
 }
@@ -1582,6 +1940,18 @@ extern "C" {
 extern "C" {
     pub fn rocks_db_get_name(db: *mut rocks_db_t, s: *mut ::std::os::raw::c_void);
 }
+extern "C" {
+    pub fn rocks_db_get_options_cf(
+        db: *mut rocks_db_t,
+        column_family: *mut rocks_column_family_handle_t,
+    ) -> *mut rocks_cfoptions_t;
+}
+extern "C" {
+    pub fn rocks_db_get_db_options(db: *mut rocks_db_t) -> *mut rocks_dboptions_t;
+}
+extern "C" {
+    pub fn rocks_db_get_thread_list(db: *mut rocks_db_t, len: *mut usize) -> *mut *mut rocks_thread_status_t;
+}
 extern "C" {
     pub fn rocks_db_flush(db: *mut rocks_db_t, options: *mut rocks_flushoptions_t, status: *mut *mut rocks_status_t);
 }
@@ -1593,12 +1963,21 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_flush_wal(db: *mut rocks_db_t, sync: ::std::os::raw::c_uchar, status: *mut *mut rocks_status_t);
+}
 extern "C" {
     pub fn rocks_db_sync_wal(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
 }
 extern "C" {
     pub fn rocks_db_get_latest_sequence_number(db: *mut rocks_db_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_db_set_preserve_deletes_sequence_number(db: *mut rocks_db_t, seqnum: u64) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_db_try_catch_up_with_primary(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
+}
 extern "C" {
     pub fn rocks_db_disable_file_deletions(db: *mut rocks_db_t, status: *mut *mut rocks_status_t);
 }
@@ -1627,9 +2006,18 @@ extern "C" {
     pub fn rocks_db_get_update_since(
         db: *mut rocks_db_t,
         seq_no: u64,
+        verify_checksums: ::std::os::raw::c_uchar,
         status: *mut *mut rocks_status_t,
     ) -> *mut rocks_transaction_log_iterator_t;
 }
+extern "C" {
+    pub fn rocks_db_get_stats_history(
+        db: *mut rocks_db_t,
+        start_time: u64,
+        end_time: u64,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_stats_history_iterator_t;
+}
 extern "C" {
     pub fn rocks_db_delete_file(
         db: *mut rocks_db_t,
@@ -1641,6 +2029,12 @@ extern "C" {
 extern "C" {
     pub fn rocks_db_get_livefiles_metadata(db: *mut rocks_db_t) -> *const rocks_livefiles_t;
 }
+extern "C" {
+    pub fn rocks_db_get_live_files_checksum_info(
+        db: *mut rocks_db_t,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_file_checksum_list_t;
+}
 extern "C" {
     pub fn rocks_db_get_column_family_metadata(
         db: *mut rocks_db_t,
@@ -1668,6 +2062,25 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_ingest_external_files(
+        db: *mut rocks_db_t,
+        num_args: usize,
+        column_families: *const *mut rocks_column_family_handle_t,
+        num_files_per_arg: *const usize,
+        file_list: *const *const ::std::os::raw::c_char,
+        file_list_sizes: *const usize,
+        options: *const rocks_ingestexternalfile_options_t,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_db_verify_checksum(
+        db: *mut rocks_db_t,
+        options: *const rocks_readoptions_t,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_db_get_db_identity(
         db: *mut rocks_db_t,
@@ -1675,6 +2088,13 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_db_get_db_session_id(
+        db: *mut rocks_db_t,
+        session_id: *mut ::std::os::raw::c_void,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_db_get_properties_of_all_tables(
         db: *mut rocks_db_t,
@@ -1717,9 +2137,101 @@ extern "C" {
         fairness: i32,
     ) -> *mut rocks_ratelimiter_t;
 }
+extern "C" {
+    pub fn rocks_ratelimiter_copy(limiter: *mut rocks_ratelimiter_t) -> *mut rocks_ratelimiter_t;
+}
 extern "C" {
     pub fn rocks_ratelimiter_destroy(limiter: *mut rocks_ratelimiter_t);
 }
+extern "C" {
+    pub fn rocks_ratelimiter_get_single_burst_bytes(limiter: *mut rocks_ratelimiter_t) -> i64;
+}
+extern "C" {
+    pub fn rocks_ratelimiter_request(
+        limiter: *mut rocks_ratelimiter_t,
+        bytes: i64,
+        pri: ::std::os::raw::c_int,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_ratelimiter_get_total_bytes_through(
+        limiter: *mut rocks_ratelimiter_t,
+        pri: ::std::os::raw::c_int,
+    ) -> i64;
+}
+extern "C" {
+    pub fn rocks_ratelimiter_get_total_requests(limiter: *mut rocks_ratelimiter_t, pri: ::std::os::raw::c_int) -> i64;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_create(
+        env: *mut rocks_env_t,
+        info_log: *mut rocks_logger_t,
+        trash_dir: *const ::std::os::raw::c_char,
+        trash_dir_len: usize,
+        rate_bytes_per_sec: i64,
+        delete_existing_trash: ::std::os::raw::c_uchar,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_sst_file_manager_t;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_copy(manager: *mut rocks_sst_file_manager_t) -> *mut rocks_sst_file_manager_t;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_destroy(manager: *mut rocks_sst_file_manager_t);
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_set_max_allowed_space_usage(
+        manager: *mut rocks_sst_file_manager_t,
+        max_allowed_space: u64,
+    );
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_is_max_allowed_space_reached(
+        manager: *mut rocks_sst_file_manager_t,
+    ) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_get_total_size(manager: *mut rocks_sst_file_manager_t) -> u64;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_get_delete_rate_bytes_per_second(manager: *mut rocks_sst_file_manager_t) -> i64;
+}
+extern "C" {
+    pub fn rocks_sst_file_manager_set_delete_rate_bytes_per_second(
+        manager: *mut rocks_sst_file_manager_t,
+        delete_rate: i64,
+    );
+}
+extern "C" {
+    pub fn rocks_file_checksum_gen_crc32c_factory_create() -> *mut rocks_file_checksum_gen_factory_t;
+}
+extern "C" {
+    pub fn rocks_file_checksum_gen_factory_destroy(factory: *mut rocks_file_checksum_gen_factory_t);
+}
+extern "C" {
+    pub fn rocks_file_checksum_list_size(list: *const rocks_file_checksum_list_t) -> usize;
+}
+extern "C" {
+    pub fn rocks_file_checksum_list_file_number(list: *const rocks_file_checksum_list_t, index: usize) -> u64;
+}
+extern "C" {
+    pub fn rocks_file_checksum_list_checksum(
+        list: *const rocks_file_checksum_list_t,
+        index: usize,
+        len: *mut usize,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_file_checksum_list_func_name(
+        list: *const rocks_file_checksum_list_t,
+        index: usize,
+        len: *mut usize,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_file_checksum_list_destroy(list: *mut rocks_file_checksum_list_t);
+}
 extern "C" {
     pub fn rocks_create_default_env() -> *mut rocks_env_t;
 }
@@ -1729,6 +2241,33 @@ extern "C" {
 extern "C" {
     pub fn rocks_create_timed_env() -> *mut rocks_env_t;
 }
+extern "C" {
+    pub fn rocks_create_encrypted_env(
+        base_env: *mut rocks_env_t,
+        cipher_trait_obj: *mut ::std::os::raw::c_void,
+    ) -> *mut rocks_env_t;
+}
+extern "C" {
+    pub fn rocks_create_encrypted_env_with_keys(
+        base_env: *mut rocks_env_t,
+        current_key_id: u32,
+        key_ids: *const u32,
+        cipher_trait_objs: *const *mut ::std::os::raw::c_void,
+        num_keys: usize,
+    ) -> *mut rocks_env_t;
+}
+extern "C" {
+    pub fn rocks_create_fault_injection_env(base_env: *mut rocks_env_t) -> *mut rocks_env_t;
+}
+extern "C" {
+    pub fn rocks_env_set_filesystem_active(env: *mut rocks_env_t, active: ::std::os::raw::c_uchar);
+}
+extern "C" {
+    pub fn rocks_env_set_read_delay(env: *mut rocks_env_t, micros: u64);
+}
+extern "C" {
+    pub fn rocks_env_set_direct_io_supported(env: *mut rocks_env_t, supported: ::std::os::raw::c_uchar);
+}
 extern "C" {
     pub fn rocks_env_destroy(env: *mut rocks_env_t);
 }
@@ -1801,6 +2340,42 @@ extern "C" {
 extern "C" {
     pub fn rocks_env_get_thread_id(env: *mut rocks_env_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_env_file_exists(
+        env: *mut rocks_env_t,
+        fname: *const ::std::os::raw::c_char,
+        fname_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_env_get_children(
+        env: *mut rocks_env_t,
+        dir: *const ::std::os::raw::c_char,
+        dir_len: usize,
+        len: *mut usize,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_env_get_children_destroy(list: *mut *mut ::std::os::raw::c_char, len: usize);
+}
+extern "C" {
+    pub fn rocks_env_delete_file(
+        env: *mut rocks_env_t,
+        fname: *const ::std::os::raw::c_char,
+        fname_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_env_create_dir_if_missing(
+        env: *mut rocks_env_t,
+        dirname: *const ::std::os::raw::c_char,
+        dirname_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_envoptions_create() -> *mut rocks_envoptions_t;
 }
@@ -1840,6 +2415,12 @@ extern "C" {
 extern "C" {
     pub fn rocks_envoptions_set_writable_file_max_buffer_size(opt: *mut rocks_envoptions_t, val: usize);
 }
+extern "C" {
+    pub fn rocks_logger_create_from_rust(
+        fn_obj: *mut ::std::os::raw::c_void,
+        log_level: ::std::os::raw::c_int,
+    ) -> *mut rocks_logger_t;
+}
 extern "C" {
     pub fn rocks_logger_destroy(logger: *mut rocks_logger_t);
 }
@@ -1878,6 +2459,12 @@ extern "C" {
 extern "C" {
     pub fn rocks_writebatch_create_with_reserved_bytes(size: usize) -> *mut rocks_writebatch_t;
 }
+extern "C" {
+    pub fn rocks_writebatch_create_with_max_bytes(reserved_bytes: usize, max_bytes: usize) -> *mut rocks_writebatch_t;
+}
+extern "C" {
+    pub fn rocks_writebatch_create_from(rep: *const ::std::os::raw::c_char, size: usize) -> *mut rocks_writebatch_t;
+}
 extern "C" {
     pub fn rocks_writebatch_destroy(b: *mut rocks_writebatch_t);
 }
@@ -1887,6 +2474,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_writebatch_count(b: *mut rocks_writebatch_t) -> ::std::os::raw::c_int;
 }
+extern "C" {
+    pub fn rocks_writebatch_get_data_size(b: *mut rocks_writebatch_t) -> usize;
+}
 extern "C" {
     pub fn rocks_writebatch_put(
         b: *mut rocks_writebatch_t,
@@ -2099,6 +2689,13 @@ extern "C" {
 extern "C" {
     pub fn rocks_writebatch_copy(b: *mut rocks_writebatch_t) -> *mut rocks_writebatch_t;
 }
+extern "C" {
+    pub fn rocks_writebatch_append(
+        dst: *mut rocks_writebatch_t,
+        src: *mut rocks_writebatch_t,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_writebatch_get_writebatch(b: *mut rocks_writebatch_t) -> *mut rocks_raw_writebatch_t;
 }
@@ -2174,6 +2771,18 @@ extern "C" {
         v: ::std::os::raw::c_int,
     );
 }
+extern "C" {
+    pub fn rocks_block_based_table_options_set_data_block_index_type(
+        options: *mut rocks_block_based_table_options_t,
+        v: ::std::os::raw::c_int,
+    );
+}
+extern "C" {
+    pub fn rocks_block_based_table_options_set_data_block_hash_table_util_ratio(
+        options: *mut rocks_block_based_table_options_t,
+        v: f64,
+    );
+}
 extern "C" {
     pub fn rocks_block_based_table_options_set_hash_index_allow_collision(
         options: *mut rocks_block_based_table_options_t,
@@ -2333,6 +2942,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_iter_get_status(iter: *const rocks_iterator_t, status: *mut *mut rocks_status_t);
 }
+extern "C" {
+    pub fn rocks_iter_refresh(iter: *mut rocks_iterator_t, status: *mut *mut rocks_status_t);
+}
 extern "C" {
     pub fn rocks_iter_get_property(
         iter: *const rocks_iterator_t,
@@ -2372,6 +2984,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_cache_destroy(cache: *mut rocks_cache_t);
 }
+extern "C" {
+    pub fn rocks_cache_clone(cache: *mut rocks_cache_t) -> *mut rocks_cache_t;
+}
 extern "C" {
     pub fn rocks_cache_set_capacity(cache: *mut rocks_cache_t, capacity: usize);
 }
@@ -2387,6 +3002,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_cache_name(cache: *mut rocks_cache_t) -> *const ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn rocks_cache_apply_to_all_entries(cache: *mut rocks_cache_t, f: *mut ::std::os::raw::c_void);
+}
 extern "C" {
     pub fn rocks_new_persistent_cache(
         env: *const rocks_env_t,
@@ -2461,6 +3079,14 @@ extern "C" {
         invalidate_page_cache: ::std::os::raw::c_uchar,
     ) -> *mut rocks_sst_file_writer_t;
 }
+extern "C" {
+    pub fn rocks_sst_file_writer_create(
+        env_options: *const rocks_envoptions_t,
+        options: *const rocks_options_t,
+        column_family: *mut rocks_column_family_handle_t,
+        invalidate_page_cache: ::std::os::raw::c_uchar,
+    ) -> *mut rocks_sst_file_writer_t;
+}
 extern "C" {
     pub fn rocks_sst_file_writer_destroy(writer: *mut rocks_sst_file_writer_t);
 }
@@ -2500,6 +3126,16 @@ extern "C" {
         status: *mut *mut rocks_status_t,
     );
 }
+extern "C" {
+    pub fn rocks_sst_file_writer_delete_range(
+        writer: *mut rocks_sst_file_writer_t,
+        begin_key: *const ::std::os::raw::c_char,
+        begin_key_len: usize,
+        end_key: *const ::std::os::raw::c_char,
+        end_key_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_sst_file_writer_finish(
         writer: *mut rocks_sst_file_writer_t,
@@ -2510,6 +3146,37 @@ extern "C" {
 extern "C" {
     pub fn rocks_sst_file_writer_file_size(writer: *mut rocks_sst_file_writer_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_sst_file_reader_create(options: *const rocks_options_t) -> *mut rocks_sst_file_reader_t;
+}
+extern "C" {
+    pub fn rocks_sst_file_reader_destroy(reader: *mut rocks_sst_file_reader_t);
+}
+extern "C" {
+    pub fn rocks_sst_file_reader_open(
+        reader: *mut rocks_sst_file_reader_t,
+        file_path: *const ::std::os::raw::c_char,
+        file_path_len: usize,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_sst_file_reader_new_iterator(
+        reader: *mut rocks_sst_file_reader_t,
+        options: *const rocks_readoptions_t,
+    ) -> *mut rocks_iterator_t;
+}
+extern "C" {
+    pub fn rocks_sst_file_reader_get_table_properties(reader: *mut rocks_sst_file_reader_t)
+        -> *mut rocks_table_props_t;
+}
+extern "C" {
+    pub fn rocks_sst_file_reader_verify_checksum(
+        reader: *mut rocks_sst_file_reader_t,
+        options: *const rocks_readoptions_t,
+        status: *mut *mut rocks_status_t,
+    );
+}
 extern "C" {
     pub fn rocks_comparator_bytewise() -> *const rocks_c_comparator_t;
 }
@@ -2660,6 +3327,16 @@ extern "C" {
     pub fn rocks_statistics_hist_enabled_for_type(stat: *mut rocks_statistics_t, type_: u32)
         -> ::std::os::raw::c_uchar;
 }
+extern "C" {
+    pub fn rocks_statistics_reset(stat: *mut rocks_statistics_t, status: *mut *mut rocks_status_t);
+}
+extern "C" {
+    pub fn rocks_statistics_histogram_type_by_name(
+        name: *const ::std::os::raw::c_char,
+        len: usize,
+        type_: *mut u32,
+    ) -> ::std::os::raw::c_uchar;
+}
 extern "C" {
     pub fn rocks_livefiles_count(lf: *const rocks_livefiles_t) -> ::std::os::raw::c_int;
 }
@@ -2901,6 +3578,37 @@ extern "C" {
         seq_no: *mut u64,
     ) -> *mut rocks_writebatch_t;
 }
+extern "C" {
+    pub fn rocks_stats_history_iterator_destroy(it: *mut rocks_stats_history_iterator_t);
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_valid(it: *mut rocks_stats_history_iterator_t) -> ::std::os::raw::c_uchar;
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_next(it: *mut rocks_stats_history_iterator_t);
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_status(
+        it: *mut rocks_stats_history_iterator_t,
+        status: *mut *mut rocks_status_t,
+    );
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_get_stats_time(it: *mut rocks_stats_history_iterator_t) -> u64;
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_load_stats_map(it: *mut rocks_stats_history_iterator_t) -> usize;
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_stats_map_nth_key(
+        it: *mut rocks_stats_history_iterator_t,
+        nth: usize,
+        len: *mut usize,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_stats_history_iterator_stats_map_nth_value(it: *mut rocks_stats_history_iterator_t, nth: usize) -> u64;
+}
 extern "C" {
     pub fn rocks_get_supported_compressions(len: *mut usize) -> *mut ::std::os::raw::c_int;
 }
@@ -2927,6 +3635,22 @@ extern "C" {
 extern "C" {
     pub fn rocks_get_string_from_cfoptions(opts: *mut rocks_cfoptions_t) -> *mut cxx_string_t;
 }
+extern "C" {
+    pub fn rocks_get_dboptions_from_string(
+        base: *mut rocks_dboptions_t,
+        opts_str: *const ::std::os::raw::c_char,
+        opts_len: usize,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_dboptions_t;
+}
+extern "C" {
+    pub fn rocks_get_cfoptions_from_string(
+        base: *mut rocks_cfoptions_t,
+        opts_str: *const ::std::os::raw::c_char,
+        opts_len: usize,
+        status: *mut *mut rocks_status_t,
+    ) -> *mut rocks_cfoptions_t;
+}
 extern "C" {
     pub fn rocks_table_props_collection_destroy(coll: *mut rocks_table_props_collection_t);
 }
@@ -2991,12 +3715,21 @@ extern "C" {
 extern "C" {
     pub fn rocks_table_props_get_num_entries(prop: *mut rocks_table_props_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_table_props_get_num_deletions(prop: *mut rocks_table_props_t) -> u64;
+}
 extern "C" {
     pub fn rocks_table_props_get_format_version(prop: *mut rocks_table_props_t) -> u64;
 }
 extern "C" {
     pub fn rocks_table_props_get_fixed_key_len(prop: *mut rocks_table_props_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_table_props_get_slow_compression_estimated_data_size(prop: *mut rocks_table_props_t) -> u64;
+}
+extern "C" {
+    pub fn rocks_table_props_get_fast_compression_estimated_data_size(prop: *mut rocks_table_props_t) -> u64;
+}
 extern "C" {
     pub fn rocks_table_props_get_column_family_id(prop: *mut rocks_table_props_t) -> u32;
 }
@@ -3117,6 +3850,7 @@ extern "C" {
         begin_keylen: usize,
         end_key: *const ::std::os::raw::c_char,
         end_keylen: usize,
+        max_num_ikeys: usize,
         status: *mut *mut rocks_status_t,
     ) -> *mut rocks_key_version_collection_t;
 }
@@ -3184,6 +3918,9 @@ extern "C" {
 extern "C" {
     pub fn rocks_flush_job_info_get_table_properties(info: *const rocks_flush_job_info_t) -> *mut rocks_table_props_t;
 }
+extern "C" {
+    pub fn rocks_flush_job_info_get_flush_reason(info: *const rocks_flush_job_info_t) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn rocks_table_file_deletion_info_get_db_name(
         info: *const rocks_table_file_deletion_info_t,
@@ -3421,6 +4158,18 @@ extern "C" {
 extern "C" {
     pub fn rocks_mem_table_info_get_num_deletes(info: *const rocks_mem_table_info_t) -> u64;
 }
+extern "C" {
+    pub fn rocks_write_stall_info_get_cf_name(
+        info: *const rocks_write_stall_info_t,
+        len: *mut usize,
+    ) -> *const ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn rocks_write_stall_info_get_cur_condition(info: *const rocks_write_stall_info_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn rocks_write_stall_info_get_prev_condition(info: *const rocks_write_stall_info_t) -> ::std::os::raw::c_int;
+}
 extern "C" {
     pub fn rocks_external_file_ingestion_info_get_cf_name(
         info: *const rocks_external_file_ingestion_info_t,
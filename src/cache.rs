@@ -7,7 +7,7 @@
 //! length strings, may use the length of the string as the charge for
 //! the string.
 
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_void};
 use std::ffi::CStr;
 
 use rocks_sys as ll;
@@ -20,16 +20,29 @@ pub enum Priority {
     Low,
 }
 
-// TODO: impl Copy for inner shared_ptr
-
 /// A builtin cache implementation with a least-recently-used eviction
 /// policy is provided.  Clients may use their own implementations if
 /// they want something more sophisticated (like scan-resistance, a
 /// custom eviction policy, variable cache sizing, etc.)
+///
+/// Options setters keep their own reference to the cache, clone it to share
+/// one cache between several options or DBs.
 pub struct Cache {
     raw: *mut ll::rocks_cache_t,
 }
 
+unsafe impl Send for Cache {}
+unsafe impl Sync for Cache {}
+
+impl Clone for Cache {
+    /// Duplicated Cache inner shared_ptr
+    fn clone(&self) -> Self {
+        Cache {
+            raw: unsafe { ll::rocks_cache_clone(self.raw) },
+        }
+    }
+}
+
 impl ToRaw<ll::rocks_cache_t> for Cache {
     fn raw(&self) -> *mut ll::rocks_cache_t {
         self.raw
@@ -64,6 +77,22 @@ impl Cache {
     pub fn get_usage(&self) -> usize {
         unsafe { ll::rocks_cache_get_usage(self.raw) }
     }
+
+    /// returns the memory size for the entries in use by the system
+    pub fn get_pinned_usage(&self) -> usize {
+        unsafe { ll::rocks_cache_get_pinned_usage(self.raw) }
+    }
+
+    /// Call `f` with the key and the charge of every entry in the cache.
+    ///
+    /// Values are opaque to Rust, so they are not passed. Each shard is locked
+    /// while it is walked, `f` must not touch the cache itself.
+    pub fn for_each_entry<F: FnMut(&[u8], usize)>(&self, mut f: F) {
+        let mut f: &mut dyn FnMut(&[u8], usize) = &mut f;
+        unsafe {
+            ll::rocks_cache_apply_to_all_entries(self.raw, &mut f as *mut &mut dyn FnMut(&[u8], usize) as *mut c_void);
+        }
+    }
 }
 
 impl Drop for Cache {
@@ -74,6 +103,18 @@ impl Drop for Cache {
     }
 }
 
+#[doc(hidden)]
+pub mod c {
+    use std::os::raw::c_char;
+    use std::slice;
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_cache_entry_call(f: *mut (), key: *const c_char, key_len: usize, charge: usize) {
+        let f = f as *mut &mut dyn FnMut(&[u8], usize);
+        (*f)(slice::from_raw_parts(key as *const u8, key_len), charge)
+    }
+}
+
 // Rust
 #[derive(PartialEq, Eq)]
 enum CacheType {
@@ -223,4 +264,91 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn walk_block_cache_entries() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let block_cache = CacheBuilder::new_lru(8 << 20).build().unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| {
+                    cf.table_factory_block_based(
+                        BlockBasedTableOptions::default()
+                            .block_size(1024)
+                            .block_cache(Some(block_cache.clone())),
+                    )
+                }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        let mut entries = 0;
+        block_cache.for_each_entry(|_, _| entries += 1);
+        assert_eq!(entries, 0);
+
+        let value = vec![b'x'; 100];
+        for i in 0..1000 {
+            let key = format!("k{:04}", i);
+            db.put(WriteOptions::default_instance(), key.as_bytes(), &value)
+                .unwrap();
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        for i in 0..1000 {
+            let key = format!("k{:04}", i);
+            assert!(db.get(ReadOptions::default_instance(), key.as_bytes()).is_ok());
+        }
+
+        let mut total_charge = 0;
+        block_cache.for_each_entry(|key, charge| {
+            assert!(!key.is_empty());
+            entries += 1;
+            total_charge += charge;
+        });
+        assert!(entries > 0);
+        assert!(total_charge > 0);
+        assert!(total_charge <= block_cache.get_usage());
+    }
+
+    #[test]
+    fn compressed_block_cache_db() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let block_cache = CacheBuilder::new_lru(64 << 10).build().unwrap();
+        let compressed_cache = CacheBuilder::new_lru(1 << 20).build().unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| {
+                    cf.compression(CompressionType::SnappyCompression)
+                        .table_factory_block_based(
+                            BlockBasedTableOptions::default()
+                                .block_size(1024)
+                                .block_cache(Some(block_cache))
+                                .block_cache_compressed(Some(compressed_cache)),
+                        )
+                }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        // highly compressible values, much larger than the uncompressed cache
+        let value = vec![b'x'; 1000];
+        for i in 0..1000 {
+            let key = format!("k{:04}", i);
+            db.put(WriteOptions::default_instance(), key.as_bytes(), &value)
+                .unwrap();
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        // twice, the second round may be served from the compressed cache
+        for _ in 0..2 {
+            for i in 0..1000 {
+                let key = format!("k{:04}", i);
+                assert_eq!(
+                    db.get(ReadOptions::default_instance(), key.as_bytes()).unwrap(),
+                    &value[..]
+                );
+            }
+        }
+    }
 }
@@ -2,6 +2,7 @@
 //! the time of compaction.
 
 use std::os::raw::{c_char, c_int};
+use std::sync::Arc;
 
 use rocks_sys as ll;
 
@@ -160,6 +161,9 @@ pub trait CompactionFilter {
 
 /// Each compaction will create a new `CompactionFilter` allowing the
 /// application to know about different compactions
+///
+/// The factory is shared with the DB through an `Arc`, use interior
+/// mutability (atomics, locks) for state that changes while the DB runs.
 pub trait CompactionFilterFactory {
     fn create_compaction_filter(&self, context: &Context) -> Box<dyn CompactionFilter>;
 
@@ -222,6 +226,13 @@ pub mod c {
         Box::from_raw(filter);
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_compaction_filter_drop_owned(f: *mut ()) {
+        assert!(!f.is_null());
+        let filter = Box::from_raw(f as *mut &mut dyn CompactionFilter);
+        drop(Box::from_raw(*filter as *mut dyn CompactionFilter));
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn rust_compaction_filter_name(f: *mut ()) -> *const c_char {
         assert!(!f.is_null());
@@ -235,6 +246,40 @@ pub mod c {
         let filter = f as *mut &(dyn CompactionFilter + Sync);
         (*filter).ignore_snapshots() as _
     }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_compaction_filter_factory_create(
+        f: *mut (),
+        is_full_compaction: u8,
+        is_manual_compaction: u8,
+        column_family_id: u32,
+    ) -> *mut () {
+        assert!(!f.is_null());
+        let factory = f as *mut Arc<dyn CompactionFilterFactory + Send + Sync>;
+        let context = Context {
+            is_full_compaction: is_full_compaction != 0,
+            is_manual_compaction: is_manual_compaction != 0,
+            column_family_id,
+        };
+        // same layout as filters from `ColumnFamilyOptions::compaction_filter`,
+        // freed by `rust_compaction_filter_drop_owned`
+        let filter: &mut dyn CompactionFilter = Box::leak((*factory).create_compaction_filter(&context));
+        Box::into_raw(Box::new(filter)) as *mut ()
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_compaction_filter_factory_name(f: *mut ()) -> *const c_char {
+        assert!(!f.is_null());
+        let factory = f as *mut Arc<dyn CompactionFilterFactory + Send + Sync>;
+        (*factory).name().as_ptr() as _
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_compaction_filter_factory_drop(f: *mut ()) {
+        assert!(!f.is_null());
+        let factory = f as *mut Arc<dyn CompactionFilterFactory + Send + Sync>;
+        drop(Box::from_raw(factory));
+    }
 }
 
 #[cfg(test)]
@@ -242,6 +287,7 @@ mod tests {
     use crate::rocksdb::*;
     use super::*;
     use lazy_static::lazy_static;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
     pub struct MyCompactionFilter;
 
@@ -324,4 +370,68 @@ mod tests {
         drop(db);
         drop(tmp_dir);
     }
+
+    /// Removes values, which are timestamps, older than a shared cutoff.
+    struct ExpiryFilter(u64);
+
+    impl CompactionFilter for ExpiryFilter {
+        fn filter(&mut self, level: i32, key: &[u8], value_type: ValueType, existing_value: &[u8]) -> Decision {
+            let ts: u64 = String::from_utf8_lossy(existing_value).parse().unwrap();
+            if ts < self.0 {
+                Decision::Remove
+            } else {
+                Decision::Keep
+            }
+        }
+    }
+
+    struct ExpiryFilterFactory {
+        cutoff: AtomicU64,
+        manual_compactions: AtomicUsize,
+    }
+
+    impl CompactionFilterFactory for ExpiryFilterFactory {
+        fn create_compaction_filter(&self, context: &Context) -> Box<dyn CompactionFilter> {
+            if context.is_manual_compaction {
+                self.manual_compactions.fetch_add(1, Ordering::SeqCst);
+            }
+            Box::new(ExpiryFilter(self.cutoff.load(Ordering::SeqCst)))
+        }
+    }
+
+    #[test]
+    fn compaction_filter_factory() {
+        let factory = Arc::new(ExpiryFilterFactory {
+            cutoff: AtomicU64::new(15),
+            manual_compactions: AtomicUsize::new(0),
+        });
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.compaction_filter_factory(factory.clone())),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for (key, ts) in &[("a", "10"), ("b", "20"), ("c", "30")] {
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), ts.as_bytes()).is_ok());
+        }
+
+        assert!(db.compact_range(&Default::default(), ..).is_ok());
+        assert!(db.get(&ReadOptions::default(), b"a").unwrap_err().is_not_found());
+        assert_eq!(db.get(&ReadOptions::default(), b"b").unwrap(), b"20");
+
+        // filters of later compactions see the new cutoff
+        factory.cutoff.store(25, Ordering::SeqCst);
+        assert!(db.compact_range(&Default::default(), ..).is_ok());
+        assert!(db.get(&ReadOptions::default(), b"b").unwrap_err().is_not_found());
+        assert_eq!(db.get(&ReadOptions::default(), b"c").unwrap(), b"30");
+
+        assert!(factory.manual_compactions.load(Ordering::SeqCst) >= 2);
+        drop(db);
+        // the DB released its reference
+        assert_eq!(Arc::strong_count(&factory), 1);
+    }
 }
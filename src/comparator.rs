@@ -191,6 +191,120 @@ mod tests {
         assert!(first > second);
     }
 
+    #[test]
+    fn bitwise_comparator_with_timestamp() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let cf = db
+            .create_column_family(
+                &ColumnFamilyOptions::default().bitwise_comparator_with_timestamp(8, false),
+                "timestamped",
+            )
+            .unwrap();
+
+        let wopts = WriteOptions::default();
+        assert!(cf.put_with_ts(&wopts, b"key", &10u64.to_le_bytes(), b"v10").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"key", &20u64.to_le_bytes(), b"v20").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"other", &15u64.to_le_bytes(), b"o15").is_ok());
+        // timestamp size must match the comparator
+        let err = cf.put_with_ts(&wopts, b"key", b"bad", b"v").unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::InvalidArgument);
+
+        let read_at = |ts: u64, key: &[u8]| -> Option<Vec<u8>> {
+            let ts = ts.to_le_bytes();
+            let ropts = ReadOptions::default().timestamp(&ts);
+            cf.get(&ropts, key).ok().map(|v| v.to_vec())
+        };
+        assert_eq!(read_at(5, b"key"), None);
+        assert_eq!(read_at(10, b"key"), Some(b"v10".to_vec()));
+        assert_eq!(read_at(19, b"key"), Some(b"v10".to_vec()));
+        assert_eq!(read_at(25, b"key"), Some(b"v20".to_vec()));
+        assert_eq!(read_at(12, b"other"), None);
+        assert_eq!(read_at(15, b"other"), Some(b"o15".to_vec()));
+
+        // so must the timestamp of a read
+        let ropts = ReadOptions::default().timestamp(b"bad");
+        let err = cf.get(&ropts, b"key").unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::InvalidArgument);
+        assert!(cf.new_iterator(&ropts).status().is_err());
+        assert!(cf.get(&ReadOptions::default(), b"key").is_err());
+    }
+
+    #[test]
+    fn reversed_bitwise_comparator_with_timestamp() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let cf = db
+            .create_column_family(
+                &ColumnFamilyOptions::default().bitwise_comparator_with_timestamp(4, true),
+                "timestamped",
+            )
+            .unwrap();
+
+        let wopts = WriteOptions::default();
+        assert!(cf.put_with_ts(&wopts, b"a", &1u32.to_le_bytes(), b"a1").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"b", &1u32.to_le_bytes(), b"b1").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"b", &300u32.to_le_bytes(), b"b300").is_ok());
+
+        let ts = 500u32.to_le_bytes();
+        let mut it = cf.new_iterator(&ReadOptions::default().timestamp(&ts));
+        it.seek_to_first();
+        let mut values = vec![];
+        while it.is_valid() {
+            values.push(it.value().to_vec());
+            it.next();
+        }
+        assert_eq!(values, vec![b"b300".to_vec(), b"a1".to_vec()]);
+    }
+
+    #[test]
+    fn snapshot_with_timestamp() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let cf = db
+            .create_column_family(
+                &ColumnFamilyOptions::default().bitwise_comparator_with_timestamp(8, false),
+                "timestamped",
+            )
+            .unwrap();
+
+        let wopts = WriteOptions::default();
+        assert!(cf.put_with_ts(&wopts, b"key", &10u64.to_le_bytes(), b"v10").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"key", &30u64.to_le_bytes(), b"v30").is_ok());
+        let snap = db.get_snapshot().unwrap();
+        // written after the snapshot, with a timestamp in the read range
+        assert!(cf.put_with_ts(&wopts, b"key", &20u64.to_le_bytes(), b"v20").is_ok());
+        assert!(cf.put_with_ts(&wopts, b"new", &5u64.to_le_bytes(), b"n5").is_ok());
+
+        let ts = 25u64.to_le_bytes();
+        let ropts = ReadOptions::default().snapshot(Some(&snap)).timestamp(&ts);
+        assert_eq!(cf.get(&ropts, b"key").unwrap(), b"v10");
+        assert!(cf.get(&ropts, b"new").unwrap_err().is_not_found());
+
+        let kvs = cf
+            .new_iterator(&ropts)
+            .map(|(k, v)| (k.to_vec(), v.to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(kvs, vec![(b"key".to_vec(), b"v10".to_vec())]);
+
+        // without the snapshot, newer writes under the timestamp are visible
+        let ropts = ReadOptions::default().timestamp(&ts);
+        assert_eq!(cf.get(&ropts, b"key").unwrap(), b"v20");
+        assert_eq!(cf.get(&ropts, b"new").unwrap(), b"n5");
+    }
+
     pub struct MyComparator;
 
     impl Comparator for MyComparator {
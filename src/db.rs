@@ -7,26 +7,33 @@ use std::iter::IntoIterator;
 use std::mem;
 use std::ops;
 use std::os::raw::{c_char, c_int, c_void};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use rocks_sys as ll;
 
 use crate::debug::KeyVersionVec;
+use crate::error::{Code, SubCode};
+use crate::file_checksum::FileChecksumInfo;
 use crate::iterator::Iterator;
 use crate::metadata::{ColumnFamilyMetaData, LevelMetaData, LiveFileMetaData, SstFileMetaData};
 use crate::options::{
-    ColumnFamilyOptions, CompactRangeOptions, CompactionOptions, DBOptions, FlushOptions, IngestExternalFileOptions,
-    Options, ReadOptions, WriteOptions,
+    BottommostLevelCompaction, ColumnFamilyOptions, CompactRangeOptions, CompactionOptions, DBOptions, FlushOptions,
+    IngestExternalFileOptions, Options, ReadOptions, WriteOptions,
 };
 use crate::slice::{CVec, PinnableSlice};
 use crate::snapshot::Snapshot;
+use crate::stats_history::StatsHistoryIterator;
 use crate::table_properties::TablePropertiesCollection;
+use crate::thread_status::ThreadStatus;
 use crate::to_raw::{FromRaw, ToRaw};
-use crate::transaction_log::{LogFile, TransactionLogIterator};
+use crate::transaction_log::{LogFile, TransactionLogIterator, TransactionLogIteratorOptions};
 use crate::types::SequenceNumber;
 use crate::write_batch::WriteBatch;
 use crate::{Error, Result};
@@ -158,10 +165,14 @@ impl Drop for ColumnFamily {
             unsafe {
                 ll::rocks_db_destroy_column_family_handle(self.db.raw, self.raw(), &mut status);
                 assert!(Error::from_ll(status).is_ok());
-                // make underlying cf_handle a nullptr, rocks-sys will skip deleting it.
-                self.handle.raw = ptr::null_mut();
+            }
+        } else {
+            unsafe {
+                ll::rocks_db_release_default_column_family(self.db.raw, self.raw());
             }
         }
+        // make underlying cf_handle a nullptr, rocks-sys will skip deleting it.
+        self.handle.raw = ptr::null_mut();
     }
 }
 
@@ -220,6 +231,10 @@ impl ColumnFamily {
         }
     }
 
+    pub fn put_with_ts(&self, options: &WriteOptions, key: &[u8], ts: &[u8], value: &[u8]) -> Result<()> {
+        self.db.put_cf_with_ts(options, self, key, ts, value)
+    }
+
     pub fn delete(&self, options: &WriteOptions, key: &[u8]) -> Result<()> {
         let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
@@ -302,6 +317,27 @@ impl ColumnFamily {
         }
     }
 
+    /// Like `get()`, but copies the value into a buffer allocated on the C++ side.
+    ///
+    /// Compared to `get()` followed by `to_vec()`, this only saves the copy into
+    /// a Rust `Vec<u8>`. The value is still copied once out of RocksDB.
+    pub fn get_raw(&self, options: &ReadOptions, key: &[u8]) -> Result<CVec<u8>> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        let mut vallen = 0;
+        unsafe {
+            let ptr = ll::rocks_db_get_cf(
+                self.db.raw,
+                options.raw(),
+                self.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                &mut vallen,
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| CVec::from_raw_parts(ptr as *mut u8, vallen))
+        }
+    }
+
     pub fn multi_get(&self, options: &ReadOptions, keys: &[&[u8]]) -> Vec<Result<CVec<u8>>> {
         let num_keys = keys.len();
         let mut c_keys: Vec<*const c_char> = Vec::with_capacity(num_keys);
@@ -401,6 +437,10 @@ impl ColumnFamily {
         }
     }
 
+    pub fn get_map_property(&self, property: &str) -> Option<HashMap<String, String>> {
+        self.db.get_map_property_cf(self, property)
+    }
+
     pub fn get_int_property(&self, property: &str) -> Option<u64> {
         let mut val = 0;
         let ok = unsafe {
@@ -632,6 +672,121 @@ impl ColumnFamily {
     // ================================================================================
 }
 
+/// Collect and free the key/value lists filled by `rocks_db_get_map_property*`.
+unsafe fn map_property_from_raw(
+    keys: *mut *mut c_char,
+    values: *mut *mut c_char,
+    len: usize,
+) -> HashMap<String, String> {
+    let mut ret = HashMap::with_capacity(len);
+    for i in 0..len {
+        ret.insert(
+            CStr::from_ptr(*keys.add(i)).to_string_lossy().into_owned(),
+            CStr::from_ptr(*values.add(i)).to_string_lossy().into_owned(),
+        );
+    }
+    ll::rocks_db_get_map_property_destroy(keys, values, len);
+    ret
+}
+
+/// External SST files to ingest into one column family, used by
+/// `DBRef::ingest_external_files()`.
+#[derive(Debug)]
+pub struct IngestExternalFileArg<'a> {
+    pub column_family: &'a ColumnFamilyHandle,
+    pub external_files: Vec<PathBuf>,
+}
+
+impl<'a> IngestExternalFileArg<'a> {
+    pub fn new<P: AsRef<Path>, T: IntoIterator<Item = P>>(
+        column_family: &'a ColumnFamilyHandle,
+        external_files: T,
+    ) -> IngestExternalFileArg<'a> {
+        IngestExternalFileArg {
+            column_family,
+            external_files: external_files.into_iter().map(|p| p.as_ref().to_path_buf()).collect(),
+        }
+    }
+}
+
+/// What happened to the write-ahead log while opening a DB, returned by
+/// `DB::open_and_report_recovery()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// Number of WAL records (write batches) replayed into the memtables.
+    pub records_replayed: u64,
+    /// Whether the WAL is corrupted beyond a torn last record, which
+    /// `DBOptions::wal_recovery_mode()` tolerated.
+    pub wal_corrupted: bool,
+}
+
+impl RecoveryReport {
+    /// Whether any WAL data was skipped, i.e. some writes acknowledged before
+    /// the crash may be missing.
+    ///
+    /// With `WALRecoveryMode::PointInTimeRecovery`, replay stops at the first
+    /// corruption, so every record after it is lost as well.
+    pub fn has_skipped_records(&self) -> bool {
+        self.wal_corrupted
+    }
+}
+
+/// What a manual compaction did to a column family, returned by
+/// `DB::compact_range_and_report()`.
+///
+/// Derived from the column family metadata before and after the compaction, so
+/// background flushes or compactions running at the same time are included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactionResult {
+    /// Number of files at each level before the compaction.
+    pub level_files_before: Vec<usize>,
+    /// Number of files at each level after the compaction.
+    pub level_files_after: Vec<usize>,
+    /// Files that survived, but at another level, i.e. trivially moved.
+    pub files_moved: usize,
+    /// Files that were rewritten, i.e. the compaction inputs.
+    pub input_files: usize,
+    /// Newly written files, i.e. the compaction outputs.
+    pub output_files: usize,
+    /// Total size of the input files.
+    pub bytes_read: u64,
+    /// Total size of the output files.
+    pub bytes_written: u64,
+}
+
+impl CompactionResult {
+    /// Whether files only changed levels without being rewritten, e.g. with
+    /// `CompactRangeOptions::change_level(true)` on already compacted data.
+    pub fn is_trivial_move(&self) -> bool {
+        self.files_moved > 0 && self.input_files == 0 && self.output_files == 0
+    }
+}
+
+/// What a secondary instance picked up from the primary, returned by
+/// `DB::try_catch_up_with_primary()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatchUpReport {
+    /// Latest sequence number before catching up.
+    pub sequence_before: SequenceNumber,
+    /// Latest sequence number after catching up.
+    pub sequence_after: SequenceNumber,
+    /// Names of the column families whose data changed, in the order they were
+    /// passed to `try_catch_up_with_primary_cf()`.
+    pub changed_column_families: Vec<String>,
+}
+
+impl CatchUpReport {
+    /// Whether any new writes were replayed from the primary.
+    pub fn has_new_writes(&self) -> bool {
+        self.sequence_after > self.sequence_before
+    }
+
+    /// Whether the column family named `name` changed.
+    pub fn is_changed(&self, name: &str) -> bool {
+        self.changed_column_families.iter().any(|cf| cf == name)
+    }
+}
+
 /// Borrowed DB handle
 pub struct DBRef {
     raw: *mut ll::rocks_db_t,
@@ -640,6 +795,8 @@ pub struct DBRef {
 impl Drop for DBRef {
     #[inline]
     fn drop(&mut self) {
+        // Also runs on panic paths. The DB is closed either way, but any error
+        // from the final flush is lost, use `DB::close()` to get it.
         unsafe {
             ll::rocks_db_close(self.raw);
         }
@@ -740,6 +897,15 @@ impl DB {
     /// If everything is OK, handles will on return be the same size
     /// as `column_families` --- `handles[i]` will be a handle that you
     /// will use to operate on column family `column_family[i]`.
+    ///
+    /// # Opening many column families
+    ///
+    /// Column families are recovered from the MANIFEST one after another, but
+    /// the open time is usually dominated by opening their table files. With
+    /// `max_open_files(-1)`, the table files of each column family are opened
+    /// up front by `max_file_opening_threads` threads in parallel.
+    /// `skip_stats_update_on_db_open` and
+    /// `skip_checking_sst_file_sizes_on_db_open` avoid further per-file work.
     pub fn open_with_column_families<CF: Into<ColumnFamilyDescriptor>, P: AsRef<Path>, I: IntoIterator<Item = CF>>(
         options: &DBOptions,
         name: P,
@@ -793,6 +959,112 @@ impl DB {
         }
     }
 
+    /// Open DB with a time to live, in seconds, for all keys.
+    ///
+    /// Each value gets a timestamp appended when it's written, and entries
+    /// older than `ttl` are dropped by compactions. Expiry is lazy: an expired
+    /// key stays readable until a compaction removes it. A `ttl` of 0 or less
+    /// means keep forever.
+    ///
+    /// A DB opened with a ttl must always be opened with a ttl afterwards,
+    /// since the timestamps are stored as part of the values.
+    pub fn open_with_ttl<T: AsRef<Options>, P: AsRef<Path>>(options: T, name: P, ttl: i32) -> Result<DB> {
+        let opt = options.as_ref().raw();
+        let dbname = name.as_ref().to_str().and_then(|s| CString::new(s).ok()).unwrap();
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let db_ptr = ll::rocks_db_open_with_ttl(opt, dbname.as_ptr(), ttl, &mut status);
+            Error::from_ll(status).map(|_| DB::from_ll(db_ptr))
+        }
+    }
+
+    /// Open DB with column families, each with its own time to live in
+    /// seconds. See `open_with_ttl()`.
+    pub fn open_with_column_families_and_ttl<
+        CF: Into<ColumnFamilyDescriptor>,
+        P: AsRef<Path>,
+        I: IntoIterator<Item = (CF, i32)>,
+    >(
+        options: &DBOptions,
+        name: P,
+        column_families: I,
+    ) -> Result<(DB, Vec<ColumnFamily>)> {
+        let dbname = name.as_ref().to_str().and_then(|s| CString::new(s).ok()).unwrap();
+
+        let (cfs, ttls): (Vec<ColumnFamilyDescriptor>, Vec<i32>) = column_families
+            .into_iter()
+            .map(|(desc, ttl)| (desc.into(), ttl))
+            .unzip();
+
+        let num_column_families = cfs.len();
+        let cfnames = cfs.iter().map(|cf| cf.name_as_ptr()).collect::<Vec<_>>();
+        let cfopts = cfs
+            .iter()
+            .map(|cf| cf.options.raw() as *const _)
+            .collect::<Vec<*const ll::rocks_cfoptions_t>>();
+        let mut cfhandles = vec![ptr::null_mut(); num_column_families];
+
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let db_ptr = ll::rocks_db_open_column_families_with_ttl(
+                options.raw(),
+                dbname.as_ptr(),
+                num_column_families as c_int,
+                cfnames.as_ptr(),
+                cfopts.as_ptr(),
+                ttls.as_ptr(),
+                cfhandles.as_mut_ptr(),
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| {
+                let db = DB::from_ll(db_ptr);
+                let db_ref = db.context.clone();
+                (
+                    db,
+                    cfhandles
+                        .into_iter()
+                        .map(|p| ColumnFamily {
+                            handle: ColumnFamilyHandle { raw: p },
+                            db: db_ref.clone(),
+                            owned: true,
+                        })
+                        .collect(),
+                )
+            })
+        }
+    }
+
+    /// Open DB like `open()`, and report how the write-ahead log was
+    /// recovered.
+    ///
+    /// The replayed records are counted with a WAL filter, chained to the one
+    /// in the options, if any. Unless `DBOptions::wal_recovery_mode()` already
+    /// fails on corruption, the WAL is first replayed in a read only DB with
+    /// `WALRecoveryMode::TolerateCorruptedTailRecords` to detect it, which
+    /// doubles the recovery time. A trailing record cut short by a crash is
+    /// not reported.
+    pub fn open_and_report_recovery<T: AsRef<Options>, P: AsRef<Path>>(
+        options: T,
+        name: P,
+    ) -> Result<(DB, RecoveryReport)> {
+        let opt = options.as_ref().raw();
+        let dbname = name.as_ref().to_str().and_then(|s| CString::new(s).ok()).unwrap();
+        let mut report = RecoveryReport::default();
+        let mut wal_corrupted = 0;
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let db_ptr = ll::rocks_db_open_and_report_recovery(
+                opt,
+                dbname.as_ptr(),
+                &mut report.records_replayed,
+                &mut wal_corrupted,
+                &mut status,
+            );
+            report.wal_corrupted = wal_corrupted != 0;
+            Error::from_ll(status).map(|_| (DB::from_ll(db_ptr), report))
+        }
+    }
+
     /// Open the database for read only. All DB interfaces
     /// that modify data, like `put/delete`, will return error.
     /// If the db is opened in read only mode, then no compactions
@@ -811,6 +1083,104 @@ impl DB {
         }
     }
 
+    /// Open DB as secondary instance with only the default column family.
+    ///
+    /// The secondary instance can be used to read from the primary DB
+    /// without interfering with it. `secondary_path` points to a directory
+    /// where the secondary instance stores its info log.
+    ///
+    /// Call `try_catch_up_with_primary()` to make the secondary instance catch up
+    /// with the primary, or `follow_primary()` to keep polling in a loop.
+    ///
+    /// The secondary instance requires `max_open_files = -1`.
+    pub fn open_as_secondary<P: AsRef<Path>, S: AsRef<Path>>(
+        options: &Options,
+        name: P,
+        secondary_path: S,
+    ) -> Result<DB> {
+        let dbname = name.as_ref().to_str().and_then(|s| CString::new(s).ok()).unwrap();
+        let secondary_path = secondary_path
+            .as_ref()
+            .to_str()
+            .and_then(|s| CString::new(s).ok())
+            .unwrap();
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let db_ptr =
+                ll::rocks_db_open_as_secondary(options.raw(), dbname.as_ptr(), secondary_path.as_ptr(), &mut status);
+            Error::from_ll(status).map(|_| DB::from_ll(db_ptr))
+        }
+    }
+
+    /// Open DB as secondary instance with column families. See
+    /// `open_as_secondary()`.
+    ///
+    /// `column_families` may be a subset of the column families of the
+    /// primary, but must include the default column family.
+    pub fn open_as_secondary_with_column_families<
+        CF: Into<ColumnFamilyDescriptor>,
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+        I: IntoIterator<Item = CF>,
+    >(
+        options: &DBOptions,
+        name: P,
+        secondary_path: S,
+        column_families: I,
+    ) -> Result<(DB, Vec<ColumnFamily>)> {
+        let dbname = name.as_ref().to_str().and_then(|s| CString::new(s).ok()).unwrap();
+        let secondary_path = secondary_path
+            .as_ref()
+            .to_str()
+            .and_then(|s| CString::new(s).ok())
+            .unwrap();
+
+        let cfs = column_families
+            .into_iter()
+            .map(|desc| desc.into())
+            .collect::<Vec<ColumnFamilyDescriptor>>();
+
+        let num_column_families = cfs.len();
+        // for ffi
+        let mut cfnames: Vec<*const c_char> = Vec::with_capacity(num_column_families);
+        let mut cfopts: Vec<*const ll::rocks_cfoptions_t> = Vec::with_capacity(num_column_families);
+        let mut cfhandles = vec![ptr::null_mut(); num_column_families];
+
+        for cf in &cfs {
+            cfnames.push(cf.name_as_ptr());
+            cfopts.push(cf.options.raw());
+        }
+
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let db_ptr = ll::rocks_db_open_as_secondary_column_families(
+                options.raw(),
+                dbname.as_ptr(),
+                secondary_path.as_ptr(),
+                num_column_families as c_int,
+                cfnames.as_ptr(),
+                cfopts.as_ptr(),
+                cfhandles.as_mut_ptr(),
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| {
+                let db = DB::from_ll(db_ptr);
+                let db_ref = db.context.clone();
+                (
+                    db,
+                    cfhandles
+                        .into_iter()
+                        .map(|p| ColumnFamily {
+                            handle: ColumnFamilyHandle { raw: p },
+                            db: db_ref.clone(),
+                            owned: true,
+                        })
+                        .collect(),
+                )
+            })
+        }
+    }
+
     /// `ListColumnFamilies` will open the DB specified by argument name
     /// and return the list of all column nfamilies in that DB
     /// through `column_families` argument. The ordering of
@@ -871,18 +1241,36 @@ impl DB {
             owned: false,
         }
     }
+
+    /// Close the DB, returning any error from flushing memtables or closing
+    /// files.
+    ///
+    /// Memtables holding writes not in the WAL are flushed first, unless
+    /// `avoid_flush_during_shutdown` is set. Fails with `Aborted` if there
+    /// are unreleased snapshots, the DB is still closed when it is dropped.
+    ///
+    /// Fails with `Busy` while any `ColumnFamily` handle is alive, they keep
+    /// the DB open until dropped.
+    pub fn close(self) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_db_close_with_status(self.raw(), &mut status);
+            Error::from_ll(status)
+        }
+    }
 }
 
 impl DBRef {
     /// Set the database entry for `"key"` to `"value"`.
     /// If `"key"` already exists, it will be overwritten.
-    /// Returns OK on success, and a non-OK status on error.
+    /// Returns the sequence number assigned to the write on success, and a
+    /// non-OK status on error.
     ///
     /// Note: consider setting `options.sync = true`.
-    pub fn put(&self, options: &WriteOptions, key: &[u8], value: &[u8]) -> Result<()> {
+    pub fn put(&self, options: &WriteOptions, key: &[u8], value: &[u8]) -> Result<SequenceNumber> {
         let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
-            ll::rocks_db_put(
+            let seq = ll::rocks_db_put(
                 self.raw(),
                 options.raw(),
                 key.as_ptr() as *const _,
@@ -891,7 +1279,7 @@ impl DBRef {
                 value.len(),
                 &mut status,
             );
-            Error::from_ll(status)
+            Error::from_ll(status).map(|_| SequenceNumber(seq))
         }
     }
 
@@ -918,6 +1306,35 @@ impl DBRef {
         }
     }
 
+    /// Set the database entry for "key" to "value" at timestamp "ts", for
+    /// column families using a timestamp-aware comparator. Fails with
+    /// `InvalidArgument` if "ts" isn't as wide as the comparator expects.
+    pub fn put_cf_with_ts(
+        &self,
+        options: &WriteOptions,
+        column_family: &ColumnFamilyHandle,
+        key: &[u8],
+        ts: &[u8],
+        value: &[u8],
+    ) -> Result<()> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_put_cf_with_ts(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                ts.as_ptr() as *const _,
+                ts.len(),
+                value.as_ptr() as *const _,
+                value.len(),
+                &mut status,
+            );
+            Error::from_ll(status)
+        }
+    }
+
     /// Remove the database entry (if any) for "key".  Returns OK on
     /// success, and a non-OK status on error.  It is not an error if "key"
     /// did not exist in the database.
@@ -1089,14 +1506,39 @@ impl DBRef {
     /// If `updates` contains no update, WAL will still be synced if
     /// `options.sync=true`.
     ///
-    /// Returns OK on success, non-OK on failure.
+    /// Returns the sequence number assigned to the first update in the batch
+    /// on success, non-OK on failure. The updates get consecutive sequence
+    /// numbers, so the last one is `seq + updates.count() - 1`. An empty batch
+    /// consumes no sequence number, the latest sequence number is returned.
+    ///
+    /// RocksDB has no public per-write callback. The root DB stamps the batch
+    /// with its sequence number, stacked DBs such as one opened with a TTL
+    /// write a copy instead, so the latest sequence number is read back right
+    /// after the write. Every write through such a DB is serialized to keep it
+    /// exact.
     ///
     /// Note: consider setting `options.sync = true`.
-    pub fn write(&self, options: &WriteOptions, updates: &WriteBatch) -> Result<()> {
+    pub fn write(&self, options: &WriteOptions, updates: &WriteBatch) -> Result<SequenceNumber> {
         let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
-            ll::rocks_db_write(self.raw(), options.raw(), updates.raw(), &mut status);
-            Error::from_ll(status)
+            let seq = ll::rocks_db_write(self.raw(), options.raw(), updates.raw(), &mut status);
+            Error::from_ll(status).map(|_| SequenceNumber(seq))
+        }
+    }
+
+    /// Apply a batch, typically one rebuilt with `WriteBatch::from_data()`,
+    /// and return the sequence number assigned to its first update. Same as
+    /// `write()`.
+    ///
+    /// RocksDB doesn't allow applying a batch at an explicit sequence number
+    /// through its public API, a replica applying batches from
+    /// `WriteBatch::from_data()` in order gets the same relative order, but
+    /// not necessarily the same sequence numbers as the primary.
+    pub fn write_with_seq(&self, options: &WriteOptions, updates: &WriteBatch) -> Result<SequenceNumber> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let seq = ll::rocks_db_write_with_seq(self.raw(), options.raw(), updates.raw(), &mut status);
+            Error::from_ll(status).map(|_| SequenceNumber(seq))
         }
     }
 
@@ -1124,6 +1566,26 @@ impl DBRef {
         }
     }
 
+    /// Like `get()`, but copies the value into a buffer allocated on the C++ side.
+    ///
+    /// Compared to `get()` followed by `to_vec()`, this only saves the copy into
+    /// a Rust `Vec<u8>`. The value is still copied once out of RocksDB.
+    pub fn get_raw(&self, options: &ReadOptions, key: &[u8]) -> Result<CVec<u8>> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        let mut vallen = 0;
+        unsafe {
+            let ptr = ll::rocks_db_get(
+                self.raw(),
+                options.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                &mut vallen,
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| CVec::from_raw_parts(ptr as *mut u8, vallen))
+        }
+    }
+
     pub fn get_cf(
         &self,
         options: &ReadOptions,
@@ -1193,6 +1655,11 @@ impl DBRef {
         }
     }
 
+    /// Like `multi_get()`, with the i'th key read from `column_families[i]`.
+    ///
+    /// All keys are read at the same sequence number, the snapshot in
+    /// `options` if set, else the latest one when the call starts. This gives
+    /// a consistent view of a row split across several column families.
     pub fn multi_get_cf(
         &self,
         options: &ReadOptions,
@@ -1236,6 +1703,57 @@ impl DBRef {
         }
     }
 
+    /// Like `multi_get()` on a single column family, using the batched
+    /// `MultiGet` which looks up keys in the same table file together,
+    /// sharing block reads and filter probes between them.
+    ///
+    /// The keys are sorted internally first. Pass `sorted_input = true` when
+    /// `keys` are already in comparator order, e.g. collected from an index
+    /// scan, to skip that sort. The keys must then really be sorted, otherwise
+    /// lookups may miss. Results are in the order of `keys` either way.
+    pub fn batched_multi_get_cf(
+        &self,
+        options: &ReadOptions,
+        column_family: &ColumnFamilyHandle,
+        keys: &[&[u8]],
+        sorted_input: bool,
+    ) -> Vec<Result<CVec<u8>>> {
+        let num_keys = keys.len();
+        let mut c_keys: Vec<*const c_char> = Vec::with_capacity(num_keys);
+        let mut c_keys_lens = Vec::with_capacity(num_keys);
+
+        let mut vals = vec![ptr::null_mut(); num_keys];
+        let mut vals_lens = vec![0_usize; num_keys];
+
+        for key in keys {
+            c_keys.push(key.as_ptr() as *const c_char);
+            c_keys_lens.push(key.len());
+        }
+
+        let mut status: Vec<*mut ll::rocks_status_t> = vec![ptr::null_mut(); num_keys];
+        let mut ret = Vec::with_capacity(num_keys);
+
+        unsafe {
+            ll::rocks_db_batched_multi_get_cf(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                num_keys,
+                c_keys.as_ptr(),
+                c_keys_lens.as_ptr(),
+                vals.as_mut_ptr(),
+                vals_lens.as_mut_ptr(),
+                status.as_mut_ptr(),
+                sorted_input as u8,
+            );
+
+            for i in 0..num_keys {
+                ret.push(Error::from_ll(status[i]).map(|_| CVec::from_raw_parts(vals[i] as *mut u8, vals_lens[i])));
+            }
+            ret
+        }
+    }
+
     /// If the key definitely does not exist in the database, then this method
     /// returns false, else true. If the caller wants to obtain value when the key
     /// is found in memory, a bool for 'value_found' must be passed. 'value_found'
@@ -1322,6 +1840,65 @@ impl DBRef {
         }
     }
 
+    /// Returns whether `key` is present in the column family, honoring the snapshot
+    /// of `options`. The value is not copied out.
+    ///
+    /// Bloom filters and memtables are consulted first, so absent keys are usually
+    /// answered without IO. A read error is reported as absent.
+    pub fn key_exists(&self, options: &ReadOptions, column_family: &ColumnFamilyHandle, key: &[u8]) -> bool {
+        unsafe {
+            ll::rocks_db_key_exists_cf(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+            ) != 0
+        }
+    }
+
+    /// Returns the merge operands of `key` without running the merge operator,
+    /// oldest first. If the key has a base value, it is the first element.
+    ///
+    /// At most `max_operands` are returned. If there are more operands than that,
+    /// an `Incomplete` error is returned. `max_operands` must be between 1 and
+    /// 65536, otherwise an `InvalidArgument` error is returned.
+    pub fn get_merge_operands(
+        &self,
+        options: &ReadOptions,
+        column_family: &ColumnFamilyHandle,
+        key: &[u8],
+        max_operands: usize,
+    ) -> Result<Vec<Vec<u8>>> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        // saturate instead of wrapping to a negative count, both are rejected
+        let max_operands = max_operands.min(c_int::MAX as usize) as c_int;
+        unsafe {
+            let operands = ll::rocks_db_get_merge_operands(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                key.as_ptr() as *const _,
+                key.len(),
+                max_operands,
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| {
+                let n = ll::cxx_string_vector_size(operands) as usize;
+                let mut ret = Vec::with_capacity(n);
+                for i in 0..n {
+                    let op = slice::from_raw_parts(
+                        ll::cxx_string_vector_nth(operands, i) as *const u8,
+                        ll::cxx_string_vector_nth_size(operands, i),
+                    );
+                    ret.push(op.to_vec());
+                }
+                ll::cxx_string_vector_destory(operands);
+                ret
+            })
+        }
+    }
+
     /// Return a heap-allocated iterator over the contents of the database.
     /// The result of NewIterator() is initially invalid (caller must
     /// call one of the Seek methods on the iterator before using it).
@@ -1371,6 +1948,10 @@ impl DBRef {
     ///
     /// nullptr will be returned if the DB fails to take a snapshot or does
     /// not support snapshot.
+    ///
+    /// For column families with user-defined timestamps, combine the
+    /// snapshot with `ReadOptions::timestamp()` to read as of a given
+    /// timestamp.
     pub fn get_snapshot(&self) -> Option<Snapshot> {
         unsafe {
             let ptr = ll::rocks_db_get_snapshot(self.raw());
@@ -1429,9 +2010,56 @@ impl DBRef {
         }
     }
 
-    // TODO:
-    pub fn get_map_property(&self, property: &str) -> Option<()> {
-        unimplemented!()
+    /// Like `get_property()`, but for properties whose value is a map, such as
+    /// `"rocksdb.cfstats"` or `"rocksdb.aggregated-table-properties"`.
+    ///
+    /// Returns `None` for properties without a map form, e.g.
+    /// `"rocksdb.cfstats-no-file-histogram"`.
+    pub fn get_map_property(&self, property: &str) -> Option<HashMap<String, String>> {
+        let mut keys = ptr::null_mut();
+        let mut values = ptr::null_mut();
+        let mut len = 0;
+        unsafe {
+            let ok = ll::rocks_db_get_map_property(
+                self.raw(),
+                property.as_bytes().as_ptr() as *const _,
+                property.len(),
+                &mut keys,
+                &mut values,
+                &mut len,
+            ) != 0;
+            if ok {
+                Some(map_property_from_raw(keys, values, len))
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn get_map_property_cf(
+        &self,
+        column_family: &ColumnFamilyHandle,
+        property: &str,
+    ) -> Option<HashMap<String, String>> {
+        let mut keys = ptr::null_mut();
+        let mut values = ptr::null_mut();
+        let mut len = 0;
+        unsafe {
+            let ok = ll::rocks_db_get_map_property_cf(
+                self.raw(),
+                column_family.raw(),
+                property.as_bytes().as_ptr() as *const _,
+                property.len(),
+                &mut keys,
+                &mut values,
+                &mut len,
+            ) != 0;
+            if ok {
+                Some(map_property_from_raw(keys, values, len))
+            } else {
+                None
+            }
+        }
     }
 
     /// Similar to `GetProperty()`, but only works for a subset of properties whose
@@ -1519,6 +2147,62 @@ impl DBRef {
         }
     }
 
+    /// Reset the DB's internal stats, the ones reported by the
+    /// `"rocksdb.stats"` family of properties.
+    ///
+    /// This doesn't touch the `Statistics` object set in `DBOptions`, use
+    /// `Statistics::reset()` for that.
+    pub fn reset_stats(&self) -> Result<()> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_reset_stats(self.raw(), &mut status);
+            Error::from_ll(status)
+        }
+    }
+
+    /// Estimated number of live keys in the column family, i.e. property
+    /// `"rocksdb.estimate-num-keys"`.
+    ///
+    /// Deletes and overwrites which have not been compacted yet make this
+    /// number inaccurate.
+    pub fn estimate_num_keys(&self, column_family: &ColumnFamilyHandle) -> u64 {
+        self.get_int_property_cf(column_family, "rocksdb.estimate-num-keys")
+            .unwrap_or(0)
+    }
+
+    /// Estimated number of delete tombstones in the column family, counting
+    /// both the memtables and the `num_deletions` of all table files.
+    ///
+    /// A tombstone is counted until a compaction drops it, even if it only
+    /// shadows another tombstone.
+    pub fn estimate_num_deletes(&self, column_family: &ColumnFamilyHandle) -> Result<u64> {
+        let in_memtables = [
+            "rocksdb.num-deletes-active-mem-table",
+            "rocksdb.num-deletes-imm-mem-tables",
+        ]
+        .iter()
+        .map(|prop| self.get_int_property_cf(column_family, prop).unwrap_or(0))
+        .sum::<u64>();
+        let props = self.get_properties_of_all_tables_cf(column_family)?;
+        let in_tables = props.iter().map(|(_, prop)| prop.num_deletions()).sum::<u64>();
+        Ok(in_memtables + in_tables)
+    }
+
+    /// Number of currently running compactions, i.e. property
+    /// `"rocksdb.num-running-compactions"`.
+    ///
+    /// Cheaper than walking `get_thread_list()` when only the count matters,
+    /// e.g. to back off a bulk loader while compactions catch up.
+    pub fn num_running_compactions(&self) -> u64 {
+        self.get_int_property("rocksdb.num-running-compactions").unwrap_or(0)
+    }
+
+    /// Number of currently running flushes, i.e. property
+    /// `"rocksdb.num-running-flushes"`.
+    pub fn num_running_flushes(&self) -> u64 {
+        self.get_int_property("rocksdb.num-running-flushes").unwrap_or(0)
+    }
+
     pub fn get_approximate_sizes(&self, column_family: &ColumnFamilyHandle, ranges: &[ops::Range<&[u8]>]) -> Vec<u64> {
         // include_flags: u8
         let num_ranges = ranges.len();
@@ -1608,6 +2292,95 @@ impl DBRef {
         }
     }
 
+    /// Compact the whole column family, including the bottommost level, e.g. to
+    /// reclaim space right after deleting lots of keys.
+    ///
+    /// Runs exclusively of automatic compactions, split into up to
+    /// `max_subcompactions` parallel subcompactions. This is `compact_range()`
+    /// with `BottommostLevelCompaction::Force`, `exclusive_manual_compaction`
+    /// and `max_subcompactions` set.
+    pub fn full_compact(&self, column_family: &ColumnFamilyHandle, max_subcompactions: u32) -> Result<()> {
+        let options = CompactRangeOptions::default()
+            .bottommost_level_compaction(BottommostLevelCompaction::Force)
+            .exclusive_manual_compaction(true)
+            .max_subcompactions(max_subcompactions);
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_compact_range_opt_cf(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                ptr::null(),
+                0,
+                ptr::null(),
+                0,
+                &mut status,
+            );
+            Error::from_ll(status)
+        }
+    }
+
+    /// Like `compact_range()` on the given column family, but reports which files
+    /// were moved, rewritten and written, see `CompactionResult`.
+    pub fn compact_range_and_report<R: AsCompactRange>(
+        &self,
+        options: &CompactRangeOptions,
+        column_family: &ColumnFamilyHandle,
+        range: R,
+    ) -> Result<CompactionResult> {
+        fn file_levels(meta: &ColumnFamilyMetaData) -> HashMap<String, (u32, u64)> {
+            meta.levels
+                .iter()
+                .flat_map(|level| level.files.iter().map(move |f| (f.name.clone(), (level.level, f.size))))
+                .collect()
+        }
+        fn level_file_counts(meta: &ColumnFamilyMetaData) -> Vec<usize> {
+            meta.levels.iter().map(|level| level.files.len()).collect()
+        }
+
+        let before = self.get_column_family_metadata(column_family);
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_compact_range_opt_cf(
+                self.raw(),
+                options.raw(),
+                column_family.raw(),
+                range.start_key() as *const _,
+                range.start_key_len(),
+                range.end_key() as *const _,
+                range.end_key_len(),
+                &mut status,
+            );
+        }
+        Error::from_ll(status)?;
+        let after = self.get_column_family_metadata(column_family);
+
+        let files_before = file_levels(&before);
+        let files_after = file_levels(&after);
+        let mut result = CompactionResult {
+            level_files_before: level_file_counts(&before),
+            level_files_after: level_file_counts(&after),
+            ..Default::default()
+        };
+        for (name, &(level, size)) in &files_before {
+            match files_after.get(name) {
+                Some(&(new_level, _)) if new_level != level => result.files_moved += 1,
+                Some(_) => (),
+                None => {
+                    result.input_files += 1;
+                    result.bytes_read += size;
+                },
+            }
+        }
+        for (name, &(_, size)) in &files_after {
+            if !files_before.contains_key(name) {
+                result.output_files += 1;
+                result.bytes_written += size;
+            }
+        }
+        Ok(result)
+    }
+
     pub fn set_options<T, H>(&self, column_family: &ColumnFamilyHandle, new_options: H) -> Result<()>
     where
         T: AsRef<str>,
@@ -1715,6 +2488,44 @@ impl DBRef {
         }
     }
 
+    /// Promote L0 files to the target level without rewriting them.
+    ///
+    /// Fails unless the key ranges of all L0 files are disjoint, and all levels
+    /// up to and including `target_level` are empty.
+    pub fn promote_l0(&self, column_family: &ColumnFamilyHandle, target_level: i32) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_db_promote_l0(self.raw(), column_family.raw(), target_level as c_int, &mut status);
+            Error::from_ll(status)
+        }
+    }
+
+    /// Mark the files overlapping `[begin, end]` for compaction, without
+    /// waiting for it to happen. `None` means the start or end of the key space.
+    ///
+    /// Unlike `compact_range()`, this returns immediately, the marked files are
+    /// picked up by the regular background compactions later on.
+    pub fn suggest_compact_range(
+        &self,
+        column_family: &ColumnFamilyHandle,
+        begin: Option<&[u8]>,
+        end: Option<&[u8]>,
+    ) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_db_suggest_compact_range(
+                self.raw(),
+                column_family.raw(),
+                begin.map_or(ptr::null(), |k| k.as_ptr() as *const _),
+                begin.map_or(0, |k| k.len()),
+                end.map_or(ptr::null(), |k| k.as_ptr() as *const _),
+                end.map_or(0, |k| k.len()),
+                &mut status,
+            );
+            Error::from_ll(status)
+        }
+    }
+
     /// This function will wait until all currently running background processes
     /// finish. After it returns, no background process will be run until
     /// UnblockBackgroundWork is called
@@ -1790,9 +2601,39 @@ impl DBRef {
         name
     }
 
-    // TODO:
-    // get options
-    // get db options
+    /// The options the column family is running with, after RocksDB has
+    /// sanitized them and filled in defaults.
+    ///
+    /// This is a copy, changing it has no effect on the DB, use
+    /// `set_options()` for that.
+    pub fn get_options(&self, column_family: &ColumnFamilyHandle) -> ColumnFamilyOptions {
+        unsafe { ColumnFamilyOptions::from_ll(ll::rocks_db_get_options_cf(self.raw(), column_family.raw())) }
+    }
+
+    /// The DB wide options the DB is running with, after RocksDB has
+    /// sanitized them and filled in defaults.
+    ///
+    /// This is a copy, changing it has no effect on the DB, use
+    /// `set_db_options()` for that.
+    pub fn get_db_options(&self) -> DBOptions {
+        unsafe { DBOptions::from_ll(ll::rocks_db_get_db_options(self.raw())) }
+    }
+
+    /// Returns the status of the background threads of the DB's `Env` that are
+    /// currently working on this DB, e.g. running a flush or a compaction.
+    ///
+    /// Requires `DBOptions::enable_thread_tracking(true)`, otherwise the list is empty.
+    pub fn get_thread_list(&self) -> Vec<ThreadStatus> {
+        let mut len = 0;
+        unsafe {
+            let thread_status_arr = ll::rocks_db_get_thread_list(self.raw(), &mut len);
+            let ret = (0..len)
+                .map(|i| ThreadStatus::from_ll(*thread_status_arr.offset(i as isize)))
+                .collect();
+            ll::rocks_env_get_thread_list_destroy(thread_status_arr);
+            ret
+        }
+    }
 
     /// Flush all mem-table data.
     pub fn flush(&self, options: &FlushOptions) -> Result<()> {
@@ -1803,11 +2644,30 @@ impl DBRef {
         }
     }
 
+    /// Write the WAL buffer to the WAL file.
+    ///
+    /// With `DBOptions::manual_wal_flush` set, writes are only buffered in
+    /// memory and are lost if the process crashes before this is called. With
+    /// `sync = false` the buffer is handed to the OS, which survives a process
+    /// crash but not a machine crash or power loss. With `sync = true` the WAL
+    /// is also fsynced, like `sync_wal()`, which survives both.
+    ///
+    /// Without `manual_wal_flush`, each write already hands its WAL record to
+    /// the OS, so only `sync = true` has an effect.
+    pub fn flush_wal(&self, sync: bool) -> Result<()> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_flush_wal(self.raw(), sync as u8, &mut status);
+            Error::from_ll(status)
+        }
+    }
+
     /// Sync the wal. Note that Write() followed by SyncWAL() is not exactly the
     /// same as Write() with sync=true: in the latter case the changes won't be
     /// visible until the sync is done.
     ///
-    /// Currently only works if allow_mmap_writes = false in Options.
+    /// Currently only works if allow_mmap_writes = false in Options, otherwise
+    /// fails with `Code::NotSupported`.
     pub fn sync_wal(&self) -> Result<()> {
         let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
@@ -1821,6 +2681,90 @@ impl DBRef {
         unsafe { ll::rocks_db_get_latest_sequence_number(self.raw()).into() }
     }
 
+    /// Instructs DB to preserve deletes with sequence numbers >= passed seqnum.
+    /// Has no effect if `DBOptions.preserve_deletes` is set to false.
+    ///
+    /// This function assumes that user calls this function with monotonically
+    /// increasing seqnums (otherwise we can't guarantee that a particular delete
+    /// hasn't been already processed); returns true if the value was successfully
+    /// updated, false if user attempted to call if with seqnum <= current value.
+    pub fn set_preserve_deletes(&self, seqnum: SequenceNumber) -> bool {
+        unsafe { ll::rocks_db_set_preserve_deletes_sequence_number(self.raw(), seqnum.0) != 0 }
+    }
+
+    /// Make the secondary instance catch up with the primary by tailing and
+    /// replaying the MANIFEST and WAL of the primary.
+    ///
+    /// Column families created by the primary after the secondary instance starts
+    /// will be ignored unless the secondary instance closes and restarts with the
+    /// newly created column families.
+    ///
+    /// Column families that exist before secondary instance starts and dropped by
+    /// the primary afterwards will be marked as dropped. However, as long as the
+    /// secondary instance does not delete the corresponding column family
+    /// handles, the data of the column family is still accessible to the
+    /// secondary.
+    ///
+    /// The returned report carries the latest sequence number before and after.
+    /// Use `try_catch_up_with_primary_cf()` to also learn which column families
+    /// changed.
+    pub fn try_catch_up_with_primary(&self) -> Result<CatchUpReport> {
+        self.try_catch_up_with_primary_cf(&[])
+    }
+
+    /// Same as `try_catch_up_with_primary()`, and reports which of
+    /// `column_families` changed, e.g. to invalidate caches selectively.
+    ///
+    /// A column family is considered changed when catching up installed a new
+    /// version of it, i.e. WAL records were replayed into its memtable or the
+    /// primary flushed or compacted it.
+    pub fn try_catch_up_with_primary_cf(&self, column_families: &[&ColumnFamilyHandle]) -> Result<CatchUpReport> {
+        const SUPER_VERSION_NUMBER: &str = "rocksdb.current-super-version-number";
+
+        let versions_before = column_families
+            .iter()
+            .map(|cf| self.get_int_property_cf(cf, SUPER_VERSION_NUMBER))
+            .collect::<Vec<_>>();
+        let sequence_before = self.get_latest_sequence_number();
+
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_try_catch_up_with_primary(self.raw(), &mut status);
+            Error::from_ll(status)?;
+        }
+
+        let changed_column_families = column_families
+            .iter()
+            .zip(versions_before)
+            .filter(|(cf, before)| self.get_int_property_cf(cf, SUPER_VERSION_NUMBER) != *before)
+            .map(|(cf, _)| cf.name().to_owned())
+            .collect();
+        Ok(CatchUpReport {
+            sequence_before,
+            sequence_after: self.get_latest_sequence_number(),
+            changed_column_families,
+        })
+    }
+
+    /// Keep the secondary instance following the primary, calling
+    /// `try_catch_up_with_primary()` every `interval` until `stop` is set.
+    ///
+    /// Transient errors, such as the primary deleting a file the secondary is
+    /// about to read, are retried at the next poll. Any other error stops the
+    /// loop and is returned.
+    pub fn follow_primary(&self, interval: Duration, stop: &AtomicBool) -> Result<()> {
+        while !stop.load(Ordering::Acquire) {
+            if let Err(e) = self.try_catch_up_with_primary() {
+                match (e.code(), e.subcode()) {
+                    (Code::TryAgain, _) | (Code::Busy, _) | (Code::IOError, SubCode::PathNotFound) => (),
+                    _ => return Err(e),
+                }
+            }
+            thread::sleep(interval);
+        }
+        Ok(())
+    }
+
     /// Prevent file deletions. Compactions will continue to occur,
     /// but no obsolete files will be deleted. Calling this multiple
     /// times have the same effect as calling it once.
@@ -1928,14 +2872,46 @@ impl DBRef {
     /// use this api, else the WAL files will get
     /// cleared aggressively and the iterator might keep getting invalid before
     /// an update is read.
+    ///
+    /// Rust: errors with `Code::Expired` if the WAL files holding `seq_number`
+    /// are already gone, so a consumer that fell behind can resync from a
+    /// snapshot instead. A `seq_number` not written yet is `Code::NotFound`.
+    ///
+    /// To check that, writes still buffered in memory under
+    /// `DBOptions::manual_wal_flush` are flushed to the WAL first, as
+    /// `flush_wal(false)` does. The WAL is not synced.
     pub fn get_updates_since(&self, seq_number: SequenceNumber) -> Result<TransactionLogIterator> {
+        self.get_updates_since_opt(seq_number, &TransactionLogIteratorOptions::default())
+    }
+
+    /// Same as `get_updates_since()`, with options for reading the WAL.
+    pub fn get_updates_since_opt(
+        &self,
+        seq_number: SequenceNumber,
+        options: &TransactionLogIteratorOptions,
+    ) -> Result<TransactionLogIterator> {
         let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
-            let iter_raw_ptr = ll::rocks_db_get_update_since(self.raw(), seq_number.0, &mut status);
+            let iter_raw_ptr = ll::rocks_db_get_update_since(
+                self.raw(),
+                seq_number.0,
+                options.verify_checksums as u8,
+                &mut status,
+            );
             Error::from_ll(status).map(|_| TransactionLogIterator::from_ll(iter_raw_ptr))
         }
     }
 
+    /// Returns the stats snapshots taken in `[start_time, end_time)`, in seconds
+    /// since the epoch. Requires `DBOptions::stats_persist_period_sec`.
+    pub fn get_stats_history(&self, start_time: u64, end_time: u64) -> Result<StatsHistoryIterator> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            let iter_raw_ptr = ll::rocks_db_get_stats_history(self.raw(), start_time, end_time, &mut status);
+            Error::from_ll(status).map(|_| StatsHistoryIterator::from_ll(iter_raw_ptr))
+        }
+    }
+
     /// Delete the file name from the db directory and update the internal state to
     /// reflect that. Supports deletion of sst and log files only. 'name' must be
     /// path relative to the db directory. eg. 000001.sst, /archive/000003.log
@@ -1976,6 +2952,9 @@ impl DBRef {
 
     /// Returns a list of all table files with their level, start key
     /// and end key
+    ///
+    /// Unlike `get_live_files()`, also carries file size, column family name
+    /// and sequence number range of every file.
     pub fn get_live_files_metadata(&self) -> Vec<LiveFileMetaData> {
         unsafe {
             let livefiles = ll::rocks_db_get_livefiles_metadata(self.raw());
@@ -2033,6 +3012,37 @@ impl DBRef {
         }
     }
 
+    /// Returns the full file checksum of every live SST file.
+    ///
+    /// Checksums are only generated for files created while
+    /// `DBOptions::file_checksum_gen_factory()` was set, other files have an
+    /// empty checksum.
+    pub fn get_live_files_checksum_info(&self) -> Result<Vec<FileChecksumInfo>> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let list = ll::rocks_db_get_live_files_checksum_info(self.raw(), &mut status);
+            if list.is_null() {
+                return Error::from_ll(status).map(|_| vec![]);
+            }
+            let cnt = ll::rocks_file_checksum_list_size(list);
+            let mut ret = Vec::with_capacity(cnt);
+            for i in 0..cnt {
+                let mut len = 0;
+                let ptr = ll::rocks_file_checksum_list_checksum(list, i, &mut len);
+                let checksum = slice::from_raw_parts(ptr as *const u8, len).to_vec();
+                let ptr = ll::rocks_file_checksum_list_func_name(list, i, &mut len);
+                let func_name = String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len)).into_owned();
+                ret.push(FileChecksumInfo {
+                    file_number: ll::rocks_file_checksum_list_file_number(list, i),
+                    checksum,
+                    func_name,
+                });
+            }
+            ll::rocks_file_checksum_list_destroy(list);
+            Error::from_ll(status).map(|_| ret)
+        }
+    }
+
     /// Obtains the meta data of the specified column family of the DB.
     pub fn get_column_family_metadata(&self, column_family: &ColumnFamilyHandle) -> ColumnFamilyMetaData {
         unsafe {
@@ -2183,6 +3193,56 @@ impl DBRef {
         }
     }
 
+    /// Ingest files into multiple column families, and record the result
+    /// atomically to the MANIFEST. Either all files of all column families
+    /// are ingested, or none of them is.
+    ///
+    /// The same `options` is used for every column family. Each column family
+    /// may appear at most once in `args`.
+    pub fn ingest_external_files(
+        &self,
+        args: &[IngestExternalFileArg],
+        options: &IngestExternalFileOptions,
+    ) -> Result<()> {
+        let c_cfs = args.iter().map(|arg| arg.column_family.raw).collect::<Vec<_>>();
+        let num_files = args.iter().map(|arg| arg.external_files.len()).collect::<Vec<_>>();
+        let mut c_files = vec![];
+        let mut c_files_lens = vec![];
+        for fpath in args.iter().flat_map(|arg| &arg.external_files) {
+            let fpath = fpath.to_str().expect("valid utf8 path");
+            c_files.push(fpath.as_ptr() as *const c_char);
+            c_files_lens.push(fpath.len());
+        }
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_ingest_external_files(
+                self.raw(),
+                args.len(),
+                c_cfs.as_ptr(),
+                num_files.as_ptr(),
+                c_files.as_ptr(),
+                c_files_lens.as_ptr(),
+                options.raw(),
+                &mut status,
+            );
+            Error::from_ll(status)
+        }
+    }
+
+    /// Verify the checksums of all blocks in all live SST files, reading every
+    /// block from storage.
+    ///
+    /// Unlike `verify_checksums` in `ReadOptions`, which only checks the blocks
+    /// touched by a read, this scans the whole DB. Returns `Code::Corruption`
+    /// on the first mismatch.
+    pub fn verify_checksum(&self, options: &ReadOptions) -> Result<()> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_verify_checksum(self.raw(), options.raw(), &mut status);
+            Error::from_ll(status)
+        }
+    }
+
     /// Sets the globally unique ID created at database creation time by invoking
     /// `Env::GenerateUniqueId()`, in identity. Returns Error::OK if identity could
     /// be set properly
@@ -2195,6 +3255,18 @@ impl DBRef {
         }
     }
 
+    /// Returns the ID of the current DB session. A new session ID is generated
+    /// every time the DB is opened, so unlike `get_db_identity()` it is not
+    /// stable across restarts.
+    pub fn get_db_session_id(&self) -> Result<String> {
+        let mut session_id = String::new();
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
+        unsafe {
+            ll::rocks_db_get_db_session_id(self.raw(), &mut session_id as *mut String as *mut _, &mut status);
+            Error::from_ll(status).map(|_| session_id)
+        }
+    }
+
     pub fn get_properties_of_all_tables_cf(
         &self,
         column_family: &ColumnFamilyHandle,
@@ -2206,6 +3278,9 @@ impl DBRef {
         }
     }
 
+    /// Like `get_properties_of_all_tables_cf`, but only for the table files
+    /// overlapping any of the given key ranges. Cheaper when only a part of
+    /// the key space is of interest.
     pub fn get_properties_of_tables_in_range(
         &self,
         column_family: &ColumnFamilyHandle,
@@ -2241,8 +3316,14 @@ impl DBRef {
     // debug
     /// Returns listing of all versions of keys in the provided user key range.
     /// The range is inclusive-inclusive, i.e., [`begin_key`, `end_key`].
-    /// The result is inserted into the provided vector, `key_versions`.
-    pub fn get_all_key_versions(&self, begin_key: &[u8], end_key: &[u8]) -> Result<KeyVersionVec> {
+    /// At most `max_num_ikeys` internal keys are returned, versions of the same
+    /// user key are ordered from newest to oldest.
+    pub fn get_all_key_versions(
+        &self,
+        begin_key: &[u8],
+        end_key: &[u8],
+        max_num_ikeys: usize,
+    ) -> Result<KeyVersionVec> {
         let mut status = ptr::null_mut();
         unsafe {
             let coll_ptr = ll::rocks_db_get_all_key_versions(
@@ -2251,6 +3332,7 @@ impl DBRef {
                 begin_key.len(),
                 end_key.as_ptr() as *const _,
                 end_key.len(),
+                max_num_ikeys,
                 &mut status,
             );
             Error::from_ll(status).map(|()| KeyVersionVec::from_ll(coll_ptr))
@@ -46,6 +46,7 @@ impl fmt::Debug for KeyVersion {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("KeyVersion")
             .field("user_key", &self.user_key())
+            .field("value", &self.value())
             .field("sequence", &self.sequence())
             .field("type", &self.value_type())
             .finish()
@@ -178,6 +179,7 @@ impl<'a> iter::Iterator for KeyVersionVecIter<'a> {
 mod tests {
     use std::iter;
     use super::super::rocksdb::*;
+    use super::ValueType;
 
     #[test]
     fn key_version() {
@@ -203,7 +205,7 @@ mod tests {
             }
         }
 
-        let vers = db.get_all_key_versions(b"\x00", b"\xff");
+        let vers = db.get_all_key_versions(b"\x00", b"\xff", 65535);
         assert!(vers.is_ok());
         let vers = vers.unwrap();
         // assert_eq!(vers.len(), 100);
@@ -217,4 +219,33 @@ mod tests {
         }
         assert!(false);
     }
+
+    #[test]
+    fn key_versions_of_rewritten_key() {
+        let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+            &tmp_dir,
+        ).unwrap();
+
+        assert!(db.put(&WriteOptions::default(), b"key", b"v1").is_ok());
+        assert!(db.delete(&WriteOptions::default(), b"key").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"key", b"v2").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"other", b"v").is_ok());
+
+        let vers = db.get_all_key_versions(b"key", b"key", 100).unwrap();
+        assert_eq!(vers.len(), 3);
+        let types = vers.iter().map(|v| v.value_type()).collect::<Vec<_>>();
+        assert_eq!(types, vec![ValueType::Value, ValueType::Deletion, ValueType::Value]);
+        assert!(vers.iter().all(|v| v.user_key() == b"key"));
+        assert_eq!(vers[0].value(), b"v2");
+        assert_eq!(vers[2].value(), b"v1");
+        assert!(vers[0].sequence().0 > vers[1].sequence().0);
+        assert!(vers[1].sequence().0 > vers[2].sequence().0);
+
+        let vers = db.get_all_key_versions(b"\x00", b"\xff", 2).unwrap();
+        assert_eq!(vers.len(), 2);
+    }
 }
@@ -13,9 +13,11 @@ use std::mem;
 use std::path::Path;
 use std::ptr;
 use std::str;
+use std::time::Duration;
 
 use rocks_sys as ll;
 
+use crate::env_encryption::{BlockCipher, KeyRing};
 use crate::thread_status::ThreadStatus;
 use crate::to_raw::{FromRaw, ToRaw};
 use crate::{Error, Result};
@@ -40,6 +42,20 @@ pub enum Priority {
     Total,
 }
 
+/// Priority for requesting bytes in rate limiter scheduler
+///
+/// Mirrors `Env::IOPriority` of RocksDB 6.29. `Total` only selects the sum
+/// over all priorities in statistics getters.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IoPriority {
+    Low = 0,
+    Mid,
+    High,
+    User,
+    Total,
+}
+
 /// Options while opening a file to read/write
 pub struct EnvOptions {
     raw: *mut ll::rocks_envoptions_t,
@@ -179,7 +195,7 @@ impl EnvOptions {
 
 /// Log levels for `Logger`
 #[repr(C)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum InfoLogLevel {
     Debug = 0,
     Info,
@@ -214,6 +230,23 @@ impl Logger {
         Logger { raw: raw }
     }
 
+    /// A logger passing every formatted line at or above `log_level` to `f`.
+    ///
+    /// Lines below the level are dropped before formatting, so a closure
+    /// behind a high level costs nothing for the lines it doesn't see. Note
+    /// that `DBOptions::info_log_level` only applies to loggers created by
+    /// RocksDB, use the level here or `set_log_level()` instead.
+    pub fn from_fn<F>(log_level: InfoLogLevel, f: F) -> Logger
+    where
+        F: Fn(InfoLogLevel, &str) + Send + Sync + 'static,
+    {
+        let f: Box<dyn Fn(InfoLogLevel, &str) + Send + Sync> = Box::new(f);
+        unsafe {
+            let raw_ptr = Box::into_raw(Box::new(f)); // Box<Box<Fn>>
+            Logger::from_ll(ll::rocks_logger_create_from_rust(raw_ptr as *mut _, log_level as _))
+        }
+    }
+
     /// Write an entry to the log file with the specified log level
     /// and format.  Any log with level under the internal log level
     /// of *this (see @SetInfoLogLevel and @GetInfoLogLevel) will not be
@@ -265,6 +298,16 @@ impl Drop for Env {
 
 unsafe impl Sync for Env {}
 
+impl Default for Env {
+    /// A new handle to the default environment of the current operating system,
+    /// i.e. the same env as `Env::default_instance()`.
+    fn default() -> Self {
+        Env {
+            raw: unsafe { ll::rocks_create_default_env() },
+        }
+    }
+}
+
 impl Env {
     /// Return a default environment suitable for the current operating
     /// system.  Sophisticated users may wish to provide their own Env
@@ -297,6 +340,82 @@ impl Env {
         }
     }
 
+    /// Returns a new environment that encrypts all files written through it,
+    /// and decrypts them on read, using `cipher` in CTR mode. All other tasks
+    /// are delegated to `base`.
+    ///
+    /// The same cipher must be used every time the DB is opened.
+    pub fn encrypted<T: BlockCipher + Send + Sync + 'static>(base: Env, cipher: T) -> Env {
+        let cipher: Box<dyn BlockCipher> = Box::new(cipher);
+        let raw = unsafe { ll::rocks_create_encrypted_env(base.raw, Box::into_raw(Box::new(cipher)) as *mut _) };
+        // base env is owned by the encrypted env from now on
+        mem::forget(base);
+        Env { raw: raw }
+    }
+
+    /// Same as `encrypted()`, but picks the cipher of each file by key id, so
+    /// that the key can be rotated. New files are written with the current key
+    /// of `keys`.
+    pub fn encrypted_with_keys(base: Env, keys: KeyRing) -> Env {
+        let mut key_ids = Vec::with_capacity(keys.ciphers.len());
+        let mut ciphers = Vec::with_capacity(keys.ciphers.len());
+        for (key_id, cipher) in keys.ciphers {
+            key_ids.push(key_id);
+            ciphers.push(Box::into_raw(Box::new(cipher)) as *mut _);
+        }
+        let raw = unsafe {
+            ll::rocks_create_encrypted_env_with_keys(
+                base.raw,
+                keys.current_key_id,
+                key_ids.as_ptr(),
+                ciphers.as_ptr(),
+                ciphers.len(),
+            )
+        };
+        // base env is owned by the encrypted env from now on
+        mem::forget(base);
+        Env { raw: raw }
+    }
+
+    /// Returns a new environment that delegates everything to `base`, but
+    /// fails all file writes with an IO error while the filesystem is
+    /// deactivated by `set_filesystem_active(false)`, fails direct IO opens
+    /// after `set_direct_io_supported(false)`, and delays random access reads
+    /// by the duration set with `set_read_delay()`.
+    ///
+    /// Mostly useful for testing how a DB reacts to IO failures.
+    pub fn new_fault_injection(base: Env) -> Env {
+        let raw = unsafe { ll::rocks_create_fault_injection_env(base.raw) };
+        // base env is owned by the fault injection env from now on
+        mem::forget(base);
+        Env { raw: raw }
+    }
+
+    /// Activate or deactivate the filesystem of an env created by
+    /// `Env::new_fault_injection()`. No-op for other envs.
+    pub fn set_filesystem_active(&self, active: bool) {
+        unsafe {
+            ll::rocks_env_set_filesystem_active(self.raw, active as u8);
+        }
+    }
+
+    /// Delay every random access read of an env created by
+    /// `Env::new_fault_injection()` by `delay`. No-op for other envs.
+    pub fn set_read_delay(&self, delay: Duration) {
+        unsafe {
+            ll::rocks_env_set_read_delay(self.raw, delay.as_micros() as u64);
+        }
+    }
+
+    /// Make an env created by `Env::new_fault_injection()` fail opening files
+    /// for direct IO with an IO error, like a filesystem without O_DIRECT
+    /// support. No-op for other envs.
+    pub fn set_direct_io_supported(&self, supported: bool) {
+        unsafe {
+            ll::rocks_env_set_direct_io_supported(self.raw, supported as u8);
+        }
+    }
+
     /// The number of background worker threads of a specific thread pool
     pub fn set_low_priority_background_threads(&self, number: i32) {
         unsafe {
@@ -441,6 +560,87 @@ impl Env {
     pub fn get_thread_id(&self) -> u64 {
         unsafe { ll::rocks_env_get_thread_id(self.raw) as u64 }
     }
+
+    /// Returns `Ok(true)` if the named file exists, `Ok(false)` if it does not,
+    /// and an error if the existence could not be determined, e.g. IO error.
+    pub fn file_exists<P: AsRef<Path>>(&self, fname: P) -> Result<bool> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let name = fname.as_ref().to_str().unwrap();
+            ll::rocks_env_file_exists(self.raw, name.as_ptr() as *const _, name.len(), &mut status);
+            match Error::from_ll(status) {
+                Ok(()) => Ok(true),
+                Err(ref e) if e.is_not_found() => Ok(false),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    /// Returns the names of the children of the specified directory.
+    /// The names are relative to "dir".
+    pub fn get_children<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<String>> {
+        let mut status = ptr::null_mut();
+        let mut len = 0;
+        unsafe {
+            let name = dir.as_ref().to_str().unwrap();
+            let children =
+                ll::rocks_env_get_children(self.raw, name.as_ptr() as *const _, name.len(), &mut len, &mut status);
+            Error::from_ll(status).map(|_| {
+                let ret = (0..len)
+                    .map(|i| {
+                        CStr::from_ptr(*children.offset(i as isize))
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                    .collect();
+                ll::rocks_env_get_children_destroy(children, len);
+                ret
+            })
+        }
+    }
+
+    /// Delete the named file.
+    pub fn delete_file<P: AsRef<Path>>(&self, fname: P) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let name = fname.as_ref().to_str().unwrap();
+            ll::rocks_env_delete_file(self.raw, name.as_ptr() as *const _, name.len(), &mut status);
+            Error::from_ll(status)
+        }
+    }
+
+    /// Creates directory if missing. Return Ok if it exists, or successful in
+    /// Creating.
+    pub fn create_dir_if_missing<P: AsRef<Path>>(&self, dirname: P) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let name = dirname.as_ref().to_str().unwrap();
+            ll::rocks_env_create_dir_if_missing(self.raw, name.as_ptr() as *const _, name.len(), &mut status);
+            Error::from_ll(status)
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod c {
+    use std::os::raw::{c_char, c_int};
+    use std::slice;
+
+    use super::InfoLogLevel;
+
+    type LogFn = Box<dyn Fn(InfoLogLevel, &str) + Send + Sync>;
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_logger_call(f: *mut (), log_level: c_int, msg: *const c_char, len: usize) {
+        let f = f as *mut LogFn;
+        let msg = slice::from_raw_parts(msg as *const u8, len);
+        (*f)(std::mem::transmute(log_level), &String::from_utf8_lossy(msg))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_logger_drop(f: *mut ()) {
+        drop(Box::from_raw(f as *mut LogFn));
+    }
 }
 
 #[cfg(test)]
@@ -460,6 +660,45 @@ mod tests {
         assert!(env.time_to_string(env.get_current_time().unwrap()).len() > 10);
     }
 
+    #[test]
+    fn logger_from_fn() {
+        use crate::rocksdb::*;
+        use std::sync::{Arc, Mutex};
+
+        let lines = Arc::new(Mutex::new(vec![]));
+        let logger = {
+            let lines = lines.clone();
+            Logger::from_fn(InfoLogLevel::Warn, move |level, msg| {
+                lines.lock().unwrap().push((level, msg.to_string()))
+            })
+        };
+        assert_eq!(logger.get_log_level(), InfoLogLevel::Warn);
+        logger.log(InfoLogLevel::Info, "info message");
+        logger.log(InfoLogLevel::Warn, "warn message with 100% literal");
+        logger.log(InfoLogLevel::Error, "error message");
+
+        // opening a DB logs plenty of INFO lines
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true).info_log(Some(logger)));
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        drop(db);
+        drop(opt);
+
+        let lines = lines.lock().unwrap();
+        assert!(
+            lines.iter().all(|&(level, _)| level >= InfoLogLevel::Warn),
+            "{:?}",
+            lines
+        );
+        assert_eq!(
+            lines[0],
+            (InfoLogLevel::Warn, "warn message with 100% literal".to_string())
+        );
+        assert_eq!(lines[1], (InfoLogLevel::Error, "error message".to_string()));
+    }
+
     #[test]
     fn logger() {
         let log_dir = ::tempdir::TempDir::new_in(".", "log").unwrap();
@@ -486,4 +725,33 @@ mod tests {
         assert!(s.contains("[ERROR] test log message"));
         assert!(!s.contains("debug log message"));
     }
+
+    #[test]
+    fn mem_env_file_helpers() {
+        use crate::rocksdb::*;
+
+        let env: &'static Env = Box::leak(Box::new(Env::new_mem()));
+
+        assert!(env.create_dir_if_missing("/mem/checkpoints").is_ok());
+        assert!(env.create_dir_if_missing("/mem/db").is_ok());
+        assert!(!env.file_exists("/mem/db/CURRENT").unwrap());
+
+        {
+            let db = DB::open(
+                Options::default().map_db_options(|db| db.create_if_missing(true).env(env)),
+                "/mem/db",
+            )
+            .unwrap();
+            assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+        }
+
+        let children = env.get_children("/mem/db").unwrap();
+        assert!(children.iter().any(|name| name == "CURRENT"));
+        assert!(children.iter().any(|name| name == "IDENTITY"));
+        assert!(env.file_exists("/mem/db/IDENTITY").unwrap());
+
+        assert!(env.delete_file("/mem/db/IDENTITY").is_ok());
+        assert!(!env.file_exists("/mem/db/IDENTITY").unwrap());
+        assert!(env.delete_file("/mem/db/IDENTITY").is_err());
+    }
 }
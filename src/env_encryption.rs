@@ -0,0 +1,212 @@
+//! Encryption at rest, by wrapping an `Env` so that all files written through
+//! it are encrypted.
+//!
+//! The encrypted env uses a CTR mode encryption provider on top of a
+//! user-supplied `BlockCipher`. Each file gets a random prefix holding the
+//! initial counter, so the same plaintext never encrypts to the same bytes.
+//!
+//! # Key rotation
+//!
+//! `Env::encrypted_with_keys()` takes a `KeyRing` instead of a single cipher.
+//! Every file then starts with the id of the key it was written with: new files
+//! use the current key, existing files keep decrypting with the key they name.
+//!
+//! To rotate to a new key, reopen the DB with a key ring whose current key is
+//! the new one, and which still holds the old keys. WAL and MANIFEST files
+//! move over as they roll, SST files once they are rewritten, e.g. by
+//! `DB::full_compact()`. Small files such as `IDENTITY` are never rewritten,
+//! so old keys must stay in the ring.
+//!
+//! Files written by `Env::encrypted()` carry no key id and can't be read
+//! through a key ring.
+
+/// A `BlockCipher` encrypts and decrypts fixed size blocks of data in place.
+///
+/// Used by `Env::encrypted()`. Only the `encrypt` direction is needed by the
+/// CTR mode provider, but both must be implemented consistently.
+pub trait BlockCipher {
+    /// The name of the cipher.
+    fn name(&self) -> &str {
+        "RustBlockCipher\0"
+    }
+
+    /// Size of the block this cipher operates on, in bytes.
+    fn block_size(&self) -> usize;
+
+    /// Encrypt a block of data. Length of `block` is equal to `block_size()`.
+    fn encrypt(&self, block: &mut [u8]);
+
+    /// Decrypt a block of data. Length of `block` is equal to `block_size()`.
+    fn decrypt(&self, block: &mut [u8]);
+}
+
+/// The ciphers of an encrypted env, by key id. See the module docs.
+pub struct KeyRing {
+    pub(crate) current_key_id: u32,
+    pub(crate) ciphers: Vec<(u32, Box<dyn BlockCipher>)>,
+}
+
+impl KeyRing {
+    /// A key ring that writes new files with `cipher`, under `current_key_id`.
+    pub fn new<T: BlockCipher + Send + Sync + 'static>(current_key_id: u32, cipher: T) -> KeyRing {
+        KeyRing {
+            current_key_id,
+            ciphers: vec![(current_key_id, Box::new(cipher))],
+        }
+    }
+
+    /// Add a key that is only used to read files written with it, replacing
+    /// any cipher already registered under `key_id`.
+    pub fn add_key<T: BlockCipher + Send + Sync + 'static>(mut self, key_id: u32, cipher: T) -> Self {
+        self.ciphers.retain(|&(id, _)| id != key_id);
+        self.ciphers.push((key_id, Box::new(cipher)));
+        self
+    }
+}
+
+#[doc(hidden)]
+pub mod c {
+    use std::os::raw::c_char;
+    use std::slice;
+
+    use super::BlockCipher;
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_block_cipher_name(c: *mut ()) -> *const c_char {
+        let cipher = c as *mut Box<dyn BlockCipher>;
+        (*cipher).name().as_ptr() as *const _
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_block_cipher_block_size(c: *mut ()) -> usize {
+        let cipher = c as *mut Box<dyn BlockCipher>;
+        (*cipher).block_size()
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_block_cipher_encrypt(c: *mut (), data: *mut c_char) {
+        let cipher = c as *mut Box<dyn BlockCipher>;
+        (*cipher).encrypt(slice::from_raw_parts_mut(data as *mut u8, (*cipher).block_size()))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_block_cipher_decrypt(c: *mut (), data: *mut c_char) {
+        let cipher = c as *mut Box<dyn BlockCipher>;
+        (*cipher).decrypt(slice::from_raw_parts_mut(data as *mut u8, (*cipher).block_size()))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_block_cipher_drop(c: *mut ()) {
+        let cipher = c as *mut Box<dyn BlockCipher>;
+        drop(Box::from_raw(cipher));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::super::rocksdb::*;
+    use super::*;
+
+    /// XOR with a rolling key, for testing only.
+    struct XorCipher(u8);
+
+    impl BlockCipher for XorCipher {
+        fn block_size(&self) -> usize {
+            32
+        }
+
+        fn encrypt(&self, block: &mut [u8]) {
+            for (i, b) in block.iter_mut().enumerate() {
+                *b ^= self.0.wrapping_add(i as u8);
+            }
+        }
+
+        fn decrypt(&self, block: &mut [u8]) {
+            self.encrypt(block)
+        }
+    }
+
+    #[test]
+    fn rotate_encryption_key() {
+        const V1: &[u8] = b"written-with-key-1";
+        const V2: &[u8] = b"written-with-key-2";
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let open = |keys: KeyRing| {
+            let env: &'static Env = Box::leak(Box::new(Env::encrypted_with_keys(Env::default(), keys)));
+            let opt = Options::default().map_db_options(|db| db.create_if_missing(true).env(env));
+            DB::open(&opt, &tmp_dir).unwrap()
+        };
+        // key id of every SST file, the first 4 bytes
+        let sst_key_ids = || {
+            tmp_dir
+                .path()
+                .read_dir()
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "sst"))
+                .map(|path| {
+                    let content = fs::read(path).unwrap();
+                    u32::from_le_bytes([content[0], content[1], content[2], content[3]])
+                })
+                .collect::<Vec<_>>()
+        };
+
+        {
+            let db = open(KeyRing::new(1, XorCipher(0x11)));
+            for i in 0..100 {
+                let key = format!("k{:03}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), V1).is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default()).is_ok());
+        }
+        assert_eq!(sst_key_ids(), vec![1]);
+
+        {
+            let db = open(KeyRing::new(2, XorCipher(0x22)).add_key(1, XorCipher(0x11)));
+            assert_eq!(db.get(&ReadOptions::default(), b"k042").unwrap(), V1);
+            for i in 100..200 {
+                let key = format!("k{:03}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), V2).is_ok());
+            }
+            assert!(db.full_compact(&db.default_column_family(), 1).is_ok());
+        }
+        let ids = sst_key_ids();
+        assert!(!ids.is_empty());
+        assert!(ids.iter().all(|&id| id == 2));
+
+        let db = open(KeyRing::new(2, XorCipher(0x22)).add_key(1, XorCipher(0x11)));
+        for i in 0..200 {
+            let key = format!("k{:03}", i);
+            let expected = if i < 100 { V1 } else { V2 };
+            assert_eq!(db.get(&ReadOptions::default(), key.as_bytes()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn encrypted_env() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let env: &'static Env = Box::leak(Box::new(Env::encrypted(Env::default(), XorCipher(0x5a))));
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true).env(env));
+
+        let plaintext = b"this-is-a-very-recognizable-plaintext-value";
+        {
+            let db = DB::open(&opt, &tmp_dir).unwrap();
+            for i in 0..100 {
+                let key = format!("k{:03}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), plaintext).is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default()).is_ok());
+        }
+
+        for entry in tmp_dir.path().read_dir().unwrap() {
+            let content = fs::read(entry.unwrap().path()).unwrap();
+            assert!(!content.windows(plaintext.len()).any(|w| w == &plaintext[..]));
+        }
+
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        assert_eq!(db.get(&ReadOptions::default(), b"k042").unwrap(), &plaintext[..]);
+    }
+}
@@ -101,6 +101,12 @@ impl Error {
         self.code() == Code::NotFound
     }
 
+    /// Returns true if the operation was not carried out, e.g. a write with
+    /// `WriteOptions::no_slowdown` hitting a write stall. Retry later.
+    pub fn is_incomplete(&self) -> bool {
+        self.code() == Code::Incomplete
+    }
+
     pub fn code(&self) -> Code {
         unsafe { mem::transmute(ll::rocks_status_code(self.raw())) }
     }
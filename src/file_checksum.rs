@@ -0,0 +1,94 @@
+//! Full file checksums of SST files, generated when a file is created and
+//! recorded in the MANIFEST.
+//!
+//! Unlike the block checksums, a full file checksum covers the whole file as
+//! written to disk, so it can be used to verify a copy of the file, e.g. after
+//! shipping a backup or checkpoint to another host.
+
+use rocks_sys as ll;
+
+use crate::to_raw::ToRaw;
+
+/// Creates a `FileChecksumGenerator` for each new SST file.
+///
+/// Set via `DBOptions::file_checksum_gen_factory()`.
+pub struct FileChecksumGenFactory {
+    raw: *mut ll::rocks_file_checksum_gen_factory_t,
+}
+
+impl Drop for FileChecksumGenFactory {
+    fn drop(&mut self) {
+        unsafe {
+            ll::rocks_file_checksum_gen_factory_destroy(self.raw);
+        }
+    }
+}
+
+impl ToRaw<ll::rocks_file_checksum_gen_factory_t> for FileChecksumGenFactory {
+    fn raw(&self) -> *mut ll::rocks_file_checksum_gen_factory_t {
+        self.raw
+    }
+}
+
+impl FileChecksumGenFactory {
+    /// The built-in factory, generating a CRC32C checksum of each file.
+    ///
+    /// The function name recorded for such files is `"FileChecksumCrc32c"`.
+    pub fn crc32c() -> FileChecksumGenFactory {
+        FileChecksumGenFactory {
+            raw: unsafe { ll::rocks_file_checksum_gen_crc32c_factory_create() },
+        }
+    }
+}
+
+/// The full file checksum of a live SST file, as returned by
+/// `DB::get_live_files_checksum_info()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChecksumInfo {
+    /// The file number, i.e. `000012.sst` has a file number of 12.
+    pub file_number: u64,
+    /// The checksum bytes, as produced by the generator.
+    ///
+    /// Empty if the file was created without a checksum generator.
+    pub checksum: Vec<u8>,
+    /// Name of the checksum function, `"Unknown"` if the file has no checksum.
+    pub func_name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rocksdb::*;
+    use super::*;
+
+    #[test]
+    fn live_files_checksum_info() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| {
+                db.create_if_missing(true)
+                    .file_checksum_gen_factory(Some(FileChecksumGenFactory::crc32c()))
+            }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..3 {
+            for j in 0..100 {
+                let key = format!("k{}-{:03}", i, j);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default()).is_ok());
+        }
+
+        let infos = db.get_live_files_checksum_info().unwrap();
+        assert_eq!(infos.len(), 3);
+
+        let live_files = db.get_live_files_metadata();
+        for info in &infos {
+            assert_eq!(info.func_name, "FileChecksumCrc32c");
+            assert_eq!(info.checksum.len(), 4);
+            let name = format!("{:06}.sst", info.file_number);
+            assert!(live_files.iter().any(|f| f.name.ends_with(&name)));
+        }
+    }
+}
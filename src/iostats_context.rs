@@ -6,6 +6,8 @@ use std::fmt;
 
 use rocks_sys as ll;
 
+use crate::perf_level::{set_perf_level, PerfLevel};
+
 /// A thread local context for gathering io-stats efficiently and transparently.
 #[derive(Debug)]
 #[repr(C)]
@@ -37,6 +39,12 @@ pub struct IOStatsContext {
 }
 
 impl IOStatsContext {
+    /// Set the perf stats level for current thread, shortcut for
+    /// `set_perf_level()`. Time stats need at least `PerfLevel::EnableTimeExceptForMutex`.
+    pub fn enable(level: PerfLevel) {
+        set_perf_level(level)
+    }
+
     /// IOStatsContext for current thread
     pub fn current() -> &'static mut IOStatsContext {
         unsafe {
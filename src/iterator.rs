@@ -164,6 +164,20 @@ impl<'a> Iterator<'a> {
         }
     }
 
+    /// Update the iterator to represent the latest state of the DB, without
+    /// recreating it. The iterator is invalidated after the call, re-seek to
+    /// use it again.
+    ///
+    /// Not supported if the iterator was created with an explicit snapshot
+    /// in `ReadOptions`.
+    pub fn refresh(&mut self) -> Result<()> {
+        unsafe {
+            let mut status = mem::zeroed();
+            ll::rocks_iter_refresh(self.raw, &mut status);
+            Error::from_ll(status)
+        }
+    }
+
     /// Property `"rocksdb.iterator.is-key-pinned"`:
     ///
     /// - If returning "1", this means that the Slice returned by key() is valid as long as the
@@ -192,6 +206,16 @@ impl<'a> Iterator<'a> {
         }
     }
 
+    /// Whether the slice returned by `key()` stays valid as long as the
+    /// iterator is alive, i.e. property `"rocksdb.iterator.is-key-pinned"`.
+    ///
+    /// REQUIRES: `is_valid()`
+    pub fn is_key_pinned(&self) -> bool {
+        self.get_property("rocksdb.iterator.is-key-pinned")
+            .map(|val| val == "1")
+            .unwrap_or(false)
+    }
+
     /// Consume and make a reversed rustic style iterator.
     pub fn rev(mut self) -> IntoRevIter<'a> {
         self.seek_to_last();
@@ -465,4 +489,98 @@ mod tests {
             .collect();
         assert_eq!(keys, vec!["k9", "k8", "k6", "k5", "k4", "k3", "k2", "k1"]);
     }
+
+    #[test]
+    fn tailing_iterator() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        assert!(db.put(&WriteOptions::default(), b"k1", b"v1").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k2", b"v2").is_ok());
+
+        let mut it = db.new_iterator(&ReadOptions::default().tailing(true));
+        it.seek_to_first();
+        assert_eq!(it.key(), b"k1");
+
+        // written after the iterator was created, found by next()
+        assert!(db.put(&WriteOptions::default(), b"k3", b"v3").is_ok());
+        it.next();
+        assert_eq!(it.key(), b"k2");
+        it.next();
+        assert!(it.is_valid());
+        assert_eq!(it.key(), b"k3");
+        it.next();
+        assert!(!it.is_valid());
+
+        // past the end, a re-seek surfaces new keys, including flushed ones
+        assert!(db.put(&WriteOptions::default(), b"k4", b"v4").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k5", b"v5").is_ok());
+        it.seek(b"k3");
+        let keys = it.keys().collect::<Vec<_>>();
+        assert_eq!(keys, vec![&b"k3"[..], b"k4", b"k5"]);
+    }
+
+    #[test]
+    fn refresh_iterator() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        assert!(db.put(&WriteOptions::default(), b"k1", b"v1").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k2", b"v2").is_ok());
+
+        let mut it = db.new_iterator(&ReadOptions::default());
+        it.seek_to_first();
+        assert_eq!(it.key(), b"k1");
+
+        // not a tailing iterator, new keys are invisible until refresh()
+        assert!(db.put(&WriteOptions::default(), b"k3", b"v3").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k4", b"v4").is_ok());
+        it.seek(b"k2");
+        it.next();
+        assert!(!it.is_valid());
+
+        assert!(it.refresh().is_ok());
+        it.seek(b"k2");
+        let keys = it.keys().collect::<Vec<_>>();
+        assert_eq!(keys, vec![&b"k2"[..], b"k3", b"k4"]);
+    }
+
+    #[test]
+    fn pinned_keys() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| {
+                cf.table_factory_block_based(BlockBasedTableOptions::default().use_delta_encoding(false))
+            });
+        let db = DB::open(opt, &tmp_dir).unwrap();
+
+        for i in 0..1000 {
+            let key = format!("key{:04}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+
+        let mut it = db.new_iterator(&ReadOptions::default().pin_data(true));
+        it.seek(b"key0042");
+        assert!(it.is_key_pinned());
+        let first = it.key();
+        for _ in 0..500 {
+            it.next();
+            assert!(it.is_key_pinned());
+        }
+        assert_eq!(it.key(), b"key0542");
+        // still points to valid memory
+        assert_eq!(first, b"key0042");
+    }
 }
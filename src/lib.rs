@@ -39,7 +39,9 @@ pub mod db;
 pub mod db_dump_tool;
 pub mod debug;
 pub mod env;
+pub mod env_encryption;
 pub mod error;
+pub mod file_checksum;
 pub mod filter_policy;
 pub mod flush_block_policy;
 pub mod iostats_context;
@@ -56,8 +58,10 @@ pub mod slice;
 pub mod slice_transform;
 pub mod snapshot;
 pub mod sst_file_manager;
+pub mod sst_file_reader;
 pub mod sst_file_writer;
 pub mod statistics;
+pub mod stats_history;
 pub mod table;
 pub mod table_properties;
 pub mod thread_status;
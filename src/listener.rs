@@ -9,6 +9,8 @@ use std::mem;
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::compaction_job_stats::CompactionJobStats;
 use crate::db::{ColumnFamilyHandle, DBRef};
@@ -92,13 +94,14 @@ impl fmt::Debug for TableFileCreationInfo {
             .field("reason", &self.reason())
             .field("cf", &self.cf_name())
             .field("file_path", &self.file_path())
+            .field("file_size", &self.file_size())
             .field("status", &self.status())
             .finish()
     }
 }
 
 impl TableFileCreationInfo {
-    /// the size of the file.
+    /// the size of the file, final as the file is already written and synced.
     pub fn file_size(&self) -> u64 {
         unsafe { ll::rocks_table_file_creation_info_get_file_size(self.raw) }
     }
@@ -159,6 +162,28 @@ impl TableFileCreationInfo {
     }
 }
 
+/// Reason for a flush job, used in `FlushJobInfo`
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FlushReason {
+    Others = 0x00,
+    GetLiveFiles = 0x01,
+    ShutDown = 0x02,
+    ExternalFileIngestion = 0x03,
+    ManualCompaction = 0x04,
+    WriteBufferManager = 0x05,
+    WriteBufferFull = 0x06,
+    Test = 0x07,
+    DeleteFiles = 0x08,
+    AutoCompaction = 0x09,
+    ManualFlush = 0x0a,
+    ErrorRecovery = 0x0b,
+    /// When set the flush reason to `ErrorRecoveryRetryFlush`, `SwitchMemtable`
+    /// will not be called to avoid many small immutable memtables.
+    ErrorRecoveryRetryFlush = 0x0c,
+    WalFull = 0x0d,
+}
+
 /// Reason for a compaction job, used in `CompactionJobInfo`
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -176,10 +201,30 @@ pub enum CompactionReason {
     UniversalSortedRunNum,
     /// [FIFO] total size > max_table_files_size
     FIFOMaxSize,
+    /// [FIFO] reduce number of files.
+    FIFOReduceNumFiles,
+    /// [FIFO] files with creation time < (current_time - interval)
+    FIFOTtl,
     /// Manual compaction
     ManualCompaction,
     /// `DB::SuggestCompactRange()` marked files for compaction
     FilesMarkedForCompaction,
+    /// [Level] Automatic compaction within bottommost level to cleanup duplicate
+    /// versions of same user key, usually due to a released snapshot.
+    BottommostFiles,
+    /// Compaction based on TTL
+    Ttl,
+    /// According to the comments in flush_job.cc, RocksDB treats flush as
+    /// a level 0 compaction in internal stats.
+    Flush,
+    /// Compaction caused by external sst file ingestion
+    ExternalSstIngestion,
+    /// Compaction due to SST file being too old
+    PeriodicCompaction,
+    /// Compaction in order to move files to temperature
+    ChangeTemperature,
+    /// Compaction scheduled to force garbage collection of blob files
+    ForcedBlobGC,
 }
 
 /// Reason for a background error, used in event listener
@@ -230,6 +275,8 @@ pub struct FlushJobInfo<'a> {
     pub largest_seqno: SequenceNumber,
     /// Table properties of the table being flushed
     pub table_properties: TableProperties<'a>,
+    /// Reason of this flush job
+    pub flush_reason: FlushReason,
 }
 
 // Big struct, avoid expensive building
@@ -245,6 +292,10 @@ impl<'a> fmt::Debug for CompactionJobInfo<'a> {
             .field("status", &self.status())
             .field("inputs", &self.input_files().len())
             .field("outputs", &self.output_files().len())
+            .field("output_level", &self.output_level())
+            .field("reason", &self.compaction_reason())
+            .field("bytes_read", &self.bytes_read())
+            .field("bytes_written", &self.bytes_written())
             .finish()
     }
 }
@@ -337,6 +388,23 @@ impl<'a> CompactionJobInfo<'a> {
     pub fn stats(&self) -> CompactionJobStats {
         unsafe { CompactionJobStats::from_ll(ll::rocks_compaction_job_info_get_stats(self.raw)) }
     }
+
+    /// Total bytes read from the input files, same as
+    /// `stats().total_input_bytes()`.
+    pub fn bytes_read(&self) -> u64 {
+        self.stats().total_input_bytes()
+    }
+
+    /// Total bytes written to the output files, same as
+    /// `stats().total_output_bytes()`.
+    pub fn bytes_written(&self) -> u64 {
+        self.stats().total_output_bytes()
+    }
+
+    /// How long the compaction took.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_micros(self.stats().elapsed_micros())
+    }
 }
 
 pub struct MemTableInfo {
@@ -389,6 +457,59 @@ impl MemTableInfo {
     }
 }
 
+/// Write stall state of a column family.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum WriteStallCondition {
+    Normal,
+    Delayed,
+    Stopped,
+}
+
+impl WriteStallCondition {
+    fn from_c(v: i32) -> WriteStallCondition {
+        match v {
+            1 => WriteStallCondition::Delayed,
+            2 => WriteStallCondition::Stopped,
+            _ => WriteStallCondition::Normal,
+        }
+    }
+}
+
+pub struct WriteStallInfo {
+    raw: *const ll::rocks_write_stall_info_t,
+}
+
+impl fmt::Debug for WriteStallInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WriteStallInfo")
+            .field("cf", &self.cf_name())
+            .field("cur", &self.cur())
+            .field("prev", &self.prev())
+            .finish()
+    }
+}
+
+impl WriteStallInfo {
+    /// the name of the column family
+    pub fn cf_name(&self) -> &str {
+        let mut len = 0;
+        unsafe {
+            let ptr = ll::rocks_write_stall_info_get_cf_name(self.raw, &mut len);
+            str::from_utf8_unchecked(slice::from_raw_parts(ptr as *const u8, len))
+        }
+    }
+
+    /// The write stall condition the column family is now in.
+    pub fn cur(&self) -> WriteStallCondition {
+        unsafe { WriteStallCondition::from_c(ll::rocks_write_stall_info_get_cur_condition(self.raw)) }
+    }
+
+    /// The write stall condition before this change.
+    pub fn prev(&self) -> WriteStallCondition {
+        unsafe { WriteStallCondition::from_c(ll::rocks_write_stall_info_get_prev_condition(self.raw)) }
+    }
+}
+
 pub struct ExternalFileIngestionInfo {
     raw: *const ll::rocks_external_file_ingestion_info_t,
 }
@@ -596,6 +717,10 @@ pub trait EventListener {
     /// Now it will also be called on failure case. User can check info.status
     /// to see if it succeeded or not.
     ///
+    /// The file is already installed when this is called, RocksDB provides no way
+    /// to veto it from here. To enforce a policy (e.g. a size cap), record the
+    /// offending files and act on them from the application.
+    ///
     /// Note that if applications would like to use the passed reference
     /// outside this function call, they should make copies from these
     /// returned value.
@@ -622,6 +747,15 @@ pub trait EventListener {
     /// returned value.
     fn on_memtable_sealed(&mut self, info: &MemTableInfo) {}
 
+    /// A call-back function for RocksDB which will be called whenever
+    /// a column family enters or leaves a write stall, e.g. when the
+    /// pending compaction bytes cross `soft_pending_compaction_bytes_limit`.
+    ///
+    /// Note that the this function must be implemented in a way such that
+    /// it should not run for an extended period of time before the function
+    /// returns.  Otherwise, RocksDB may be blocked.
+    fn on_stall_conditions_changed(&mut self, info: &WriteStallInfo) {}
+
     /// A call-back function for RocksDB which will be called before
     /// a column family handle is deleted.
     ///
@@ -665,6 +799,63 @@ pub trait EventListener {
     }
 }
 
+/// A shared listener, so that a handle can be kept to inspect the listener's
+/// state while the DB is running:
+///
+/// ```ignore
+/// let listener = Arc::new(Mutex::new(MyListener::default()));
+/// let opts = DBOptions::default().add_listener(listener.clone());
+/// ```
+///
+/// Each call-back locks the mutex for the duration of the call.
+/// `get_compaction_event_listener()` is not forwarded, RocksDB 6.29 has no
+/// compaction event listeners and never asks for one.
+impl<T: EventListener + ?Sized> EventListener for Arc<Mutex<T>> {
+    fn on_flush_completed(&mut self, db: &DBRef, flush_job_info: &FlushJobInfo) {
+        self.lock().unwrap().on_flush_completed(db, flush_job_info)
+    }
+
+    fn on_flush_begin(&mut self, db: &DBRef, flush_job_info: &FlushJobInfo) {
+        self.lock().unwrap().on_flush_begin(db, flush_job_info)
+    }
+
+    fn on_table_file_deleted(&mut self, info: &TableFileDeletionInfo) {
+        self.lock().unwrap().on_table_file_deleted(info)
+    }
+
+    fn on_compaction_completed(&mut self, db: &DBRef, ci: &CompactionJobInfo) {
+        self.lock().unwrap().on_compaction_completed(db, ci)
+    }
+
+    fn on_table_file_created(&mut self, info: &TableFileCreationInfo) {
+        self.lock().unwrap().on_table_file_created(info)
+    }
+
+    fn on_table_file_creation_started(&mut self, info: &TableFileCreationBriefInfo) {
+        self.lock().unwrap().on_table_file_creation_started(info)
+    }
+
+    fn on_memtable_sealed(&mut self, info: &MemTableInfo) {
+        self.lock().unwrap().on_memtable_sealed(info)
+    }
+
+    fn on_stall_conditions_changed(&mut self, info: &WriteStallInfo) {
+        self.lock().unwrap().on_stall_conditions_changed(info)
+    }
+
+    fn on_column_family_handle_deletion_started(&mut self, handle: &ColumnFamilyHandle) {
+        self.lock().unwrap().on_column_family_handle_deletion_started(handle)
+    }
+
+    fn on_external_file_ingested(&mut self, db: &DBRef, info: &ExternalFileIngestionInfo) {
+        self.lock().unwrap().on_external_file_ingested(db, info)
+    }
+
+    fn on_background_error(&mut self, reason: BackgroundErrorReason, bg_error: Error) -> Result<()> {
+        self.lock().unwrap().on_background_error(reason, bg_error)
+    }
+}
+
 #[doc(hidden)]
 pub mod c {
     use super::*;
@@ -700,6 +891,7 @@ pub mod c {
             smallest_seqno: SequenceNumber(ll::rocks_flush_job_info_get_smallest_seqno(info)),
             largest_seqno: SequenceNumber(ll::rocks_flush_job_info_get_largest_seqno(info)),
             table_properties: TableProperties::from_ll(ll::rocks_flush_job_info_get_table_properties(info)),
+            flush_reason: mem::transmute(ll::rocks_flush_job_info_get_flush_reason(info)),
         }
     }
 
@@ -803,6 +995,16 @@ pub mod c {
         (*listener).on_memtable_sealed(&info);
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn rust_event_listener_on_stall_conditions_changed(
+        l: *mut (),
+        info: *const ll::rocks_write_stall_info_t,
+    ) {
+        let listener = l as *mut Box<dyn EventListener>;
+        let info = WriteStallInfo { raw: info };
+        (*listener).on_stall_conditions_changed(&info);
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn rust_event_listener_on_column_family_handle_deletion_started(
         l: *mut (),
@@ -1038,4 +1240,204 @@ mod tests {
         // safe shutdown
         assert!(db.pause_background_work().is_ok());
     }
+
+    #[derive(Default)]
+    struct FlushCounter {
+        flush_completed: usize,
+    }
+
+    impl EventListener for FlushCounter {
+        fn on_flush_completed(&mut self, _db: &DBRef, _flush_job_info: &FlushJobInfo) {
+            self.flush_completed += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct FlushRecorder {
+        // (cf name, file path, smallest seqno, largest seqno, reason)
+        flushes: Vec<(String, String, SequenceNumber, SequenceNumber, FlushReason)>,
+    }
+
+    impl EventListener for FlushRecorder {
+        fn on_flush_completed(&mut self, _db: &DBRef, info: &FlushJobInfo) {
+            assert!(!info.triggered_writes_stop);
+            self.flushes.push((
+                info.cf_name.to_owned(),
+                info.file_path.to_owned(),
+                info.smallest_seqno,
+                info.largest_seqno,
+                info.flush_reason,
+            ));
+        }
+    }
+
+    #[test]
+    fn flush_job_info() {
+        use std::path::Path;
+        use std::sync::{Arc, Mutex};
+
+        let listener = Arc::new(Mutex::new(FlushRecorder::default()));
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true).add_listener(listener.clone())),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..10 {
+            let key = format!("key-{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        let flushes = &listener.lock().unwrap().flushes;
+        assert_eq!(flushes.len(), 1);
+        let (ref cf_name, ref file_path, smallest_seqno, largest_seqno, reason) = flushes[0];
+        assert_eq!(cf_name, "default");
+        assert!(Path::new(file_path).exists());
+        assert_eq!(smallest_seqno.0, 1);
+        assert_eq!(largest_seqno.0, 10);
+        assert_eq!(reason, FlushReason::ManualFlush);
+    }
+
+    #[derive(Default)]
+    struct CompactionRecorder {
+        // (input files, output files, bytes read, bytes written, output level, reason)
+        compactions: Vec<(usize, usize, u64, u64, i32, CompactionReason)>,
+    }
+
+    impl EventListener for CompactionRecorder {
+        fn on_compaction_completed(&mut self, _db: &DBRef, ci: &CompactionJobInfo) {
+            assert!(ci.status().is_ok());
+            self.compactions.push((
+                ci.input_files().len(),
+                ci.output_files().len(),
+                ci.bytes_read(),
+                ci.bytes_written(),
+                ci.output_level(),
+                ci.compaction_reason(),
+            ));
+        }
+    }
+
+    #[test]
+    fn compaction_job_info() {
+        use std::sync::{Arc, Mutex};
+
+        let listener = Arc::new(Mutex::new(CompactionRecorder::default()));
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true).add_listener(listener.clone()))
+                .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..4 {
+            for j in 0..100 {
+                let key = format!("key-{:03}", j);
+                let val = format!("value-{}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), val.as_bytes()).is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        }
+        assert!(db.compact_range(&CompactRangeOptions::default(), ..).is_ok());
+
+        let compactions = &listener.lock().unwrap().compactions;
+        assert_eq!(compactions.len(), 1);
+        let (inputs, outputs, bytes_read, bytes_written, output_level, reason) = compactions[0];
+        assert_eq!(inputs, 4);
+        assert_eq!(outputs, 1);
+        assert!(bytes_read > bytes_written);
+        assert!(bytes_written > 0);
+        assert!(output_level > 0);
+        assert_eq!(reason, CompactionReason::ManualCompaction);
+    }
+
+    #[test]
+    fn shared_event_listener() {
+        use std::sync::{Arc, Mutex};
+
+        let listener = Arc::new(Mutex::new(FlushCounter::default()));
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true).add_listener(listener.clone())),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..3 {
+            let key = format!("key-{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+            assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        }
+
+        assert_eq!(listener.lock().unwrap().flush_completed, 3);
+    }
+
+    #[derive(Default)]
+    struct SizeCapRecorder {
+        cap: u64,
+        // (file path, file size)
+        oversized: Vec<(String, u64)>,
+    }
+
+    impl EventListener for SizeCapRecorder {
+        fn on_table_file_created(&mut self, info: &TableFileCreationInfo) {
+            assert!(info.status().is_ok());
+            if info.file_size() > self.cap {
+                self.oversized.push((info.file_path().to_owned(), info.file_size()));
+            }
+        }
+    }
+
+    #[test]
+    fn table_file_created_size_cap() {
+        use std::sync::{Arc, Mutex};
+
+        let listener = Arc::new(Mutex::new(SizeCapRecorder {
+            cap: 64 << 10,
+            ..Default::default()
+        }));
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true).add_listener(listener.clone()))
+                .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        // a small file, below the cap
+        assert!(db.put(&WriteOptions::default(), b"small", b"value").is_ok());
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        // a large file of hardly compressible values
+        let mut x = 0x2545_f491_u32;
+        for i in 0..1000 {
+            let key = format!("large-{:04}", i);
+            let val = (0..256)
+                .map(|_| {
+                    // xorshift
+                    x ^= x << 13;
+                    x ^= x >> 17;
+                    x ^= x << 5;
+                    x as u8
+                })
+                .collect::<Vec<u8>>();
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), &val).is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        let oversized = &listener.lock().unwrap().oversized;
+        assert_eq!(oversized.len(), 1);
+        let (ref file_path, file_size) = oversized[0];
+        assert!(file_size > 64 << 10);
+        assert_eq!(::std::fs::metadata(file_path).unwrap().len(), file_size);
+    }
 }
@@ -158,6 +158,28 @@ pub trait MergeOperator {
     }
 }
 
+/// Built-in merge operators, ready for `ColumnFamilyOptions::merge_operator()`:
+///
+/// ```ignore
+/// let opts = ColumnFamilyOptions::default().merge_operator(<dyn MergeOperator>::int64_add());
+/// ```
+impl dyn MergeOperator {
+    /// See `Int64AddOperator`.
+    pub fn int64_add() -> Box<dyn MergeOperator> {
+        Box::new(Int64AddOperator)
+    }
+
+    /// See `UInt64MaxOperator`.
+    pub fn uint64_max() -> Box<dyn MergeOperator> {
+        Box::new(UInt64MaxOperator)
+    }
+
+    /// See `StringAppendOperator`.
+    pub fn string_append(delimiter: u8) -> Box<dyn MergeOperator> {
+        Box::new(StringAppendOperator::new(delimiter))
+    }
+}
+
 /// `AssociativeMergeOperator` - for most simple semantics (always take
 /// two values, and merge them into one value, which is then put back
 /// into rocksdb); numeric addition and string concatenation are examples;
@@ -194,6 +216,109 @@ pub trait AssociativeMergeOperator {
     }
 }
 
+/// Treats values as little-endian encoded `i64`s and sums them up, i.e. a
+/// counter.
+///
+/// A missing existing value counts as 0, arithmetic wraps on overflow.
+/// Values which are not 8 bytes long fail the merge.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Int64AddOperator;
+
+impl MergeOperator for Int64AddOperator {
+    fn full_merge(&self, merge_in: &MergeOperationInput, merge_out: &mut MergeOperationOutput) -> bool {
+        let mut sum = match merge_in.existing_value().map(decode_fixed64) {
+            Some(Some(val)) => val as i64,
+            Some(None) => return false,
+            None => 0,
+        };
+        for operand in merge_in.operands() {
+            match decode_fixed64(operand) {
+                Some(val) => sum = sum.wrapping_add(val as i64),
+                None => return false,
+            }
+        }
+        merge_out.assign(&sum.to_le_bytes());
+        true
+    }
+
+    fn name(&self) -> &str {
+        "Int64AddOperator\0"
+    }
+}
+
+/// Treats values as little-endian encoded `u64`s and keeps the largest one.
+///
+/// Values which are not 8 bytes long fail the merge.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UInt64MaxOperator;
+
+impl MergeOperator for UInt64MaxOperator {
+    fn full_merge(&self, merge_in: &MergeOperationInput, merge_out: &mut MergeOperationOutput) -> bool {
+        let mut max = match merge_in.existing_value().map(decode_fixed64) {
+            Some(Some(val)) => val,
+            Some(None) => return false,
+            None => 0,
+        };
+        for operand in merge_in.operands() {
+            match decode_fixed64(operand) {
+                Some(val) => max = max.max(val),
+                None => return false,
+            }
+        }
+        merge_out.assign(&max.to_le_bytes());
+        true
+    }
+
+    fn name(&self) -> &str {
+        "UInt64MaxOperator\0"
+    }
+}
+
+/// Concatenates the operands with a delimiter, same as RocksDB's
+/// `StringAppendOperator`.
+///
+/// If the key has no existing value, the result starts with the first operand
+/// without a leading delimiter. An existing empty value is still joined with
+/// a delimiter.
+#[derive(Debug, Clone, Copy)]
+pub struct StringAppendOperator {
+    delimiter: u8,
+}
+
+impl StringAppendOperator {
+    pub fn new(delimiter: u8) -> StringAppendOperator {
+        StringAppendOperator { delimiter }
+    }
+}
+
+impl MergeOperator for StringAppendOperator {
+    fn full_merge(&self, merge_in: &MergeOperationInput, merge_out: &mut MergeOperationOutput) -> bool {
+        let mut ret = merge_in.existing_value().map(|v| v.to_vec()).unwrap_or_default();
+        for (i, operand) in merge_in.operands().iter().enumerate() {
+            if i > 0 || merge_in.existing_value().is_some() {
+                ret.push(self.delimiter);
+            }
+            ret.extend_from_slice(operand);
+        }
+        merge_out.assign(&ret);
+        true
+    }
+
+    fn name(&self) -> &str {
+        "StringAppendOperator\0"
+    }
+}
+
+fn decode_fixed64(val: &[u8]) -> Option<u64> {
+    if val.len() == 8 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(val);
+        Some(u64::from_le_bytes(buf))
+    } else {
+        None
+    }
+}
+
 // call rust fn in C
 #[doc(hidden)]
 pub mod c {
@@ -448,4 +573,134 @@ mod tests {
         // println!("ret => {:?}", ret.as_ref().map(|s| String::from_utf8_lossy(s)));
         assert_eq!(ret.unwrap().as_ref(), b"I-am-the-test-233");
     }
+
+    #[test]
+    fn int64_add_merge() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.merge_operator(<dyn MergeOperator>::int64_add())),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 1..=100_i64 {
+            assert!(db.merge(&WriteOptions::default(), b"counter", &i.to_le_bytes()).is_ok());
+        }
+        assert!(db
+            .merge(&WriteOptions::default(), b"counter", &(-50_i64).to_le_bytes())
+            .is_ok());
+        let val = db.get(&ReadOptions::default(), b"counter").unwrap();
+        assert_eq!(val.as_ref(), &5000_i64.to_le_bytes());
+
+        // merges on top of a flushed value
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db
+            .merge(&WriteOptions::default(), b"counter", &8_i64.to_le_bytes())
+            .is_ok());
+        let val = db.get(&ReadOptions::default(), b"counter").unwrap();
+        assert_eq!(val.as_ref(), &5008_i64.to_le_bytes());
+
+        // malformed operand
+        assert!(db.merge(&WriteOptions::default(), b"counter", b"bad").is_ok());
+        assert!(db.get(&ReadOptions::default(), b"counter").is_err());
+    }
+
+    #[test]
+    fn uint64_max_merge() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.merge_operator(<dyn MergeOperator>::uint64_max())),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for val in &[3_u64, 42, 7, u64::max_value(), 9] {
+            assert!(db.merge(&WriteOptions::default(), b"max", &val.to_le_bytes()).is_ok());
+        }
+        assert!(db
+            .put(&WriteOptions::default(), b"reset", &10_u64.to_le_bytes())
+            .is_ok());
+        assert!(db
+            .merge(&WriteOptions::default(), b"reset", &5_u64.to_le_bytes())
+            .is_ok());
+
+        let val = db.get(&ReadOptions::default(), b"max").unwrap();
+        assert_eq!(val.as_ref(), &u64::max_value().to_le_bytes());
+        let val = db.get(&ReadOptions::default(), b"reset").unwrap();
+        assert_eq!(val.as_ref(), &10_u64.to_le_bytes());
+    }
+
+    #[test]
+    fn string_append_merge() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.merge_operator(<dyn MergeOperator>::string_append(b','))),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for val in &[&b"a"[..], b"b", b"c"] {
+            assert!(db.merge(&WriteOptions::default(), b"list", val).is_ok());
+        }
+        assert_eq!(db.get(&ReadOptions::default(), b"list").unwrap().as_ref(), b"a,b,c");
+
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db.merge(&WriteOptions::default(), b"list", b"d").is_ok());
+        assert_eq!(db.get(&ReadOptions::default(), b"list").unwrap().as_ref(), b"a,b,c,d");
+
+        // an existing empty value is joined like any other
+        assert!(db.put(&WriteOptions::default(), b"empty", b"").is_ok());
+        assert!(db.merge(&WriteOptions::default(), b"empty", b"x").is_ok());
+        assert_eq!(db.get(&ReadOptions::default(), b"empty").unwrap().as_ref(), b",x");
+
+        // a deleted key starts over
+        assert!(db.delete(&WriteOptions::default(), b"list").is_ok());
+        assert!(db.merge(&WriteOptions::default(), b"list", b"z").is_ok());
+        assert_eq!(db.get(&ReadOptions::default(), b"list").unwrap().as_ref(), b"z");
+    }
+
+    #[test]
+    fn get_merge_operands_in_order() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.merge_operator(Box::new(StringAppendOperator::new(b',')))),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        assert!(db.merge(&WriteOptions::default(), b"list", b"a").is_ok());
+        assert!(db.merge(&WriteOptions::default(), b"list", b"b").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db.merge(&WriteOptions::default(), b"list", b"c").is_ok());
+
+        let operands = db
+            .get_merge_operands(&ReadOptions::default(), &db.default_column_family(), b"list", 10)
+            .unwrap();
+        assert_eq!(operands, vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+
+        // more operands than asked for
+        let ret = db.get_merge_operands(&ReadOptions::default(), &db.default_column_family(), b"list", 2);
+        assert!(ret.unwrap_err().is_incomplete());
+
+        for &max_operands in &[0, 1 << 20, usize::max_value()] {
+            let ret = db.get_merge_operands(
+                &ReadOptions::default(),
+                &db.default_column_family(),
+                b"list",
+                max_operands,
+            );
+            assert_eq!(ret.unwrap_err().code(), crate::error::Code::InvalidArgument);
+        }
+
+        // the full merge is unaffected
+        assert_eq!(db.get(&ReadOptions::default(), b"list").unwrap().as_ref(), b"a,b,c");
+    }
 }
@@ -1,14 +1,17 @@
 //! Common options for DB, CF, read/write/flush/compact...
 
 use lazy_static::lazy_static;
+use std::ffi::CStr;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
-use std::os::raw::c_int;
+use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::ptr;
 use std::slice;
 use std::str;
+use std::sync::Arc;
+use std::time::Duration;
 use std::u64;
 
 use rocks_sys as ll;
@@ -17,7 +20,9 @@ use crate::advanced_options::{CompactionOptionsFIFO, CompactionPri, CompactionSt
 use crate::cache::Cache;
 use crate::compaction_filter::{CompactionFilter, CompactionFilterFactory};
 use crate::comparator::Comparator;
-use crate::env::{Env, InfoLogLevel, Logger};
+use crate::convenience::get_supported_compressions;
+use crate::env::{Env, InfoLogLevel, IoPriority, Logger};
+use crate::file_checksum::FileChecksumGenFactory;
 use crate::listener::EventListener;
 use crate::merge_operator::{AssociativeMergeOperator, MergeOperator};
 use crate::rate_limiter::RateLimiter;
@@ -31,6 +36,7 @@ use crate::universal_compaction::CompactionOptionsUniversal;
 use crate::write_buffer_manager::WriteBufferManager;
 
 use crate::to_raw::{FromRaw, ToRaw};
+use crate::{Error, Result};
 
 lazy_static! {
     // since all Options field are guaranteed to be thread safe
@@ -100,6 +106,25 @@ pub enum WALRecoveryMode {
     SkipAnyCorruptedRecords = 0x03,
 }
 
+/// The memtable representation, used by `ColumnFamilyOptions::memtable_factory()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemtableFactory {
+    /// The default, a skip list. Supports concurrent writes.
+    #[default]
+    SkipList,
+    /// A fixed array of `bucket_count` buckets, each pointing to a skip list.
+    ///
+    /// Requires a prefix extractor, keys are hashed by prefix.
+    HashSkipList { bucket_count: usize },
+    /// A fixed array of `bucket_count` buckets, each pointing to a linked list
+    /// that turns into a skip list once it grows large.
+    ///
+    /// Requires a prefix extractor, keys are hashed by prefix.
+    HashLinkedList { bucket_count: usize },
+    /// Backed by a vector that is sorted on iteration.
+    Vector,
+}
+
 #[derive(Debug)]
 pub struct DbPath {
     pub path: PathBuf,
@@ -210,6 +235,24 @@ impl ColumnFamilyOptions {
         }
     }
 
+    /// Parse an options string like `"write_buffer_size=67108864;max_write_buffer_number=4"`
+    /// on top of `base`, i.e. `GetColumnFamilyOptionsFromString()`.
+    ///
+    /// Options not mentioned keep their value from `base`. Unknown option
+    /// names or malformed values are an `InvalidArgument` error.
+    pub fn from_string(base: &ColumnFamilyOptions, opts_str: &str) -> Result<ColumnFamilyOptions> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let raw = ll::rocks_get_cfoptions_from_string(
+                base.raw,
+                opts_str.as_ptr() as *const _,
+                opts_str.len(),
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| ColumnFamilyOptions::from_ll(raw))
+        }
+    }
+
     /// Some functions that make it easier to optimize RocksDB
 
     /// Use this if your DB is very small (like under 1GB) and you don't want to
@@ -293,6 +336,22 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// rust-rocks extension.
+    ///
+    /// use bitwise comparator with user-defined timestamps of `ts_size` bytes,
+    /// and set if reversed. Timestamps are little-endian unsigned integers
+    /// (e.g. `u64::to_le_bytes()` for a `ts_size` of 8), appended to each user
+    /// key by RocksDB. Keys with the same user key are ordered by descending
+    /// timestamp. A `ts_size` of 0 is the plain bitwise comparator.
+    ///
+    /// Write with `put_with_ts()` and read with `ReadOptions::timestamp()`.
+    pub fn bitwise_comparator_with_timestamp(self, ts_size: usize, reversed: bool) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_bitwise_comparator_with_timestamp(self.raw, ts_size, reversed as u8);
+        }
+        self
+    }
+
     /// REQUIRES: The client must provide a merge operator if Merge operation
     /// needs to be accessed. Calling Merge on a DB without a merge operator
     /// would result in Status::NotSupported. The client must ensure that the
@@ -353,13 +412,16 @@ impl ColumnFamilyOptions {
     /// compaction is being used, each created CompactionFilter will only be used
     /// from a single thread and so does not need to be thread-safe.
     ///
+    /// Rust: the factory is shared, keep a clone of the `Arc` to update the
+    /// state later created filters are built from, while the DB is running.
+    ///
     /// Default: nullptr
-    pub fn compaction_filter_factory(self, factory: Box<dyn CompactionFilterFactory>) -> Self {
-        // unsafe {
-        // ll::rocks_cfoptions_set_compaction_filter_factory(self.raw, )
-        // }
-        // self
-        unimplemented!()
+    pub fn compaction_filter_factory(self, factory: Arc<dyn CompactionFilterFactory + Send + Sync>) -> Self {
+        unsafe {
+            let raw_ptr = Box::into_raw(Box::new(factory)); // Box<Arc<CompactionFilterFactory>>
+            ll::rocks_cfoptions_set_compaction_filter_factory_by_trait(self.raw, raw_ptr as *mut _);
+        }
+        self
     }
 
     // -------------------
@@ -439,6 +501,42 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// Compression options for the bottommost level, used together with
+    /// `bottommost_compression`, e.g. a high ZSTD level and a dictionary for
+    /// the rarely rewritten bulk of the data. `compression_opts` keeps applying
+    /// to the other levels.
+    ///
+    /// Default: unset, `compression_opts` is used for all levels
+    pub fn bottommost_compression_opts(self, val: CompressionOptions) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_bottommost_compression_options(
+                self.raw,
+                val.window_bits,
+                val.level,
+                val.strategy,
+                val.max_dict_bytes,
+            );
+        }
+        self
+    }
+
+    /// If non-zero, use the specified sampling rate to estimate how compressible
+    /// the data is. One in every `val` data blocks is compressed with a fast
+    /// (LZ4 or Snappy) and a slow (ZSTD) algorithm, and the extrapolated sizes
+    /// are recorded in the table properties as
+    /// `fast_compression_estimated_data_size` and
+    /// `slow_compression_estimated_data_size`.
+    ///
+    /// Default: 0 (disabled)
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn sample_for_compression(self, val: u64) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_sample_for_compression(self.raw, val);
+        }
+        self
+    }
+
     /// Number of files to trigger level-0 compaction. A value <0 means that
     /// level-0 compaction will not be triggered by number of files at all.
     ///
@@ -465,6 +563,12 @@ impl ColumnFamilyOptions {
     /// 3) If Compare(k1, k2) <= 0, then Compare(prefix(k1), prefix(k2)) <= 0
     /// 4) prefix(prefix(key)) == prefix(key)
     ///
+    /// The `name()` of the extractor is persisted in the properties of every
+    /// table file. After reopening with an extractor of a different name, RocksDB
+    /// no longer uses the prefix bloom filters of the old files, and a warning is
+    /// written to the `info_log` at open. `ReadOptions::total_order_seek(true)`
+    /// reads the data without depending on prefix filters at all.
+    ///
     /// Default: nullptr
     // FIXME: split other prefix extractor variants
     pub fn prefix_extractor(self, val: Box<dyn SliceTransform + Sync>) -> Self {
@@ -622,6 +726,10 @@ impl ColumnFamilyOptions {
     /// If using a TransactionDB/OptimisticTransactionDB, the default value will
     /// be set to the value of 'max_write_buffer_number' if it is not explicitly
     /// set by the user.  Otherwise, the default is 0.
+    ///
+    /// Deprecated by RocksDB, use `max_write_buffer_size_to_maintain()`
+    /// instead. This is only used when `max_write_buffer_size_to_maintain`
+    /// is 0.
     pub fn max_write_buffer_number_to_maintain(self, val: i32) -> Self {
         unsafe {
             ll::rocks_cfoptions_set_max_write_buffer_number_to_maintain(self.raw, val);
@@ -629,6 +737,32 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// The target number of write history bytes to hold in memory. Write
+    /// history comprises the latest write buffers (memtables), both the
+    /// immutable ones already flushed and the mutable one. Like
+    /// `max_write_buffer_number_to_maintain`, this does not affect flushing,
+    /// it only controls how much history is kept for conflict checking when
+    /// Transactions are used.
+    ///
+    /// When this is set, it takes precedence over
+    /// `max_write_buffer_number_to_maintain`.
+    ///
+    /// If this value is set to -1, `max_write_buffer_number * write_buffer_size`
+    /// will be used.
+    ///
+    /// Default:
+    ///
+    /// If using a TransactionDB/OptimisticTransactionDB, the default value will
+    /// be set to `max_write_buffer_number * write_buffer_size` if it is neither
+    /// explicitly set by the user nor `max_write_buffer_number_to_maintain`.
+    /// Otherwise, the default is 0.
+    pub fn max_write_buffer_size_to_maintain(self, val: i64) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_max_write_buffer_size_to_maintain(self.raw, val);
+        }
+        self
+    }
+
     /// Allows thread-safe inplace updates. If this is true, there is no way to
     /// achieve point-in-time consistency using snapshot or iterator (assuming
     /// concurrent updates). Hence iterator and multi-get will return results
@@ -730,6 +864,20 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// Enable whole key bloom filter in memtable. Note this will only take effect
+    /// if memtable_prefix_bloom_size_ratio is not 0. Enabling whole key filtering
+    /// can potentially reduce CPU usage for point-look-ups.
+    ///
+    /// Default: false (disable)
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn memtable_whole_key_filtering(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_memtable_whole_key_filtering(self.raw, val as u8);
+        }
+        self
+    }
+
     /// Page size for huge page for the arena used by the memtable. If <=0, it
     /// won't allocate from huge page but from malloc.
     /// Users are responsible to reserve huge pages for it to be allocated. For
@@ -883,6 +1031,9 @@ impl ColumnFamilyOptions {
     /// point. A value <0 means that no writing slow down will be triggered by
     /// number of files in level-0.
     ///
+    /// Must not be below `level0_file_num_compaction_trigger`, otherwise
+    /// opening the DB fails with `InvalidArgument`.
+    ///
     /// Default: 20
     ///
     /// Dynamically changeable through `SetOptions()` API
@@ -895,6 +1046,9 @@ impl ColumnFamilyOptions {
 
     /// Maximum number of level-0 files.  We stop writes at this point.
     ///
+    /// Must not be below `level0_slowdown_writes_trigger`, otherwise opening
+    /// the DB fails with `InvalidArgument`.
+    ///
     /// Default: 36
     ///
     /// Dynamically changeable through `SetOptions()` API
@@ -1004,7 +1158,8 @@ impl ColumnFamilyOptions {
     /// `max_bytes_for_level_multiplier_additional` is ignored with this flag on.
     ///
     /// Turning this feature on or off for an existing DB can cause unexpected
-    /// LSM tree structure so it's not recommended.
+    /// LSM tree structure so it's not recommended. Opening a DB with a value
+    /// different from the persisted one logs a warning to `info_log`.
     ///
     /// NOTE: this option is experimental
     ///
@@ -1048,6 +1203,9 @@ impl ColumnFamilyOptions {
     /// threshold. But it's not guaranteed.
     /// Value 0 will be sanitized.
     ///
+    /// The sanitized value is reported by `DB::get_options()`, see also
+    /// `effective_max_compaction_bytes()`.
+    ///
     /// Default: result.target_file_size_base * 25
     pub fn max_compaction_bytes(self, val: u64) -> Self {
         unsafe {
@@ -1056,10 +1214,18 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// The `max_compaction_bytes` a DB opened with these options uses, i.e.
+    /// `target_file_size_base * 25` if it's 0.
+    pub fn effective_max_compaction_bytes(&self) -> u64 {
+        unsafe { ll::rocks_cfoptions_get_effective_max_compaction_bytes(self.raw) }
+    }
+
     /// All writes will be slowed down to at least delayed_write_rate if estimated
     /// bytes needed to be compaction exceed this threshold.
     ///
     /// Default: 64GB
+    ///
+    /// Dynamically changeable through `SetOptions()` API
     pub fn soft_pending_compaction_bytes_limit(self, val: u64) -> Self {
         unsafe {
             ll::rocks_cfoptions_set_soft_pending_compaction_bytes_limit(self.raw, val);
@@ -1071,6 +1237,8 @@ impl ColumnFamilyOptions {
     /// this threshold.
     ///
     /// Default: 256GB
+    ///
+    /// Dynamically changeable through `SetOptions()` API
     pub fn hard_pending_compaction_bytes_limit(self, val: u64) -> Self {
         unsafe {
             ll::rocks_cfoptions_set_hard_pending_compaction_bytes_limit(self.raw, val);
@@ -1130,6 +1298,29 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// The memtable representation to use.
+    ///
+    /// Hash based memtables are much faster for prefix lookups, but need a
+    /// `prefix_extractor`. Other parameters take their RocksDB defaults; use the
+    /// `memtable_factory_*` methods to tune them.
+    ///
+    /// Default: `MemtableFactory::SkipList`
+    pub fn memtable_factory(self, val: MemtableFactory) -> Self {
+        match val {
+            MemtableFactory::SkipList => {
+                unsafe {
+                    ll::rocks_cfoptions_set_memtable_skip_list_rep(self.raw, 0);
+                }
+                self
+            },
+            MemtableFactory::HashSkipList { bucket_count } => {
+                self.memtable_factory_hash_skip_list_rep(bucket_count, 4, 4)
+            },
+            MemtableFactory::HashLinkedList { bucket_count } => self.memtable_factory_hash_link_list_rep(bucket_count),
+            MemtableFactory::Vector => self.memtable_factory_vector_rep(0),
+        }
+    }
+
     /// This creates MemTableReps that are backed by an std::vector. On iteration,
     /// the vector is sorted. This is useful for workloads where iteration is very
     /// rare and writes are generally not issued after reads begin.
@@ -1346,11 +1537,16 @@ impl ColumnFamilyOptions {
         self
     }
 
-    /// In debug mode, RocksDB run consistency checks on the LSM everytime the LSM
-    /// change (Flush, Compaction, AddFile). These checks are disabled in release
-    /// mode, use this option to enable them in release mode as well.
+    /// RocksDB runs consistency checks on the LSM every time the LSM changes
+    /// (Flush, Compaction, AddFile). They are cheap and catch corruption
+    /// early, so they are on by default; set to false to skip them in release
+    /// builds for a perf-sensitive column family.
     ///
-    /// Default: false
+    /// This option is immutable once the column family is open, so it can't
+    /// be toggled at runtime, `set_options()` rejects it.
+    ///
+    /// Default: true, set explicitly by `ColumnFamilyOptions::default()` and
+    /// `Options::default()`
     pub fn force_consistency_checks(self, val: bool) -> Self {
         unsafe {
             ll::rocks_cfoptions_set_force_consistency_checks(self.raw, val as u8);
@@ -1368,6 +1564,75 @@ impl ColumnFamilyOptions {
         self
     }
 
+    /// When set, large values (blobs) are written to separate blob files, and
+    /// only pointers to them are stored in SST files. This can reduce write
+    /// amplification for large-value use cases at the cost of introducing a level
+    /// of indirection for reads.
+    ///
+    /// Default: false
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn enable_blob_files(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_enable_blob_files(self.raw, val as u8);
+        }
+        self
+    }
+
+    /// The size of the smallest value to be stored separately in a blob file.
+    /// Values which have an uncompressed size smaller than this threshold are
+    /// stored alongside the keys in SST files in the usual fashion.
+    ///
+    /// Default: 0
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn min_blob_size(self, val: u64) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_min_blob_size(self.raw, val);
+        }
+        self
+    }
+
+    /// The size limit for blob files. When writing blob files, a new file is
+    /// opened once this limit is reached.
+    ///
+    /// Default: 256 MB
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn blob_file_size(self, val: u64) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_blob_file_size(self.raw, val);
+        }
+        self
+    }
+
+    /// The compression algorithm to use for large values stored in blob files.
+    ///
+    /// Default: no compression
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn blob_compression_type(self, val: CompressionType) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_blob_compression_type(self.raw, mem::transmute(val));
+        }
+        self
+    }
+
+    /// Enables garbage collection of blobs. Valid blobs residing in blob files
+    /// older than a cutoff get relocated to new files as they are encountered
+    /// during compaction, which makes it possible to clean up blob files once
+    /// they contain nothing but obsolete/garbage blobs.
+    ///
+    /// Default: false
+    ///
+    /// Dynamically changeable through `SetOptions()` API
+    pub fn enable_blob_garbage_collection(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_cfoptions_set_enable_blob_garbage_collection(self.raw, val as u8);
+        }
+        self
+    }
+
     pub fn dump(&self, log: &mut Logger) {
         unimplemented!()
     }
@@ -1412,6 +1677,12 @@ impl ToRaw<ll::rocks_dboptions_t> for DBOptions {
     }
 }
 
+impl FromRaw<ll::rocks_dboptions_t> for DBOptions {
+    unsafe fn from_ll(raw: *mut ll::rocks_dboptions_t) -> Self {
+        DBOptions { raw }
+    }
+}
+
 impl fmt::Debug for DBOptions {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "DBOptions {{ ")?;
@@ -1436,6 +1707,24 @@ impl DBOptions {
         DBOptions { raw: raw }
     }
 
+    /// Parse an options string like `"max_background_jobs=4;bytes_per_sync=1048576"`
+    /// on top of `base`, i.e. `GetDBOptionsFromString()`.
+    ///
+    /// Options not mentioned keep their value from `base`. Unknown option
+    /// names or malformed values are an `InvalidArgument` error.
+    pub fn from_string(base: &DBOptions, opts_str: &str) -> Result<DBOptions> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let raw = ll::rocks_get_dboptions_from_string(
+                base.raw,
+                opts_str.as_ptr() as *const _,
+                opts_str.len(),
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| DBOptions::from_ll(raw))
+        }
+    }
+
     /// By default, RocksDB uses only one background thread for flush and
     /// compaction. Calling this function will set it up such that total of
     /// `total_threads` is used. Good value for `total_threads` is the number of
@@ -1510,6 +1799,9 @@ impl DBOptions {
     /// priority than compaction. Rate limiting is disabled if nullptr.
     /// If rate limiter is enabled, bytes_per_sync is set to 1MB by default.
     ///
+    /// Pass a clone of the same `RateLimiter` to several DBs to make them
+    /// share one write budget.
+    ///
     /// Default: nullptr
     pub fn rate_limiter(self, val: Option<RateLimiter>) -> Self {
         unsafe {
@@ -1522,6 +1814,22 @@ impl DBOptions {
         self
     }
 
+    /// If set, a full file checksum is generated for each new SST file and
+    /// stored in the MANIFEST. Use `DB::get_live_files_checksum_info()` to
+    /// read them back.
+    ///
+    /// Default: nullptr (no file checksum)
+    pub fn file_checksum_gen_factory(self, val: Option<FileChecksumGenFactory>) -> Self {
+        unsafe {
+            if let Some(factory) = val {
+                ll::rocks_dboptions_set_file_checksum_gen_factory(self.raw, factory.raw());
+            } else {
+                ll::rocks_dboptions_set_file_checksum_gen_factory(self.raw, ptr::null_mut());
+            }
+        }
+        self
+    }
+
     /// Use to track SST files and control their file deletion rate.
     ///
     /// Features:
@@ -1538,12 +1846,17 @@ impl DBOptions {
     ///    empty).
     ///
     /// Default: nullptr
+    ///
+    /// Rust: will move in and use share_ptr, clone the `SstFileManager` to share it.
     pub fn sst_file_manager(self, val: Option<SstFileManager>) -> Self {
-        // unsafe {
-        //     ll::rocks_dboptions_set_sst_file_manager(self.raw, val);
-        // }
-        // self
-        unimplemented!()
+        unsafe {
+            if let Some(manager) = val {
+                ll::rocks_dboptions_set_sst_file_manager(self.raw, manager.raw());
+            } else {
+                ll::rocks_dboptions_set_sst_file_manager(self.raw, ptr::null_mut());
+            }
+        }
+        self
     }
 
     /// Any internal progress/error information generated by the db will
@@ -1562,6 +1875,11 @@ impl DBOptions {
         self
     }
 
+    /// Log level of the info log RocksDB creates when `info_log` is not set.
+    ///
+    /// A custom `info_log` keeps its own level, see `Logger::from_fn()`.
+    ///
+    /// Default: Info
     pub fn info_log_level(self, val: InfoLogLevel) -> Self {
         unsafe {
             ll::rocks_dboptions_set_info_log_level(self.raw, mem::transmute(val));
@@ -1586,6 +1904,9 @@ impl DBOptions {
     /// If max_open_files is -1, DB will open all files on DB::Open(). You can
     /// use this option to increase the number of threads used to open the files.
     ///
+    /// This is what speeds up opening a DB with many column families and
+    /// table files. Has no effect unless `max_open_files` is -1.
+    ///
     /// Default: 16
     pub fn max_file_opening_threads(self, val: i32) -> Self {
         unsafe {
@@ -1600,6 +1921,11 @@ impl DBOptions {
     /// (default), we will dynamically choose the WAL size limit to be
     /// [sum of all write_buffer_size * max_write_buffer_number] * 4
     ///
+    /// This option takes effect only when there are more than one column family as
+    /// otherwise the wal size is dictated by the write_buffer_size.
+    ///
+    /// Dynamically changeable through `DB::set_db_options()`.
+    ///
     /// Default: 0
     pub fn max_total_wal_size(self, val: u64) -> Self {
         unsafe {
@@ -1734,6 +2060,10 @@ impl DBOptions {
 
     /// Maximum number of concurrent background jobs (compactions and flushes).
     ///
+    /// Ignored if either of the deprecated `max_background_compactions` or
+    /// `max_background_flushes` is set, a warning is written to the info log
+    /// at open when this is the case.
+    ///
     /// Default: 2
     pub fn max_background_jobs(self, val: i32) -> Self {
         unsafe {
@@ -1742,6 +2072,34 @@ impl DBOptions {
         self
     }
 
+    /// NOT SUPPORTED ANYMORE: RocksDB automatically decides this based on the
+    /// value of `max_background_jobs`.
+    ///
+    /// When set, this and `max_background_flushes` take precedence over
+    /// `max_background_jobs`, with an unset one of the two counting as 1.
+    ///
+    /// Default: -1
+    #[deprecated(note = "use max_background_jobs instead")]
+    pub fn max_background_compactions(self, val: i32) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_max_background_compactions(self.raw, val);
+        }
+        self
+    }
+
+    /// NOT SUPPORTED ANYMORE: RocksDB automatically decides this based on the
+    /// value of `max_background_jobs`. See `max_background_compactions()` for
+    /// the precedence rules.
+    ///
+    /// Default: -1
+    #[deprecated(note = "use max_background_jobs instead")]
+    pub fn max_background_flushes(self, val: i32) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_max_background_flushes(self.raw, val);
+        }
+        self
+    }
+
     /// This value represents the maximum number of threads that will
     /// concurrently perform a compaction job by breaking it into multiple,
     /// smaller ones that are run simultaneously.
@@ -1873,7 +2231,8 @@ impl DBOptions {
 
     /// Allow the OS to mmap file for writing.
     ///
-    /// DB::SyncWAL() only works if this is set to false.
+    /// DB::SyncWAL() only works if this is set to false, `DB::sync_wal()` returns
+    /// a `NotSupported` error otherwise.
     ///
     /// Default: false
     pub fn allow_mmap_writes(self, val: bool) -> Self {
@@ -1893,6 +2252,9 @@ impl DBOptions {
     ///
     /// Use O_DIRECT for user reads
     ///
+    /// If the filesystem of the DB does not support O_DIRECT (e.g. tmpfs on older
+    /// kernels), opening the DB fails with a descriptive `NotSupported` error.
+    ///
     /// Default: false
     ///
     /// Not supported in ROCKSDB_LITE mode!
@@ -1906,6 +2268,8 @@ impl DBOptions {
     /// Use O_DIRECT for both reads and writes in background flush and compactions
     /// When true, we also force new_table_reader_for_compaction_inputs to true.
     ///
+    /// Checked at open like `use_direct_reads`.
+    ///
     /// Default: false
     pub fn use_direct_io_for_flush_and_compaction(self, val: bool) -> Self {
         unsafe {
@@ -1942,6 +2306,30 @@ impl DBOptions {
         self
     }
 
+    /// if not zero, dump rocksdb.stats to RocksDB every stats_persist_period_sec,
+    /// kept in memory and queryable with `DB::get_stats_history()`.
+    ///
+    /// Requires `statistics` to be set.
+    ///
+    /// Default: 600
+    pub fn stats_persist_period_sec(self, val: u32) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_stats_persist_period_sec(self.raw, val);
+        }
+        self
+    }
+
+    /// if not zero, periodically take stats snapshots and store in memory, the
+    /// memory size for stats snapshots is capped at stats_history_buffer_size
+    ///
+    /// Default: 1MB
+    pub fn stats_history_buffer_size(self, val: usize) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_stats_history_buffer_size(self.raw, val);
+        }
+        self
+    }
+
     /// If set true, will hint the underlying file system that the file
     /// access pattern is random, when a sst file is opened.
     ///
@@ -1959,6 +2347,11 @@ impl DBOptions {
     /// This is distinct from write_buffer_size, which enforces a limit
     /// for a single memtable.
     ///
+    /// Once the limit is exceeded, the next write schedules a flush of a
+    /// column family, even if none of them is full. Memtable memory across
+    /// column families can be checked with
+    /// `DB::get_aggregated_int_property("rocksdb.cur-size-all-mem-tables")`.
+    ///
     /// This feature is disabled by default. Specify a non-zero value
     /// to enable it.
     ///
@@ -2096,6 +2489,8 @@ impl DBOptions {
     /// to 1MB.
     ///
     /// This option applies to table files
+    ///
+    /// Dynamically changeable through SetDBOptions() API.
     pub fn bytes_per_sync(self, val: u64) -> Self {
         unsafe {
             ll::rocks_dboptions_set_bytes_per_sync(self.raw, val);
@@ -2106,6 +2501,8 @@ impl DBOptions {
     /// Same as bytes_per_sync, but applies to WAL files
     ///
     /// Default: 0, turned off
+    ///
+    /// Dynamically changeable through SetDBOptions() API.
     pub fn wal_bytes_per_sync(self, val: u64) -> Self {
         unsafe {
             ll::rocks_dboptions_set_wal_bytes_per_sync(self.raw, val);
@@ -2115,6 +2512,9 @@ impl DBOptions {
 
     /// A vector of EventListeners which call-back functions will be called
     /// when specific RocksDB event happens.
+    ///
+    /// Pass an `Arc<Mutex<T>>` to keep a handle on the listener, e.g. to read
+    /// counters it accumulates while the DB is running.
     pub fn add_listener<T: EventListener>(self, val: T) -> Self {
         unsafe {
             ll::rocks_dboptions_add_listener(
@@ -2223,6 +2623,19 @@ impl DBOptions {
         self
     }
 
+    /// If true, then `DB::open()` will not fetch and check sizes of all sst
+    /// files. This may significantly speed up startup if there are many sst
+    /// files, especially when using non-default Env with expensive GetFileSize().
+    /// We'll still check that all required sst files exist.
+    ///
+    /// Default: false
+    pub fn skip_checking_sst_file_sizes_on_db_open(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_skip_checking_sst_file_sizes_on_db_open(self.raw, val as u8);
+        }
+        self
+    }
+
     /// Recovery mode to control the consistency while replaying WAL
     ///
     /// Default: PointInTimeRecovery
@@ -2233,6 +2646,37 @@ impl DBOptions {
         self
     }
 
+    /// By default, RocksDB recovery fails if any table/blob file referenced in
+    /// MANIFEST is missing, or the MANIFEST itself cannot be read. If this
+    /// option is true, RocksDB instead recovers to the most recent consistent
+    /// point in time for which all the needed files exist, trying every
+    /// MANIFEST file it finds and ignoring CURRENT.
+    ///
+    /// A last resort for a damaged DB, unrelated to `wal_recovery_mode`.
+    ///
+    /// Default: false
+    pub fn best_efforts_recovery(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_best_efforts_recovery(self.raw, val as u8);
+        }
+        self
+    }
+
+    /// If true, the log numbers and sizes of the synced WALs are tracked in
+    /// MANIFEST, then during DB recovery, if a synced WAL is missing from
+    /// disk, or the WAL's size does not match the recorded size in MANIFEST,
+    /// an error will be reported and the recovery will be aborted.
+    ///
+    /// Note that this option does not work with secondary instance.
+    ///
+    /// Default: false
+    pub fn track_and_verify_wals_in_manifest(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_track_and_verify_wals_in_manifest(self.raw, val as u8);
+        }
+        self
+    }
+
     /// if set to false then recovery will fail when a prepared
     /// transaction is encountered in the WAL
     pub fn allow_2pc(self, val: bool) -> Self {
@@ -2248,7 +2692,7 @@ impl DBOptions {
     ///
     /// Not supported in ROCKSDB_LITE mode!
     ///
-    /// Rust: will move in and use share_ptr
+    /// Rust: will move in and use share_ptr, clone the `Cache` to share it.
     pub fn row_cache(self, val: Option<Cache>) -> Self {
         unsafe {
             if let Some(cache) = val {
@@ -2338,6 +2782,25 @@ impl DBOptions {
         self
     }
 
+    /// Needed to support differential snapshots.
+    /// If set to true then DB will only process deletes with sequence number
+    /// less than what was set by `set_preserve_deletes(n)`.
+    /// Clients are responsible to periodically call this method to advance
+    /// the cutoff time. If this method is never called and preserve_deletes
+    /// is set to true NO deletes will ever be processed.
+    /// At the moment this only keeps normal deletes, SingleDeletes will
+    /// not be preserved.
+    ///
+    /// DEFAULT: false
+    ///
+    /// Immutable (TODO: make it dynamically changeable)
+    pub fn preserve_deletes(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_dboptions_set_preserve_deletes(self.raw, val as u8);
+        }
+        self
+    }
+
     /// If enabled it uses two queues for writes, one for the ones with
     /// disable_memtable and one for the ones that also write to memtable. This
     /// allows the memtable writes not to lag behind other writes. It can be used
@@ -2354,7 +2817,7 @@ impl DBOptions {
 
     /// If true WAL is not flushed automatically after each write. Instead it
     /// relies on manual invocation of FlushWAL to write the WAL buffer to its
-    /// file.
+    /// file, see `DB::flush_wal()`.
     ///
     /// Default: false
     pub fn manual_wal_flush(self, val: bool) -> Self {
@@ -2475,6 +2938,111 @@ impl Options {
         unsafe { ll::rocks_options_optimize_for_small_db(self.raw) };
         self
     }
+
+    /// Apply the commonly recommended settings for fast NVMe SSDs.
+    ///
+    /// Random reads are cheap on such devices, so compaction readahead is off
+    /// and the OS page cache is used, i.e. direct IO is off. Background jobs
+    /// are plenty, and data is synced incrementally every 1MB to smooth out
+    /// write bursts. Level compaction uses dynamic level sizes, LZ4 for the
+    /// upper levels and ZSTD for the bottommost level, which holds most of
+    /// the data.
+    ///
+    /// Codecs missing from `get_supported_compressions()` are replaced: LZ4
+    /// by Snappy, or no compression if that's missing as well, and ZSTD by
+    /// the codec of the upper levels. The default build links Snappy only.
+    pub fn tune_for_nvme_ssd(self) -> Self {
+        let supported = get_supported_compressions();
+        let compression = [CompressionType::LZ4Compression, CompressionType::SnappyCompression]
+            .iter()
+            .cloned()
+            .find(|codec| supported.contains(codec))
+            .unwrap_or(CompressionType::NoCompression);
+        let bottommost_compression = if supported.contains(&CompressionType::ZSTD) {
+            CompressionType::ZSTD
+        } else {
+            compression
+        };
+        self.map_db_options(|db| {
+            db.max_background_jobs(8)
+                .bytes_per_sync(1 << 20)
+                .wal_bytes_per_sync(1 << 20)
+                .compaction_readahead_size(0)
+                .use_direct_reads(false)
+                .use_direct_io_for_flush_and_compaction(false)
+        })
+        .map_cf_options(|cf| {
+            cf.compaction_style(CompactionStyle::CompactionStyleLevel)
+                .level_compaction_dynamic_level_bytes(true)
+                .compaction_pri(CompactionPri::MinOverlappingRatio)
+                .compression(compression)
+                .bottommost_compression(bottommost_compression)
+        })
+    }
+
+    /// Apply the recommended settings for spinning disks, where seeks are
+    /// expensive.
+    ///
+    /// Compactions read their inputs sequentially through a separate table
+    /// reader with 2MB readahead, so user reads keep the random access hint
+    /// on the shared file descriptors. Few background jobs run at once, to
+    /// limit the seeks between concurrent compactions.
+    pub fn tune_for_spinning_disk(self) -> Self {
+        self.map_db_options(|db| {
+            db.max_background_jobs(2)
+                .compaction_readahead_size(2 << 20)
+                .new_table_reader_for_compaction_inputs(true)
+                .advise_random_on_open(true)
+                .access_hint_on_compaction_start(AccessHint::Sequential)
+        })
+    }
+
+    /// Check that a DB created with `self` can be reopened with `other`.
+    ///
+    /// Compares the options that are persisted with the data. A different
+    /// comparator name or a reduced `num_levels` fails the open. A changed
+    /// merge operator or prefix extractor doesn't, but existing merge
+    /// operands then go through the new operator and table files keep the
+    /// prefix filters of the old extractor, so these are reported as well.
+    /// On mismatch, returns a description of each differing option.
+    pub fn compatible_with(&self, other: &Options) -> ::std::result::Result<(), Vec<String>> {
+        unsafe fn name(ptr: *const c_char) -> Option<String> {
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+            }
+        }
+
+        type NameGetter = unsafe extern "C" fn(*mut ll::rocks_options_t) -> *const c_char;
+
+        let mut mismatches = vec![];
+        unsafe {
+            let getters: [(&str, NameGetter); 3] = [
+                ("comparator", ll::rocks_options_get_comparator_name),
+                ("merge_operator", ll::rocks_options_get_merge_operator_name),
+                ("prefix_extractor", ll::rocks_options_get_prefix_extractor_name),
+            ];
+            for &(field, getter) in &getters {
+                let (this, that) = (name(getter(self.raw)), name(getter(other.raw)));
+                if this != that {
+                    mismatches.push(format!("{}: {:?} != {:?}", field, this, that));
+                }
+            }
+            let (this, that) = (
+                ll::rocks_options_get_num_levels(self.raw),
+                ll::rocks_options_get_num_levels(other.raw),
+            );
+            if that < this {
+                mismatches.push(format!("num_levels: {} reduced to {}", this, that));
+            }
+        }
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
 }
 
 /// An application can issue a read request (via Get/Iterators) and specify
@@ -2589,6 +3157,27 @@ impl<'a> ReadOptions<'a> {
         self
     }
 
+    /// Timestamp of operation. Read should return the latest data visible to the
+    /// specified timestamp. All timestamps of the same database must be of the
+    /// same length and format. The user is responsible for providing a customized
+    /// compare function via Comparator to order <key, timestamp> tuples.
+    ///
+    /// Required for reads of a column family with a timestamp-aware
+    /// comparator, see `ColumnFamilyOptions::bitwise_comparator_with_timestamp()`.
+    /// Reads fail with `InvalidArgument` if its size doesn't match the
+    /// comparator's, or if it's set for a column family without timestamps.
+    ///
+    /// Composes with `snapshot()`: a read sees only entries that are both in
+    /// the snapshot and have a timestamp not newer than this one. That gives a
+    /// consistent read "as of" an application timestamp, there is no separate
+    /// snapshot-at-timestamp API.
+    ///
+    /// Default: None
+    pub fn timestamp<'b: 'a>(self, val: &'b [u8]) -> Self {
+        unsafe { ll::rocks_readoptions_set_timestamp(self.raw, val.as_ptr() as *const _, val.len()) }
+        self
+    }
+
     /// If non-zero, NewIterator will create a new table reader which
     /// performs reads of the given size. Using a large size (> 2MB) can
     /// improve the performance of forward iteration on spinning disks.
@@ -2654,6 +3243,9 @@ impl<'a> ReadOptions<'a> {
     /// added data) and is optimized for sequential reads. It will return records
     /// that were inserted into the database after the creation of the iterator.
     ///
+    /// Once a tailing iterator has run past the last key it becomes invalid,
+    /// call `seek()` again to pick up records written since then.
+    ///
     /// Default: false
     pub fn tailing(self, val: bool) -> Self {
         unsafe {
@@ -2681,6 +3273,10 @@ impl<'a> ReadOptions<'a> {
     /// If true when calling `get()`, we also skip prefix bloom when reading from
     /// block based table. It provides a way to read existing data after
     /// changing implementation of prefix extractor.
+    ///
+    /// Required to iterate beyond the prefix of the seek key with a hash based
+    /// memtable (`MemtableFactory::HashSkipList`, `MemtableFactory::HashLinkedList`),
+    /// which otherwise only iterates the bucket of the seek key.
     pub fn total_order_seek(self, val: bool) -> Self {
         unsafe {
             ll::rocks_readoptions_set_total_order_seek(self.raw, val as u8);
@@ -2739,6 +3335,67 @@ impl<'a> ReadOptions<'a> {
         }
         self
     }
+
+    /// When true, by default use total_order_seek = true, and RocksDB can
+    /// selectively enable prefix seek mode if won't generate a different result
+    /// from total_order_seek, based on seek key, and iterator upper bound.
+    ///
+    /// Not supported in ROCKSDB_LITE mode, in the way that even with value true
+    /// prefix mode is not used.
+    ///
+    /// Default: false
+    pub fn auto_prefix_mode(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_readoptions_set_auto_prefix_mode(self.raw, val as u8);
+        }
+        self
+    }
+
+    /// Deadline for completing an API call (Get/MultiGet/Seek/Next for now),
+    /// as a duration since the UNIX epoch, i.e.
+    /// `SystemTime::now().duration_since(UNIX_EPOCH) + timeout`.
+    ///
+    /// This is a best effort. The call may exceed the deadline if there is IO
+    /// involved and the file system doesn't support deadlines, or due to
+    /// checking for deadline periodically rather than for every key if
+    /// processing a batch. On expiry the call fails with `Code::TimedOut`.
+    ///
+    /// Default: None
+    pub fn deadline(self, val: Option<Duration>) -> Self {
+        unsafe {
+            ll::rocks_readoptions_set_deadline(self.raw, val.map_or(0, |d| d.as_micros() as u64));
+        }
+        self
+    }
+
+    /// A timeout in microseconds to be passed to the underlying FileSystem for
+    /// reads. As opposed to `deadline`, this determines the timeout for each
+    /// individual file read request. If a MultiGet/Get/Seek/Next etc call
+    /// results in multiple reads, each read can last up to `io_timeout`.
+    ///
+    /// Default: None
+    pub fn io_timeout(self, val: Option<Duration>) -> Self {
+        unsafe {
+            ll::rocks_readoptions_set_io_timeout(self.raw, val.map_or(0, |d| d.as_micros() as u64));
+        }
+        self
+    }
+
+    /// If async_io is enabled, RocksDB will prefetch some of data asynchronously.
+    /// RocksDB apply it if reads are sequential and its internal automatic
+    /// prefetching. MultiGet will also read data blocks from different files in
+    /// parallel.
+    ///
+    /// Requires RocksDB 7.0 or later. Linked against an older release, such
+    /// as the bundled 6.29, this option has no effect, see `rocks::version()`.
+    ///
+    /// Default: false
+    pub fn async_io(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_readoptions_set_async_io(self.raw, val as u8);
+        }
+        self
+    }
 }
 
 /// Options that control write operations
@@ -2829,6 +3486,8 @@ impl WriteOptions {
 
     /// If true and we need to wait or sleep for the write request, fails
     /// immediately with Status::Incomplete().
+    ///
+    /// Check for it with `Error::is_incomplete()`, nothing is written then.
     pub fn no_slowdown(self, val: bool) -> Self {
         unsafe {
             ll::rocks_writeoptions_set_no_slowdown(self.raw, val as u8);
@@ -2849,6 +3508,28 @@ impl WriteOptions {
         }
         self
     }
+
+    /// For writes associated with this option, charge the internal rate
+    /// limiter (see `DBOptions::rate_limiter`) at the specified priority. The
+    /// special value `IoPriority::Total` disables charging the rate limiter.
+    ///
+    /// Currently the support covers automatic WAL flushes, which happen during
+    /// live updates when `disable_wal` and `DBOptions::manual_wal_flush` are
+    /// false.
+    ///
+    /// Only `IoPriority::User` and `IoPriority::Total` are allowed, writes with
+    /// any other priority fail with `Code::InvalidArgument`.
+    ///
+    /// Requires RocksDB 7.5 or later. Linked against an older release, such
+    /// as the bundled 6.29, this option has no effect, see `rocks::version()`.
+    ///
+    /// Default: `IoPriority::Total`
+    pub fn rate_limiter_priority(self, val: IoPriority) -> Self {
+        unsafe {
+            ll::rocks_writeoptions_set_rate_limiter_priority(self.raw, val as c_int);
+        }
+        self
+    }
 }
 
 /// Options that control flush operations
@@ -3034,6 +3715,14 @@ impl CompactRangeOptions {
         }
         self
     }
+
+    /// If > 0, it will replace the option in the DBOptions for this compaction.
+    pub fn max_subcompactions(self, val: u32) -> Self {
+        unsafe {
+            ll::rocks_compactrange_options_set_max_subcompactions(self.raw, val);
+        }
+        self
+    }
 }
 
 unsafe impl Sync for CompactRangeOptions {}
@@ -3067,6 +3756,22 @@ impl ToRaw<ll::rocks_ingestexternalfile_options_t> for IngestExternalFileOptions
 }
 
 impl IngestExternalFileOptions {
+    /// Preset for the fastest import of files living on the same filesystem
+    /// as the DB.
+    ///
+    /// Files are hard-linked into the DB instead of copied, their blocks are
+    /// not read to verify checksums, and the assigned global sequence number
+    /// is only recorded in the MANIFEST, so the file is never rewritten.
+    ///
+    /// Files ingested this way can not be read by RocksDB versions older
+    /// than 5.16.
+    pub fn fast_move() -> Self {
+        IngestExternalFileOptions::default()
+            .move_files(true)
+            .write_global_seqno(false)
+            .verify_checksums_before_ingest(false)
+    }
+
     /// Can be set to true to move the files instead of copying them.
     pub fn move_files(self, val: bool) -> Self {
         unsafe {
@@ -3075,6 +3780,17 @@ impl IngestExternalFileOptions {
         self
     }
 
+    /// If set to true, ingestion falls back to copy when move fails, e.g. when
+    /// the external files live on a different filesystem than the DB.
+    ///
+    /// Default: true
+    pub fn failed_move_fall_back_to_copy(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_ingestexternalfile_options_set_failed_move_fall_back_to_copy(self.raw, val as u8);
+        }
+        self
+    }
+
     /// If set to false, an ingested file keys could appear in existing snapshots
     /// that where created before the file was ingested.
     pub fn snapshot_consistency(self, val: bool) -> Self {
@@ -3116,6 +3832,32 @@ impl IngestExternalFileOptions {
         }
         self
     }
+
+    /// Set to true to write the global sequence number assigned to an
+    /// ingested file into the file itself. This modifies the external file
+    /// (or its hard link inside the DB).
+    ///
+    /// When false, the global sequence number is only kept in the MANIFEST,
+    /// which requires RocksDB 5.16 or newer to read the DB.
+    ///
+    /// Default: true
+    pub fn write_global_seqno(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_ingestexternalfile_options_set_write_global_seqno(self.raw, val as u8);
+        }
+        self
+    }
+
+    /// Set to true to read all the blocks of the ingested files and verify
+    /// their checksums before ingesting them.
+    ///
+    /// Default: false
+    pub fn verify_checksums_before_ingest(self, val: bool) -> Self {
+        unsafe {
+            ll::rocks_ingestexternalfile_options_set_verify_checksums_before_ingest(self.raw, val as u8);
+        }
+        self
+    }
 }
 
 unsafe impl Sync for IngestExternalFileOptions {}
@@ -3137,6 +3879,421 @@ mod tests {
         assert!(format!("{:?}", opts).contains("max_write_buffer_number=5"));
     }
 
+    #[test]
+    fn tune_for_nvme_ssd() {
+        let mut db_opts = String::new();
+        let mut cf_opts = String::new();
+        let _ = Options::default()
+            .tune_for_nvme_ssd()
+            .map_db_options(|db| {
+                db_opts = format!("{:?}", db);
+                db
+            })
+            .map_cf_options(|cf| {
+                cf_opts = format!("{:?}", cf);
+                cf
+            });
+
+        assert!(db_opts.contains("max_background_jobs=8"), "{}", db_opts);
+        assert!(db_opts.contains("bytes_per_sync=1048576"));
+        assert!(db_opts.contains("wal_bytes_per_sync=1048576"));
+        assert!(db_opts.contains("compaction_readahead_size=0"));
+        assert!(db_opts.contains("use_direct_reads=false"));
+        assert!(cf_opts.contains("level_compaction_dynamic_level_bytes=true"));
+        let has_option = |kv: &str| cf_opts.split(';').any(|field| field.trim() == kv);
+        let supported = get_supported_compressions();
+        if supported.contains(&CompressionType::ZSTD) {
+            assert!(has_option("bottommost_compression=kZSTD"), "{}", cf_opts);
+        }
+        if supported.contains(&CompressionType::LZ4Compression) {
+            assert!(has_option("compression=kLZ4Compression"), "{}", cf_opts);
+        } else if supported.contains(&CompressionType::SnappyCompression) {
+            assert!(has_option("compression=kSnappyCompression"), "{}", cf_opts);
+        }
+
+        // the tuned options always open
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opts = Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .tune_for_nvme_ssd();
+        assert!(DB::open(&opts, &tmp_dir).is_ok());
+    }
+
+    #[test]
+    fn tune_for_spinning_disk() {
+        let mut readahead = 0;
+        let mut db_opts = String::new();
+        let _ = Options::default().tune_for_spinning_disk().map_db_options(|db| {
+            db_opts = format!("{:?}", db);
+            db
+        });
+
+        for kv in db_opts.split(';') {
+            let mut kv = kv.trim().splitn(2, '=');
+            if kv.next() == Some("compaction_readahead_size") {
+                readahead = kv.next().unwrap().parse::<usize>().unwrap();
+            }
+        }
+        assert!(readahead >= 2 << 20, "{}", db_opts);
+        assert!(db_opts.contains("new_table_reader_for_compaction_inputs=true"));
+        assert!(db_opts.contains("access_hint_on_compaction_start=SEQUENTIAL"));
+    }
+
+    #[test]
+    fn bottommost_compression_opts() {
+        let bottommost = if get_supported_compressions().contains(&CompressionType::ZSTD) {
+            CompressionType::ZSTD
+        } else {
+            CompressionType::NoCompression
+        };
+        let cf_opts = ColumnFamilyOptions::default()
+            .compression_opts(CompressionOptions::new(-14, 1, 0, 0))
+            .bottommost_compression(bottommost)
+            .bottommost_compression_opts(CompressionOptions::new(-14, 19, 0, 16 << 10));
+        let cf_str = format!("{:?}", cf_opts);
+        // struct options serialize as "name={field=value;...}"
+        let fields_of = |name: &str| -> Vec<String> {
+            let pattern = format!("{}={{", name);
+            let (start, _) = cf_str
+                .match_indices(&pattern)
+                .find(|&(i, _)| i == 0 || !cf_str[..i].ends_with(|c: char| c.is_alphanumeric() || c == '_'))
+                .unwrap_or_else(|| panic!("no {} in {}", name, cf_str));
+            let rest = &cf_str[start + pattern.len()..];
+            rest[..rest.find('}').unwrap()]
+                .split(';')
+                .map(|field| field.trim().to_owned())
+                .collect()
+        };
+        let bottommost = fields_of("bottommost_compression_opts");
+        assert!(bottommost.iter().any(|f| f == "level=19"), "{:?}", bottommost);
+        assert!(
+            bottommost.iter().any(|f| f == "max_dict_bytes=16384"),
+            "{:?}",
+            bottommost
+        );
+        let general = fields_of("compression_opts");
+        assert!(general.iter().any(|f| f == "level=1"), "{:?}", general);
+        assert!(general.iter().any(|f| f == "max_dict_bytes=0"), "{:?}", general);
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            &Options::new(Some(DBOptions::default().create_if_missing(true)), Some(cf_opts)),
+            &tmp_dir,
+        )
+        .unwrap();
+        for i in 0..1000 {
+            let key = format!("key-{:04}", i);
+            let value = format!("value-{}", i % 17);
+            assert!(db
+                .put(&WriteOptions::default(), key.as_bytes(), value.as_bytes())
+                .is_ok());
+        }
+        assert!(db.compact_range(&CompactRangeOptions::default(), ..).is_ok());
+        assert_eq!(db.get(&ReadOptions::default(), b"key-0042").unwrap(), b"value-8");
+    }
+
+    #[test]
+    fn derived_max_compaction_bytes() {
+        let cf_opts = ColumnFamilyOptions::default()
+            .target_file_size_base(1 << 20)
+            .max_compaction_bytes(0);
+        assert_eq!(cf_opts.effective_max_compaction_bytes(), 25 << 20);
+        assert_eq!(
+            ColumnFamilyOptions::default()
+                .max_compaction_bytes(12345)
+                .effective_max_compaction_bytes(),
+            12345
+        );
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            &Options::new(Some(DBOptions::default().create_if_missing(true)), Some(cf_opts)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let opts = db.get_options(&db.default_column_family());
+        assert_eq!(opts.effective_max_compaction_bytes(), 25 << 20);
+        let opts = format!("{:?}", opts);
+        assert!(opts.contains(&format!("max_compaction_bytes={};", 25 << 20)));
+    }
+
+    #[test]
+    fn memtable_whole_key_bloom_filters_point_gets() {
+        use crate::perf_context::PerfContext;
+        use crate::perf_level::PerfLevel;
+
+        for &whole_key in &[false, true] {
+            let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+            let db = DB::open(
+                Options::default()
+                    .map_db_options(|db| db.create_if_missing(true))
+                    .map_cf_options(|cf| {
+                        cf.prefix_extractor_fixed(4)
+                            .memtable_prefix_bloom_size_ratio(0.1)
+                            .memtable_whole_key_filtering(whole_key)
+                    }),
+                &tmp_dir,
+            )
+            .unwrap();
+            for i in 0..100 {
+                let key = format!("key-{:03}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+            }
+
+            PerfContext::enable(PerfLevel::EnableCount);
+            PerfContext::current().reset();
+            // same prefix as the stored keys, only a whole key bloom can tell it's missing
+            for i in 100..200 {
+                let key = format!("key-{:03}", i);
+                assert!(db.get(&ReadOptions::default(), key.as_bytes()).is_err());
+            }
+            let misses = PerfContext::current().bloom_memtable_miss_count;
+            PerfContext::enable(PerfLevel::Disable);
+
+            if whole_key {
+                assert!(misses > 0);
+            } else {
+                assert_eq!(misses, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn options_from_string() {
+        use crate::error::Code;
+
+        let base = ColumnFamilyOptions::default().num_levels(5);
+        let cf_opts =
+            ColumnFamilyOptions::from_string(&base, "write_buffer_size=67108864;max_write_buffer_number=4").unwrap();
+        let cf_opts = format!("{:?}", cf_opts);
+        assert!(cf_opts.contains("write_buffer_size=67108864;"));
+        assert!(cf_opts.contains("max_write_buffer_number=4;"));
+        // untouched options come from base
+        assert!(cf_opts.contains("num_levels=5;"));
+
+        let db_opts =
+            DBOptions::from_string(&DBOptions::default(), "max_background_jobs=6;bytes_per_sync=1048576").unwrap();
+        let db_opts = format!("{:?}", db_opts);
+        assert!(db_opts.contains("max_background_jobs=6;"));
+        assert!(db_opts.contains("bytes_per_sync=1048576;"));
+
+        let err = ColumnFamilyOptions::from_string(&base, "write_buffer_size=1024;no_such_option=1").unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(ColumnFamilyOptions::from_string(&base, "write_buffer_size=not-a-number").is_err());
+        assert!(DBOptions::from_string(&DBOptions::default(), "no_such_option=1").is_err());
+    }
+
+    #[test]
+    fn shared_handles_outlive_options() {
+        use crate::cache::CacheBuilder;
+        use crate::rate_limiter::RateLimiter;
+        use crate::statistics::{Statistics, Tickers};
+        use crate::write_buffer_manager::WriteBufferManager;
+
+        let block_cache = CacheBuilder::new_lru(4 << 20).build().unwrap();
+        let row_cache = CacheBuilder::new_lru(1 << 20).build().unwrap();
+        let stats = Statistics::new();
+        let limiter = RateLimiter::new(64 << 20, 100_000, 10);
+        let manager = WriteBufferManager::new(16 << 20);
+        let sst_manager = SstFileManager::new(crate::env::Env::default_instance(), None, "", 0, false).unwrap();
+
+        for i in 0..20 {
+            let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+            let opt = Options::default()
+                .map_db_options(|db| {
+                    db.create_if_missing(true)
+                        .row_cache(Some(row_cache.clone()))
+                        .statistics(Some(stats.clone()))
+                        .rate_limiter(Some(limiter.clone()))
+                        .write_buffer_manager(&manager)
+                        .sst_file_manager(Some(sst_manager.clone()))
+                })
+                .map_cf_options(|cf| {
+                    cf.table_factory_block_based(
+                        BlockBasedTableOptions::default().block_cache(Some(block_cache.clone())),
+                    )
+                });
+            let db = DB::open(&opt, &tmp_dir).unwrap();
+            // the C++ side holds its own references from here on
+            drop(opt);
+
+            let key = format!("key{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+            assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+            for _ in 0..2 {
+                assert_eq!(db.get(&ReadOptions::default(), key.as_bytes()).unwrap(), b"value");
+            }
+            assert!(block_cache.get_usage() > 0);
+            assert!(sst_manager.get_total_size() > 0);
+        }
+
+        // resetting to None doesn't touch the old handles
+        let opt = DBOptions::default()
+            .row_cache(None)
+            .statistics(None)
+            .rate_limiter(None)
+            .sst_file_manager(None);
+        let table_opt = BlockBasedTableOptions::default().block_cache(None);
+        drop((opt, table_opt));
+
+        assert_eq!(row_cache.get_capacity(), 1 << 20);
+        assert_eq!(stats.get_ticker_count(Tickers::RowCacheHit), 20);
+        assert!(limiter.get_total_bytes_through(crate::env::IoPriority::Total) > 0);
+    }
+
+    #[test]
+    fn max_write_buffer_size_to_maintain() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.max_write_buffer_size_to_maintain(32 << 20)),
+            &tmp_dir,
+        )
+        .unwrap();
+        assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+
+        let opts = format!("{:?}", db.get_options(&db.default_column_family()));
+        assert!(opts.contains("max_write_buffer_size_to_maintain=33554432"), "{}", opts);
+    }
+
+    #[test]
+    fn force_consistency_checks() {
+        let opts = format!("{:?}", ColumnFamilyOptions::default());
+        assert!(opts.contains("force_consistency_checks=true"), "{}", opts);
+        let opts = format!("{:?}", ColumnFamilyOptions::from_options(&Options::default()));
+        assert!(opts.contains("force_consistency_checks=true"), "{}", opts);
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let cf = db
+            .create_column_family(&ColumnFamilyOptions::default().force_consistency_checks(false), "perf")
+            .unwrap();
+        assert!(cf.put(&WriteOptions::default(), b"key", b"value").is_ok());
+
+        let opts = format!("{:?}", db.get_options(&cf));
+        assert!(opts.contains("force_consistency_checks=false"), "{}", opts);
+        let opts = format!("{:?}", db.get_options(&db.default_column_family()));
+        assert!(opts.contains("force_consistency_checks=true"), "{}", opts);
+
+        assert!(db.set_options(&cf, vec![("force_consistency_checks", "true")]).is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn conflicting_background_limits() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        {
+            let db = DB::open(
+                Options::default().map_db_options(|db| {
+                    db.create_if_missing(true)
+                        .max_background_jobs(8)
+                        .max_background_compactions(3)
+                }),
+                &tmp_dir,
+            )
+            .unwrap();
+
+            let opts = format!("{:?}", db.get_db_options());
+            assert!(opts.contains("max_background_jobs=8"), "{}", opts);
+            assert!(opts.contains("max_background_compactions=3"), "{}", opts);
+            assert!(opts.contains("max_background_flushes=-1"), "{}", opts);
+        }
+
+        let log = std::fs::read_to_string(tmp_dir.path().join("LOG")).unwrap();
+        assert!(log.contains("max_background_jobs=8 is ignored"));
+    }
+
+    #[test]
+    fn changed_dynamic_level_bytes_is_logged() {
+        use crate::env::{InfoLogLevel, Logger};
+        use std::sync::{Arc, Mutex};
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let open = |dynamic: bool| {
+            let lines = Arc::new(Mutex::new(vec![]));
+            let logger = {
+                let lines = lines.clone();
+                Logger::from_fn(InfoLogLevel::Warn, move |_, msg| {
+                    lines.lock().unwrap().push(msg.to_string())
+                })
+            };
+            let opt = Options::default()
+                .map_db_options(|db| db.create_if_missing(true).info_log(Some(logger)))
+                .map_cf_options(|cf| cf.level_compaction_dynamic_level_bytes(dynamic));
+            let db = DB::open(&opt, &tmp_dir).unwrap();
+            assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+            drop(db);
+            let lines = lines.lock().unwrap().clone();
+            lines
+                .into_iter()
+                .filter(|line| line.contains("level_compaction_dynamic_level_bytes changed"))
+                .collect::<Vec<_>>()
+        };
+
+        // new DB, then reopened unchanged
+        assert!(open(false).is_empty());
+        assert!(open(false).is_empty());
+
+        let warnings = open(true);
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("[default]"));
+        assert!(warnings[0].contains("from false to true"));
+
+        // the new value is persisted
+        assert!(open(true).is_empty());
+    }
+
+    #[test]
+    fn inconsistent_level0_triggers() {
+        use crate::error::Code;
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opts = |stop, slowdown, compaction| {
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| {
+                    cf.level0_stop_writes_trigger(stop)
+                        .level0_slowdown_writes_trigger(slowdown)
+                        .level0_file_num_compaction_trigger(compaction)
+                })
+        };
+
+        let err = DB::open(opts(10, 20, 4), &tmp_dir).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+        assert!(err.state().contains("level0_stop_writes_trigger (10)"), "{}", err);
+
+        let err = DB::open(opts(36, 2, 4), &tmp_dir).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+
+        // disabled slowdown is not checked
+        assert!(DB::open(opts(36, -1, 4), &tmp_dir).is_ok());
+    }
+
+    #[test]
+    fn options_compatible_with() {
+        let opts = Options::default().map_cf_options(|cf| cf.prefix_extractor_fixed(4));
+        assert!(opts.compatible_with(&opts).is_ok());
+
+        let reversed = Options::default().map_cf_options(|cf| cf.bitwise_comparator_reversed(true).num_levels(3));
+        let mismatches = opts.compatible_with(&reversed).unwrap_err();
+        assert_eq!(mismatches.len(), 3, "{:?}", mismatches);
+        assert!(mismatches[0].starts_with("comparator:"));
+        assert!(mismatches[0].contains("leveldb.BytewiseComparator"));
+        assert!(mismatches[0].contains("rocksdb.ReverseBytewiseComparator"));
+        assert!(mismatches[1].starts_with("prefix_extractor:"));
+        assert!(mismatches[2].starts_with("num_levels:"));
+
+        // more levels is fine
+        let more_levels = Options::default().map_cf_options(|cf| cf.bitwise_comparator_reversed(true).num_levels(10));
+        assert!(reversed.compatible_with(&more_levels).is_ok());
+    }
+
     #[test]
     fn readoptions() {
         // FIXME: is disable block cache works?
@@ -5,6 +5,8 @@ use std::fmt;
 
 use rocks_sys as ll;
 
+use crate::perf_level::{set_perf_level, PerfLevel};
+
 /// A thread local context for gathering performance counter efficiently
 /// and transparently.
 ///
@@ -130,6 +132,12 @@ pub struct PerfContext {
 }
 
 impl PerfContext {
+    /// Set the perf stats level for current thread, shortcut for
+    /// `set_perf_level()`.
+    pub fn enable(level: PerfLevel) {
+        set_perf_level(level)
+    }
+
     /// PerfContext for current thread
     pub fn current() -> &'static mut PerfContext {
         unsafe {
@@ -196,4 +204,69 @@ mod tests {
         stat.reset();
         assert_eq!(stat.user_key_comparison_count, 0);
     }
+
+    #[test]
+    fn perf_level_gates_counters() {
+        use crate::perf_level::get_perf_level;
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(Options::default().map_db_options(|db| db.create_if_missing(true)), &tmp_dir).unwrap();
+        assert!(db.put(&Default::default(), b"key", b"value").is_ok());
+
+        let run_gets = |level| {
+            set_perf_level(level);
+            assert_eq!(get_perf_level(), level);
+            PerfContext::current().reset();
+            for _ in 0..100 {
+                assert!(db.get(&Default::default(), b"key").is_ok());
+            }
+            let stat = PerfContext::current();
+            (stat.get_from_memtable_count, stat.get_from_memtable_time)
+        };
+
+        assert_eq!(run_gets(PerfLevel::Disable), (0, 0));
+
+        let (count, time) = run_gets(PerfLevel::EnableCount);
+        assert!(count > 0);
+        assert_eq!(time, 0);
+
+        let (count, time) = run_gets(PerfLevel::EnableTime);
+        assert!(count > 0);
+        assert!(time > 0);
+
+        set_perf_level(PerfLevel::Disable);
+    }
+
+    #[test]
+    fn perf_context_cache_miss() {
+        use crate::iostats_context::IOStatsContext;
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.table_factory_block_based(BlockBasedTableOptions::default().no_block_cache(true))),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..100 {
+            let key = format!("key-{:03}", i);
+            assert!(db.put(&Default::default(), key.as_bytes(), b"value").is_ok());
+        }
+        assert!(db.flush(&Default::default()).is_ok());
+
+        PerfContext::enable(PerfLevel::EnableTime);
+        PerfContext::current().reset();
+        IOStatsContext::current().reset();
+
+        assert!(db.get(&ReadOptions::default().fill_cache(false), b"key-042").is_ok());
+
+        let stat = PerfContext::current();
+        assert!(stat.block_read_count > 0);
+        assert_eq!(stat.block_cache_hit_count, 0);
+        assert!(IOStatsContext::current().bytes_read > 0);
+
+        PerfContext::enable(PerfLevel::Disable);
+    }
 }
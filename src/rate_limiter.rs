@@ -1,12 +1,20 @@
 //! RateLimiter object can be shared among RocksDB instances to
 //! control write rate of flush and compaction.
 
+use std::cmp;
+use std::ptr;
+
 use rocks_sys as ll;
 
+use crate::env::IoPriority;
 use crate::to_raw::ToRaw;
+use crate::{Error, Result};
 
 /// `RateLimiter` object, which can be shared among RocksDB instances to
 /// control write rate of flush and compaction.
+///
+/// Cloning is cheap, clones refer to the same limiter, so cloning one into
+/// the `DBOptions` of several DBs makes them share a single budget.
 pub struct RateLimiter {
     raw: *mut ll::rocks_ratelimiter_t,
 }
@@ -19,12 +27,23 @@ impl Drop for RateLimiter {
     }
 }
 
+impl Clone for RateLimiter {
+    fn clone(&self) -> Self {
+        RateLimiter {
+            raw: unsafe { ll::rocks_ratelimiter_copy(self.raw) },
+        }
+    }
+}
+
 impl ToRaw<ll::rocks_ratelimiter_t> for RateLimiter {
     fn raw(&self) -> *mut ll::rocks_ratelimiter_t {
         self.raw
     }
 }
 
+unsafe impl Send for RateLimiter {}
+unsafe impl Sync for RateLimiter {}
+
 impl RateLimiter {
     /// Create a RateLimiter object, which can be shared among RocksDB instances to
     /// control write rate of flush and compaction.
@@ -53,4 +72,134 @@ impl RateLimiter {
             },
         }
     }
+
+    /// Max bytes can be granted in a single burst.
+    pub fn get_single_burst_bytes(&self) -> i64 {
+        unsafe { ll::rocks_ratelimiter_get_single_burst_bytes(self.raw) }
+    }
+
+    /// Request for token for bytes, blocking until it is granted. Useful for
+    /// throttling custom IO by the same limiter as flush and compaction.
+    ///
+    /// Requests larger than `get_single_burst_bytes()` are split into several
+    /// requests of at most that size.
+    ///
+    /// `IoPriority::Total` is not a priority of its own, requesting with it
+    /// fails with `InvalidArgument`.
+    pub fn request(&self, bytes: i64, pri: IoPriority) -> Result<()> {
+        let burst = self.get_single_burst_bytes();
+        let mut remaining = bytes;
+        // at least one request, so that the priority is checked even for 0 bytes
+        loop {
+            let n = cmp::min(remaining, burst);
+            let mut status = ptr::null_mut();
+            unsafe {
+                ll::rocks_ratelimiter_request(self.raw, n, pri as _, &mut status);
+            }
+            Error::from_ll(status)?;
+            remaining -= n;
+            if remaining <= 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Total bytes that went through the rate limiter at the given priority,
+    /// `IoPriority::Total` for all of them.
+    pub fn get_total_bytes_through(&self, pri: IoPriority) -> i64 {
+        unsafe { ll::rocks_ratelimiter_get_total_bytes_through(self.raw, pri as _) }
+    }
+
+    /// Total number of requests that went through the rate limiter at the
+    /// given priority, `IoPriority::Total` for all of them.
+    pub fn get_total_requests(&self, pri: IoPriority) -> i64 {
+        unsafe { ll::rocks_ratelimiter_get_total_requests(self.raw, pri as _) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn request() {
+        // 1MB/s, refilled every 100ms
+        let limiter = RateLimiter::new(1 << 20, 100_000, 10);
+        assert_eq!(limiter.get_single_burst_bytes(), (1 << 20) / 10);
+
+        let start = Instant::now();
+        limiter.request(2 << 20, IoPriority::Low).unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed.as_millis() >= 1500, "elapsed => {:?}", elapsed);
+
+        // small request is granted without waiting for long
+        let start = Instant::now();
+        limiter.request(1024, IoPriority::High).unwrap();
+        assert!(start.elapsed().as_millis() < 1000);
+
+        let err = limiter.request(1024, IoPriority::Total).unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::InvalidArgument);
+        let err = limiter.request(0, IoPriority::Total).unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::InvalidArgument);
+        assert!(limiter.request(0, IoPriority::Low).is_ok());
+    }
+
+    #[test]
+    fn shared_between_dbs() {
+        use crate::rocksdb::*;
+
+        let limiter = RateLimiter::new(64 << 20, 100_000, 10);
+        assert_eq!(limiter.get_total_bytes_through(IoPriority::Total), 0);
+
+        let mut through = vec![];
+        let dirs = (0..2)
+            .map(|_| ::tempdir::TempDir::new_in(".", "rocks").unwrap())
+            .collect::<Vec<_>>();
+        for dir in &dirs {
+            // uncompressed, so the flushed bytes are known
+            let opt = Options::default()
+                .map_db_options(|db| db.create_if_missing(true).rate_limiter(Some(limiter.clone())))
+                .map_cf_options(|cf| cf.compression(CompressionType::NoCompression));
+            let db = DB::open(opt, dir).unwrap();
+            for i in 0..1000 {
+                let key = format!("key-{:04}", i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), &[b'x'; 100]).is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+            through.push(limiter.get_total_bytes_through(IoPriority::Total));
+        }
+
+        // the flushes of both DBs were charged to the same limiter
+        assert!(through[0] > 100_000, "through => {:?}", through);
+        assert!(through[1] - through[0] > 100_000, "through => {:?}", through);
+    }
+
+    #[test]
+    fn write_rate_limiter_priority() {
+        use crate::error::Code;
+        use crate::rocksdb::*;
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default().map_db_options(|db| {
+            db.create_if_missing(true)
+                .rate_limiter(Some(RateLimiter::new(1 << 20, 100_000, 10)))
+        });
+        let db = DB::open(opt, &tmp_dir).unwrap();
+
+        let wopts = WriteOptions::default().rate_limiter_priority(IoPriority::User);
+        assert!(db.put(&wopts, b"key", b"value").is_ok());
+        assert_eq!(db.get(&ReadOptions::default(), b"key").unwrap(), &b"value"[..]);
+
+        // WAL writes can only be charged at user priority, older releases ignore the option
+        let linked = version().split('.').map(|v| v.parse().unwrap()).collect::<Vec<u32>>();
+        let wopts = WriteOptions::default().rate_limiter_priority(IoPriority::Low);
+        let ret = db.put(&wopts, b"key", b"value");
+        if linked[..2] >= [7, 5][..] {
+            assert_eq!(ret.unwrap_err().code(), Code::InvalidArgument);
+        } else {
+            assert!(ret.is_ok());
+        }
+    }
 }
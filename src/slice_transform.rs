@@ -175,4 +175,184 @@ mod tests {
         assert!(keys.contains(&"abc-002".to_string()));
         assert!(!keys.contains(&"def-000".to_string()));
     }
+
+    #[test]
+    fn auto_prefix_mode() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| cf.prefix_extractor_fixed(3).memtable_prefix_bloom_size_ratio(0.1)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for prefix in &["aaa", "abc", "abd", "bcd"] {
+            for i in 0..10 {
+                let key = format!("{}-{:03}", prefix, i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"23333").is_ok());
+            }
+        }
+        assert!(db.flush(&Default::default()).is_ok());
+
+        let scan = |auto_prefix: bool| -> Vec<Vec<u8>> {
+            let mut it = db.new_iterator(
+                &ReadOptions::default()
+                    .iterate_upper_bound(b"abc-005")
+                    .auto_prefix_mode(auto_prefix),
+            );
+            it.seek(b"abc-002");
+            it.keys().map(|k| k.to_vec()).collect()
+        };
+
+        let expected: Vec<Vec<u8>> = (2..5).map(|i| format!("abc-{:03}", i).into_bytes()).collect();
+        assert_eq!(scan(false), expected);
+        assert_eq!(scan(true), expected);
+    }
+
+    #[test]
+    fn hash_skip_list_memtable() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                // hash based memtables do not support concurrent writes
+                .map_db_options(|db| db.create_if_missing(true).allow_concurrent_memtable_write(false))
+                .map_cf_options(|cf| {
+                    cf.prefix_extractor_fixed(3)
+                        .memtable_factory(MemtableFactory::HashSkipList { bucket_count: 1000 })
+                }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for prefix in &["aaa", "abc", "bcd"] {
+            for i in 0..10 {
+                let key = format!("{}-{:03}", prefix, i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"23333").is_ok());
+            }
+        }
+        assert_eq!(db.get(&ReadOptions::default(), b"abc-007").unwrap(), &b"23333"[..]);
+
+        let mut it = db.new_iterator(&ReadOptions::default().prefix_same_as_start(true));
+        it.seek(b"abc");
+        let keys: Vec<Vec<u8>> = it.keys().map(|k| k.to_vec()).collect();
+        let expected: Vec<Vec<u8>> = (0..10).map(|i| format!("abc-{:03}", i).into_bytes()).collect();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn reopen_with_changed_prefix_extractor() {
+        use crate::env::{InfoLogLevel, Logger};
+        use crate::filter_policy::FilterPolicy;
+        use std::sync::{Arc, Mutex};
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let lines = Arc::new(Mutex::new(vec![]));
+        let open_with = |cf_opt: fn(ColumnFamilyOptions) -> ColumnFamilyOptions| {
+            lines.lock().unwrap().clear();
+            let logger = {
+                let lines = lines.clone();
+                Logger::from_fn(InfoLogLevel::Warn, move |_, msg| {
+                    lines.lock().unwrap().push(msg.to_string())
+                })
+            };
+            DB::open(
+                Options::default()
+                    .map_db_options(|db| db.create_if_missing(true).info_log(Some(logger)))
+                    .map_cf_options(|cf| {
+                        cf_opt(cf).table_factory_block_based(
+                            BlockBasedTableOptions::default()
+                                .filter_policy(Some(FilterPolicy::new_bloom_filter(10, false))),
+                        )
+                    }),
+                &tmp_dir,
+            )
+            .unwrap()
+        };
+        let warnings = || {
+            lines
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|line| line.contains("prefix extractor other than"))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        {
+            let db = open_with(|cf| cf.prefix_extractor_fixed(3));
+            for prefix in &["aaa", "abc", "bcd"] {
+                for i in 0..10 {
+                    let key = format!("{}-{:03}", prefix, i);
+                    assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"23333").is_ok());
+                }
+            }
+            assert!(db.flush(&FlushOptions::default()).is_ok());
+        }
+
+        // reopened unchanged
+        drop(open_with(|cf| cf.prefix_extractor_fixed(3)));
+        assert!(warnings().is_empty());
+
+        let db = open_with(|cf| cf.prefix_extractor_capped(4));
+        let cf = db.default_column_family();
+        let props = db.get_properties_of_all_tables_cf(&cf).unwrap();
+        assert!(props
+            .iter()
+            .all(|(_, prop)| prop.prefix_extractor_name() == Some("rocksdb.FixedPrefix.3")));
+        let warnings = warnings();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("[default]"));
+        assert!(warnings[0].contains("rocksdb.CappedPrefix.4"));
+
+        // old data is still readable, bypassing the stale prefix filters
+        assert_eq!(
+            db.get(&ReadOptions::default().total_order_seek(true), b"abc-007")
+                .unwrap(),
+            &b"23333"[..]
+        );
+        let mut it = db.new_iterator(&ReadOptions::default().total_order_seek(true));
+        it.seek_to_first();
+        assert_eq!(it.keys().count(), 30);
+    }
+
+    #[test]
+    fn total_order_seek_with_hash_memtable() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true).allow_concurrent_memtable_write(false))
+                .map_cf_options(|cf| {
+                    cf.prefix_extractor_fixed(3)
+                        .memtable_factory(MemtableFactory::HashSkipList { bucket_count: 1000 })
+                }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        // written out of order, across prefixes
+        for prefix in &["bcd", "aaa", "abc"] {
+            for i in (0..10).rev() {
+                let key = format!("{}-{:03}", prefix, i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"23333").is_ok());
+            }
+        }
+
+        // total order: every key, in order
+        let mut it = db.new_iterator(&ReadOptions::default().total_order_seek(true));
+        it.seek_to_first();
+        let keys: Vec<Vec<u8>> = it.keys().map(|k| k.to_vec()).collect();
+        let expected: Vec<Vec<u8>> = ["aaa", "abc", "bcd"]
+            .iter()
+            .flat_map(|prefix| (0..10).map(move |i| format!("{}-{:03}", prefix, i).into_bytes()))
+            .collect();
+        assert_eq!(keys, expected);
+
+        // prefix seek: the hash memtable only iterates the bucket of the seek key
+        let mut it = db.new_iterator(&ReadOptions::default());
+        it.seek(b"abc-005");
+        let keys: Vec<Vec<u8>> = it.keys().map(|k| k.to_vec()).collect();
+        let expected: Vec<Vec<u8>> = (5..10).map(|i| format!("abc-{:03}", i).into_bytes()).collect();
+        assert_eq!(keys, expected);
+    }
 }
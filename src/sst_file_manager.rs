@@ -2,20 +2,64 @@
 //! deletion rate.
 
 use std::path::Path;
+use std::ptr;
+
+use rocks_sys as ll;
 
-use super::Result;
 use crate::env::{Env, Logger};
+use crate::to_raw::ToRaw;
+use crate::{Error, Result};
 
 /// SstFileManager is used to track SST files in the DB and control there
 /// deletion rate.
 ///
 /// All SstFileManager public functions are thread-safe.
-pub struct SstFileManager;
+///
+/// Cloning is cheap, clones refer to the same manager, so cloning one into
+/// the `DBOptions` of several DBs makes them share it.
+pub struct SstFileManager {
+    raw: *mut ll::rocks_sst_file_manager_t,
+}
 
 unsafe impl Sync for SstFileManager {}
 unsafe impl Send for SstFileManager {}
 
+impl ToRaw<ll::rocks_sst_file_manager_t> for SstFileManager {
+    fn raw(&self) -> *mut ll::rocks_sst_file_manager_t {
+        self.raw
+    }
+}
+
+impl Drop for SstFileManager {
+    fn drop(&mut self) {
+        unsafe {
+            ll::rocks_sst_file_manager_destroy(self.raw);
+        }
+    }
+}
+
+impl Clone for SstFileManager {
+    fn clone(&self) -> Self {
+        SstFileManager {
+            raw: unsafe { ll::rocks_sst_file_manager_copy(self.raw) },
+        }
+    }
+}
+
 impl SstFileManager {
+    /// Create a new SstFileManager that can be shared among multiple RocksDB
+    /// instances to track SST file and control there deletion rate.
+    ///
+    /// - `env`: Pointer to Env object, please see "rocksdb/env.h". It must
+    ///   outlive the manager.
+    /// - `info_log`: If not `None`, info_log will be used to log errors.
+    /// - `trash_dir`: Deprecated, only used if `delete_existing_trash` is true.
+    /// - `rate_bytes_per_sec`: How many bytes should be deleted per second, If
+    ///   this value is set to 1024 (1 Kb / sec) and we deleted a file of size
+    ///   4 Kb in 1 second, we will wait for another 3 seconds before we delete
+    ///   other files, Set to 0 to disable deletion rate limiting.
+    /// - `delete_existing_trash`: Deprecated, trash files found in the DB
+    ///   paths are always deleted.
     pub fn new<P: AsRef<Path>>(
         env: &Env,
         info_log: Option<&Logger>,
@@ -23,12 +67,83 @@ impl SstFileManager {
         rate_bytes_per_sec: i64,
         delete_existing_trash: bool,
     ) -> Result<SstFileManager> {
-        unimplemented!()
+        let trash_dir = trash_dir.as_ref().to_str().expect("valid utf8");
+        let mut status = ptr::null_mut();
+        unsafe {
+            let raw = ll::rocks_sst_file_manager_create(
+                env.raw(),
+                info_log.map(|l| l.raw()).unwrap_or_else(ptr::null_mut),
+                trash_dir.as_ptr() as *const _,
+                trash_dir.len(),
+                rate_bytes_per_sec,
+                delete_existing_trash as u8,
+                &mut status,
+            );
+            Error::from_ll(status).map(|_| SstFileManager { raw })
+        }
+    }
+
+    /// Update the maximum allowed space that should be used by RocksDB, if
+    /// the total size of the SST files exceeds `max_allowed_space`, writes to
+    /// RocksDB will fail.
+    ///
+    /// Setting `max_allowed_space` to 0 will disable this feature; maximum
+    /// allowed space will be infinite (Default value).
+    pub fn set_max_allowed_space_usage(&self, max_allowed_space: u64) {
+        unsafe {
+            ll::rocks_sst_file_manager_set_max_allowed_space_usage(self.raw, max_allowed_space);
+        }
+    }
+
+    /// Return true if the total size of SST files exceeded the maximum allowed
+    /// space usage.
+    pub fn is_max_allowed_space_reached(&self) -> bool {
+        unsafe { ll::rocks_sst_file_manager_is_max_allowed_space_reached(self.raw) != 0 }
+    }
+
+    /// Return the total size of all tracked files.
+    pub fn get_total_size(&self) -> u64 {
+        unsafe { ll::rocks_sst_file_manager_get_total_size(self.raw) }
+    }
+
+    /// Return delete rate limit in bytes per second.
+    pub fn get_delete_rate_bytes_per_second(&self) -> i64 {
+        unsafe { ll::rocks_sst_file_manager_get_delete_rate_bytes_per_second(self.raw) }
+    }
+
+    /// Update the delete rate limit in bytes per second.
+    /// zero means disable delete rate limiting and delete files immediately
+    pub fn set_delete_rate_bytes_per_second(&self, delete_rate: i64) {
+        unsafe {
+            ll::rocks_sst_file_manager_set_delete_rate_bytes_per_second(self.raw, delete_rate);
+        }
     }
 }
 
-// extern SstFileManager* NewSstFileManager(
-// Env* env, std::shared_ptr<Logger> info_log = nullptr,
-// std::string trash_dir = "", int64_t rate_bytes_per_sec = 0,
-// bool delete_existing_trash = true, Status* status = nullptr);
-//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rocksdb::*;
+
+    #[test]
+    fn track_sst_files() {
+        let manager = SstFileManager::new(Env::default_instance(), None, "", 0, false).unwrap();
+        assert_eq!(manager.get_total_size(), 0);
+        manager.set_delete_rate_bytes_per_second(1 << 20);
+        assert_eq!(manager.get_delete_rate_bytes_per_second(), 1 << 20);
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt =
+            Options::default().map_db_options(|db| db.create_if_missing(true).sst_file_manager(Some(manager.clone())));
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        drop(opt);
+
+        assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        assert!(manager.get_total_size() > 0);
+        assert!(!manager.is_max_allowed_space_reached());
+
+        manager.set_max_allowed_space_usage(1);
+        assert!(manager.is_max_allowed_space_reached());
+    }
+}
@@ -0,0 +1,137 @@
+//! SstFileReader is used to read sst files that are generated by DB or
+//! SstFileWriter, without opening a DB.
+
+use std::path::Path;
+use std::ptr;
+
+use rocks_sys as ll;
+
+use crate::iterator::Iterator;
+use crate::options::{Options, ReadOptions};
+use crate::table_properties::TableProperties;
+use crate::to_raw::{FromRaw, ToRaw};
+use crate::{Error, Result};
+
+/// SstFileReader is used to read sst files that are generated by DB or
+/// SstFileWriter.
+///
+/// Useful for validating an external sst file before ingesting it.
+pub struct SstFileReader {
+    raw: *mut ll::rocks_sst_file_reader_t,
+}
+
+impl Drop for SstFileReader {
+    fn drop(&mut self) {
+        unsafe {
+            ll::rocks_sst_file_reader_destroy(self.raw);
+        }
+    }
+}
+
+impl SstFileReader {
+    /// Prepares to read from the file located at "file_path".
+    ///
+    /// `options` must be compatible with the ones the file was written with,
+    /// especially the comparator.
+    pub fn open<T: AsRef<Options>, P: AsRef<Path>>(options: T, file_path: P) -> Result<SstFileReader> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            let reader = SstFileReader {
+                raw: ll::rocks_sst_file_reader_create(options.as_ref().raw()),
+            };
+            let path = file_path.as_ref().to_str().expect("file path");
+            ll::rocks_sst_file_reader_open(reader.raw, path.as_ptr() as *const _, path.len(), &mut status);
+            Error::from_ll(status).map(|_| reader)
+        }
+    }
+
+    /// Returns a new iterator over the table contents.
+    ///
+    /// Most read options provide the same control as we read from DB.
+    /// If `snapshot` is set, the iterator ignores keys written after it.
+    pub fn new_iterator<'c, 'd: 'c>(&'d self, options: &ReadOptions) -> Iterator<'c> {
+        unsafe {
+            let ptr = ll::rocks_sst_file_reader_new_iterator(self.raw, options.raw());
+            Iterator::from_ll(ptr)
+        }
+    }
+
+    /// Returns the properties of the table.
+    pub fn table_properties(&self) -> TableProperties {
+        unsafe { TableProperties::from_ll(ll::rocks_sst_file_reader_get_table_properties(self.raw)) }
+    }
+
+    /// Verifies checksum of all the blocks in the file.
+    pub fn verify_checksum(&self) -> Result<()> {
+        self.verify_checksum_with_options(ReadOptions::default_instance())
+    }
+
+    /// Same as `verify_checksum()`, with `ReadOptions` controlling e.g.
+    /// readahead.
+    pub fn verify_checksum_with_options(&self, options: &ReadOptions) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_sst_file_reader_verify_checksum(self.raw, options.raw(), &mut status);
+            Error::from_ll(status)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{Seek, SeekFrom, Write};
+
+    use super::*;
+    use crate::sst_file_writer::SstFileWriter;
+
+    #[test]
+    fn read_sst_file() {
+        let sst_dir = ::tempdir::TempDir::new_in(".", "sst").unwrap();
+        let path = sst_dir.path().join("external.sst");
+
+        let writer = SstFileWriter::builder().build();
+        writer.open(&path).unwrap();
+        for i in 0..100 {
+            let key = format!("key{:03}", i);
+            let value = format!("value{}", i);
+            writer.put(key.as_bytes(), value.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let reader = SstFileReader::open(Options::default(), &path).unwrap();
+        assert!(reader.verify_checksum().is_ok());
+
+        let props = reader.table_properties();
+        assert_eq!(props.num_entries(), 100);
+        assert_eq!(props.comparator_name(), "leveldb.BytewiseComparator");
+
+        let kvs = reader
+            .new_iterator(&ReadOptions::default())
+            .map(|(k, v)| (k.to_vec(), v.to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(kvs.len(), 100);
+        assert_eq!(kvs[0], (b"key000".to_vec(), b"value0".to_vec()));
+        assert_eq!(kvs[99], (b"key099".to_vec(), b"value99".to_vec()));
+
+        let mut it = reader.new_iterator(&ReadOptions::default());
+        it.seek(b"key042");
+        assert!(it.is_valid());
+        assert_eq!(it.value(), b"value42");
+
+        // corrupt the data block
+        let mut f = fs::OpenOptions::new().write(true).open(&path).unwrap();
+        f.seek(SeekFrom::Start(16)).unwrap();
+        f.write_all(b"garbage").unwrap();
+        drop(f);
+
+        let reader = SstFileReader::open(Options::default(), &path).unwrap();
+        assert!(reader.verify_checksum().is_err());
+    }
+
+    #[test]
+    fn open_missing_file() {
+        let sst_dir = ::tempdir::TempDir::new_in(".", "sst").unwrap();
+        assert!(SstFileReader::open(Options::default(), sst_dir.path().join("missing.sst")).is_err());
+    }
+}
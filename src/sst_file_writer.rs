@@ -10,7 +10,7 @@ use rocks_sys as ll;
 
 use crate::db::ColumnFamilyHandle;
 use crate::env::EnvOptions;
-use crate::options::Options;
+use crate::options::{ColumnFamilyOptions, Options};
 use crate::to_raw::ToRaw;
 use crate::types::SequenceNumber;
 use crate::{Error, Result};
@@ -116,7 +116,7 @@ impl SstFileWriter {
         SstFileWriterBuilder {
             env_options: None,
             options: None,
-            c_comparator: unsafe { ll::rocks_comparator_bytewise() },
+            c_comparator: None,
             rust_comparator: ptr::null_mut(),
             use_rust_comparator: false,
             column_family: ptr::null_mut(),
@@ -181,6 +181,27 @@ impl SstFileWriter {
         }
     }
 
+    /// Add a range deletion tombstone to currently opened file, deleting keys
+    /// in `[begin_key, end_key)`.
+    ///
+    /// Range tombstones don't need to be added in order. Once ingested, the
+    /// tombstone only covers keys already in the DB, not the keys in the same
+    /// file, so a range can be cleared and repopulated atomically.
+    pub fn delete_range(&self, begin_key: &[u8], end_key: &[u8]) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_sst_file_writer_delete_range(
+                self.raw,
+                begin_key.as_ptr() as *const _,
+                begin_key.len(),
+                end_key.as_ptr() as *const _,
+                end_key.len(),
+                &mut status,
+            );
+            Error::from_ll(status)
+        }
+    }
+
     /// Finalize writing to sst file and close file.
     ///
     /// An optional ExternalSstFileInfo pointer can be passed to the function
@@ -195,15 +216,18 @@ impl SstFileWriter {
     }
 
     /// Return the current file size.
+    ///
+    /// Can be used to roll over to a new file once a size threshold is reached.
     pub fn file_size(&self) -> u64 {
-        unimplemented!()
+        unsafe { ll::rocks_sst_file_writer_file_size(self.raw) }
     }
 }
 
 pub struct SstFileWriterBuilder {
     env_options: Option<EnvOptions>,
     options: Option<Options>,
-    c_comparator: *const ll::rocks_c_comparator_t,
+    // None takes the comparator from the options
+    c_comparator: Option<*const ll::rocks_c_comparator_t>,
     rust_comparator: *mut (),
     use_rust_comparator: bool,
     column_family: *mut ll::rocks_column_family_handle_t,
@@ -216,11 +240,26 @@ impl SstFileWriterBuilder {
         self
     }
 
+    /// Build sst files with the same options as the target column family, so
+    /// that comparator, merge operator, compression and table format match.
+    ///
+    /// The comparator is taken from `cfopts`.
+    pub fn column_family_options(&mut self, cfopts: ColumnFamilyOptions) -> &mut Self {
+        self.options = Some(Options::new(None, Some(cfopts)));
+        self
+    }
+
+    /// Same as `column_family_options()`, but with full `Options`.
+    pub fn options(&mut self, opts: Options) -> &mut Self {
+        self.options = Some(opts);
+        self
+    }
+
     pub fn build(&mut self) -> SstFileWriter {
         let env_options = self.env_options.take().unwrap_or_default();
         let options = self.options.take().unwrap_or_default();
-        let ptr = if self.use_rust_comparator {
-            unsafe {
+        let ptr = unsafe {
+            if self.use_rust_comparator {
                 ll::rocks_sst_file_writer_create_from_rust_comparator(
                     env_options.raw(),
                     options.raw(),
@@ -228,13 +267,18 @@ impl SstFileWriterBuilder {
                     self.column_family,
                     self.invalidate_page_cache as u8,
                 )
-            }
-        } else {
-            unsafe {
+            } else if let Some(comparator) = self.c_comparator {
                 ll::rocks_sst_file_writer_create_from_c_comparator(
                     env_options.raw(),
                     options.raw(),
-                    self.c_comparator as *const _,
+                    comparator as *const _,
+                    self.column_family,
+                    self.invalidate_page_cache as u8,
+                )
+            } else {
+                ll::rocks_sst_file_writer_create(
+                    env_options.raw(),
+                    options.raw(),
                     self.column_family,
                     self.invalidate_page_cache as u8,
                 )
@@ -242,8 +286,8 @@ impl SstFileWriterBuilder {
         };
         SstFileWriter {
             raw: ptr,
-            env_options: env_options,
-            options: options,
+            env_options,
+            options,
         }
     }
 }
@@ -279,4 +323,46 @@ mod tests {
         let ret = writer.put(b"0000000", b"hello rust");
         assert!(ret.is_err()); // "Keys must be added in order"
     }
+
+    #[test]
+    fn sst_file_with_column_family_options() {
+        use crate::rocksdb::*;
+
+        let sst_dir = ::tempdir::TempDir::new_in(".", "sst").unwrap();
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        let cf = db
+            .create_column_family(
+                &ColumnFamilyOptions::default().bitwise_comparator_reversed(true),
+                "reversed",
+            )
+            .unwrap();
+
+        let writer = SstFileWriter::builder()
+            .column_family_options(ColumnFamilyOptions::default().bitwise_comparator_reversed(true))
+            .column_family(&cf)
+            .build();
+        writer.open(sst_dir.path().join("reversed.sst")).unwrap();
+        let mut last_size = writer.file_size();
+        for i in (0..999).rev() {
+            let key = format!("B{:05}", i);
+            writer.put(key.as_bytes(), b"23333").unwrap();
+            assert!(writer.file_size() >= last_size);
+            last_size = writer.file_size();
+        }
+        let info = writer.finish().unwrap();
+        assert_eq!(info.num_entries(), 999);
+        assert_eq!(info.smallest_key(), b"B00998");
+
+        let ret = cf.ingest_external_file(
+            &[sst_dir.path().join("reversed.sst")],
+            &IngestExternalFileOptions::default(),
+        );
+        assert!(ret.is_ok(), "ingest external file: {:?}", ret);
+        assert_eq!(cf.get(&ReadOptions::default(), b"B00042").unwrap(), b"23333");
+    }
 }
@@ -3,10 +3,12 @@
 use std::fmt;
 use std::mem;
 use std::os::raw::c_void;
+use std::ptr;
 
 use rocks_sys as ll;
 
 use crate::to_raw::ToRaw;
+use crate::{Error, Result};
 
 /// Ticker statistics
 #[repr(u32)]
@@ -357,12 +359,16 @@ impl fmt::Display for Tickers {
 }
 
 /// Histogram statistics
-#[repr(u32)]
+///
+/// Mapped to the RocksDB histogram IDs by name, see `Display`, so that the
+/// variants stay correct as RocksDB adds histograms.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Histograms {
-    DbGet = 0,
+    DbGet,
     DbWrite,
     CompactionTime,
+    /// CPU time spent in compactions
+    CompactionCpuTime,
     SubcompactionSetupTime,
     TableSyncMicros,
     CompactionOutfileSyncMicros,
@@ -396,6 +402,27 @@ pub enum Histograms {
     BytesDecompressed,
     CompressionTimesNanos,
     DecompressionTimesNanos,
+    /// Number of merge operands passed to the merge operator in user read
+    /// requests.
+    ReadNumMergeOperands,
+    /// Time spent in flushes
+    FlushTime,
+}
+
+impl Histograms {
+    /// The histogram ID of the linked RocksDB, `None` if it doesn't have this
+    /// histogram.
+    fn raw_type(self) -> Option<u32> {
+        let name = self.to_string();
+        let mut type_ = 0;
+        unsafe {
+            if ll::rocks_statistics_histogram_type_by_name(name.as_ptr() as *const _, name.len(), &mut type_) != 0 {
+                Some(type_)
+            } else {
+                None
+            }
+        }
+    }
 }
 
 
@@ -407,6 +434,7 @@ impl fmt::Display for Histograms {
             DbGet => "rocksdb.db.get.micros",
             DbWrite => "rocksdb.db.write.micros",
             CompactionTime => "rocksdb.compaction.times.micros",
+            CompactionCpuTime => "rocksdb.compaction.times.cpu_micros",
             SubcompactionSetupTime => "rocksdb.subcompaction.setup.times.micros",
             TableSyncMicros => "rocksdb.table.sync.micros",
             CompactionOutfileSyncMicros => "rocksdb.compaction.outfile.sync.micros",
@@ -434,6 +462,8 @@ impl fmt::Display for Histograms {
             BytesDecompressed => "rocksdb.bytes.decompressed",
             CompressionTimesNanos => "rocksdb.compression.times.nanos",
             DecompressionTimesNanos => "rocksdb.decompression.times.nanos",
+            ReadNumMergeOperands => "rocksdb.read.num.merge_operands",
+            FlushTime => "rocksdb.db.flush.micros",
         };
         write!(f, "{}", val)
     }
@@ -449,6 +479,11 @@ pub struct HistogramData {
     pub average: f64,
     pub standard_deviation: f64,
     pub max: f64,
+    /// Number of recorded values
+    pub count: u64,
+    /// Sum of the recorded values
+    pub sum: u64,
+    pub min: f64,
 }
 
 #[repr(C)]
@@ -510,30 +545,67 @@ impl Statistics {
         unsafe { ll::rocks_statistics_get_ticker_count(self.raw, mem::transmute(ticker_type)) }
     }
 
+    /// Data of the histogram, empty if the linked RocksDB lacks it.
     pub fn histogram_data(&self, type_: Histograms) -> HistogramData {
-        unsafe {
-            let mut data = HistogramData::default();
-            ll::rocks_statistics_histogram_data(
-                self.raw,
-                mem::transmute(type_),
-                &mut data as *mut HistogramData as *mut ll::rocks_histogram_data_t,
-            );
-            data
+        let mut data = HistogramData::default();
+        if let Some(type_) = type_.raw_type() {
+            unsafe {
+                ll::rocks_statistics_histogram_data(
+                    self.raw,
+                    type_,
+                    &mut data as *mut HistogramData as *mut ll::rocks_histogram_data_t,
+                );
+            }
         }
+        data
     }
 
     pub fn get_histogram_string(&self, type_: Histograms) -> String {
         let mut ret = String::new();
-        unsafe {
-            ll::rocks_statistics_get_histogram_string(
-                self.raw,
-                mem::transmute(type_),
-                &mut ret as *mut String as *mut _,
-            );
+        if let Some(type_) = type_.raw_type() {
+            unsafe {
+                ll::rocks_statistics_get_histogram_string(self.raw, type_, &mut ret as *mut String as *mut _);
+            }
         }
         ret
     }
 
+    /// Latency of `get()` calls, in microseconds.
+    pub fn db_get_micros(&self) -> HistogramData {
+        self.histogram_data(Histograms::DbGet)
+    }
+
+    /// Latency of `write()` calls, including `put()` and `delete()`, in
+    /// microseconds.
+    pub fn db_write_micros(&self) -> HistogramData {
+        self.histogram_data(Histograms::DbWrite)
+    }
+
+    /// Duration of compactions, in microseconds.
+    pub fn compaction_time_micros(&self) -> HistogramData {
+        self.histogram_data(Histograms::CompactionTime)
+    }
+
+    /// Duration of flushes, in microseconds.
+    pub fn flush_time_micros(&self) -> HistogramData {
+        self.histogram_data(Histograms::FlushTime)
+    }
+
+    /// Latency of table file reads, in microseconds.
+    pub fn sst_read_micros(&self) -> HistogramData {
+        self.histogram_data(Histograms::SstReadMicros)
+    }
+
+    /// Size of the values read by `get()`, in bytes.
+    pub fn bytes_per_read(&self) -> HistogramData {
+        self.histogram_data(Histograms::BytesPerRead)
+    }
+
+    /// Size of the write batches written, in bytes.
+    pub fn bytes_per_write(&self) -> HistogramData {
+        self.histogram_data(Histograms::BytesPerWrite)
+    }
+
     // add count to ticker
     pub fn record_tick(&mut self, ticker_type: Tickers, count: u64) {
         unsafe {
@@ -552,14 +624,30 @@ impl Statistics {
     }
 
     pub fn measure_time(&mut self, histogram_type: Histograms, time: u64) {
+        if let Some(histogram_type) = histogram_type.raw_type() {
+            unsafe {
+                ll::rocks_statistics_measure_time(self.raw, histogram_type, time);
+            }
+        }
+    }
+
+    /// Resets all ticker and histogram stats.
+    ///
+    /// A clone shares its stats with the original, so this resets the stats
+    /// of every DB the statistics object was handed to.
+    pub fn reset(&self) -> Result<()> {
+        let mut status = ptr::null_mut::<ll::rocks_status_t>();
         unsafe {
-            ll::rocks_statistics_measure_time(self.raw, mem::transmute(histogram_type), time);
+            ll::rocks_statistics_reset(self.raw, &mut status);
+            Error::from_ll(status)
         }
     }
 
     // Override this function to disable particular histogram collection
     pub fn hist_enabled_for_type(&self, type_: Histograms) -> bool {
-        unsafe { ll::rocks_statistics_hist_enabled_for_type(self.raw, mem::transmute(type_)) != 0 }
+        type_.raw_type().is_some_and(|type_| unsafe {
+            ll::rocks_statistics_hist_enabled_for_type(self.raw, type_) != 0
+        })
     }
 }
 
@@ -618,4 +706,64 @@ mod tests {
         // a multiline string
         assert!(stat.get_histogram_string(Histograms::BytesPerRead).len() > 100);
     }
+
+    #[test]
+    fn reset_statistics() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+        let stat = Statistics::new();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true).statistics(Some(stat.clone()))),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..10 {
+            let key = format!("k{}", i);
+            assert!(db.put(&Default::default(), key.as_bytes(), b"v").is_ok());
+        }
+        assert_eq!(stat.get_ticker_count(Tickers::NumberKeysWritten), 10);
+
+        assert!(stat.reset().is_ok());
+        assert!(db.reset_stats().is_ok());
+        assert_eq!(stat.get_ticker_count(Tickers::NumberKeysWritten), 0);
+
+        assert!(db.put(&Default::default(), b"k", b"v").is_ok());
+        assert_eq!(db.get(&Default::default(), b"k0").unwrap(), b"v");
+        assert_eq!(stat.get_ticker_count(Tickers::NumberKeysWritten), 1);
+    }
+
+    #[test]
+    fn typed_histograms() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+        let stat = Statistics::new();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true).statistics(Some(stat.clone()))),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..10 {
+            let key = format!("k{}", i);
+            assert!(db.put(&Default::default(), key.as_bytes(), b"value").is_ok());
+        }
+        for i in 0..20 {
+            let key = format!("k{}", i);
+            let _ = db.get(&Default::default(), key.as_bytes());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        let get = stat.db_get_micros();
+        assert_eq!(get.count, 20);
+        assert!(get.max >= get.min);
+        // recorded for every get, misses read 0 bytes
+        assert_eq!(stat.bytes_per_read().count, 20);
+        assert_eq!(stat.bytes_per_read().sum, 10 * 5);
+        assert_eq!(stat.db_write_micros().count, 10);
+        assert_eq!(stat.bytes_per_write().count, 10);
+        assert_eq!(stat.compaction_time_micros().count, 0);
+        assert_eq!(stat.histogram_data(Histograms::DbGet).count, get.count);
+        assert!(stat.get_histogram_string(Histograms::DbGet).contains("Count: 20"));
+    }
 }
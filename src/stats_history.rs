@@ -0,0 +1,159 @@
+//! In-memory history of DB statistics, see `DBOptions::stats_persist_period_sec`.
+
+use std::collections::HashMap;
+use std::iter;
+use std::ptr;
+use std::slice;
+use std::str;
+
+use rocks_sys as ll;
+
+use crate::to_raw::{FromRaw, ToRaw};
+use crate::{Error, Result};
+
+/// Iterates over the stats snapshots returned by `DB::get_stats_history()`,
+/// oldest first. Each snapshot holds the delta of every ticker since the
+/// previous one.
+#[derive(Debug)]
+pub struct StatsHistoryIterator {
+    raw: *mut ll::rocks_stats_history_iterator_t,
+}
+
+impl ToRaw<ll::rocks_stats_history_iterator_t> for StatsHistoryIterator {
+    fn raw(&self) -> *mut ll::rocks_stats_history_iterator_t {
+        self.raw
+    }
+}
+
+impl FromRaw<ll::rocks_stats_history_iterator_t> for StatsHistoryIterator {
+    unsafe fn from_ll(raw: *mut ll::rocks_stats_history_iterator_t) -> StatsHistoryIterator {
+        StatsHistoryIterator { raw: raw }
+    }
+}
+
+impl Drop for StatsHistoryIterator {
+    fn drop(&mut self) {
+        unsafe {
+            ll::rocks_stats_history_iterator_destroy(self.raw);
+        }
+    }
+}
+
+impl StatsHistoryIterator {
+    /// Whether the iterator is positioned at a stats snapshot.
+    pub fn is_valid(&self) -> bool {
+        unsafe { ll::rocks_stats_history_iterator_valid(self.raw) != 0 }
+    }
+
+    /// Moves the iterator to the next stats snapshot.
+    ///
+    /// REQUIRES: Valid() to be true.
+    ///
+    /// Rust: avoid name collision with `Iterator::next`
+    pub fn move_next(&mut self) {
+        unsafe {
+            ll::rocks_stats_history_iterator_next(self.raw);
+        }
+    }
+
+    /// Returns ok if the iterator is valid.
+    /// Returns the Error when something has gone wrong.
+    pub fn status(&self) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_stats_history_iterator_status(self.raw, &mut status);
+            Error::from_ll(status)
+        }
+    }
+
+    /// The time the current snapshot was taken, in seconds since the epoch.
+    pub fn get_stats_time(&self) -> u64 {
+        unsafe { ll::rocks_stats_history_iterator_get_stats_time(self.raw) }
+    }
+
+    /// The ticker values of the current snapshot, keyed by ticker name,
+    /// e.g. `"rocksdb.number.keys.written"`.
+    pub fn get_stats_map(&self) -> HashMap<String, u64> {
+        unsafe {
+            let len = ll::rocks_stats_history_iterator_load_stats_map(self.raw);
+            (0..len)
+                .map(|i| {
+                    let mut key_len = 0;
+                    let key_ptr = ll::rocks_stats_history_iterator_stats_map_nth_key(self.raw, i, &mut key_len);
+                    let key = str::from_utf8_unchecked(slice::from_raw_parts(key_ptr as *const u8, key_len));
+                    (
+                        key.to_owned(),
+                        ll::rocks_stats_history_iterator_stats_map_nth_value(self.raw, i),
+                    )
+                })
+                .collect()
+        }
+    }
+}
+
+impl iter::Iterator for StatsHistoryIterator {
+    /// (stats time, stats map)
+    type Item = (u64, HashMap<String, u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_valid() && self.status().is_ok() {
+            let item = (self.get_stats_time(), self.get_stats_map());
+            self.move_next();
+            Some(item)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rocksdb::*;
+    use super::super::statistics::Statistics;
+
+    #[test]
+    fn stats_history() {
+        use std::thread;
+        use std::time::Duration;
+
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| {
+                db.create_if_missing(true)
+                    .statistics(Some(Statistics::new()))
+                    .stats_persist_period_sec(1)
+                    .stats_history_buffer_size(1024 * 1024)
+            }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..10 {
+            let key = format!("k{}", i);
+            assert!(db.put(&Default::default(), key.as_bytes(), b"v").is_ok());
+        }
+
+        // the first persist only takes a baseline, snapshots start from the second
+        let mut snapshots = vec![];
+        for _ in 0..50 {
+            thread::sleep(Duration::from_millis(200));
+            snapshots = db.get_stats_history(0, u64::max_value()).unwrap().collect::<Vec<_>>();
+            if !snapshots.is_empty() {
+                break;
+            }
+        }
+        assert!(!snapshots.is_empty());
+
+        let (stats_time, ref stats_map) = snapshots[0];
+        assert!(stats_time > 0);
+        assert!(stats_map.contains_key("rocksdb.number.keys.written"));
+
+        // nothing in the future
+        assert_eq!(
+            db.get_stats_history(stats_time + 3600, u64::max_value())
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+}
@@ -44,6 +44,17 @@ pub enum IndexType {
     TwoLevelIndexSearch,
 }
 
+/// The index type of data blocks, see `BlockBasedTableOptions::data_block_index_type()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub enum DataBlockIndexType {
+    /// Traditional block type, binary search within the block's restart points.
+    BinarySearch,
+
+    /// Additional hash index, to speed up point lookups within a block.
+    BinarySearchAndHash,
+}
+
 // not yet supported. Will fail
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(C)]
@@ -137,6 +148,27 @@ impl BlockBasedTableOptions {
         self
     }
 
+    /// The index type of the data blocks. `BinarySearchAndHash` adds a small
+    /// hash index to each data block, so point lookups don't need a binary
+    /// search. It has no effect on range scans.
+    ///
+    /// Default: BinarySearch
+    pub fn data_block_index_type(self, val: DataBlockIndexType) -> Self {
+        unsafe { ll::rocks_block_based_table_options_set_data_block_index_type(self.raw, mem::transmute(val)) }
+        self
+    }
+
+    /// `#entries/#buckets` of the data block hash index. It is only used when
+    /// `data_block_index_type` is `BinarySearchAndHash`.
+    ///
+    /// Default: 0.75
+    pub fn data_block_hash_table_util_ratio(self, val: f64) -> Self {
+        unsafe {
+            ll::rocks_block_based_table_options_set_data_block_hash_table_util_ratio(self.raw, val);
+        }
+        self
+    }
+
     /// This option is now deprecated. No matter what value it is set to,
     /// it will behave as if `hash_index_allow_collision=true`.
     pub fn hash_index_allow_collision(self, val: bool) -> Self {
@@ -167,7 +199,7 @@ impl BlockBasedTableOptions {
     /// If NULL, rocksdb will automatically create and use an 8MB internal cache.
     pub fn block_cache(self, val: Option<Cache>) -> Self {
         unsafe {
-            let ptr = val.map(|c| c.raw()).unwrap_or_else(ptr::null_mut);
+            let ptr = val.as_ref().map(|c| c.raw()).unwrap_or_else(ptr::null_mut);
             ll::rocks_block_based_table_options_set_block_cache(self.raw, ptr);
         }
         self
@@ -191,9 +223,13 @@ impl BlockBasedTableOptions {
     /// If non-NULL use the specified cache for compressed blocks.
     ///
     /// If NULL, rocksdb will not use a compressed block cache.
+    ///
+    /// Blocks evicted from `block_cache` can then be served from here without
+    /// a read from disk, at the cost of decompressing them again. Since
+    /// compressed blocks are smaller, this cache holds more data per byte.
     pub fn block_cache_compressed(self, val: Option<Cache>) -> Self {
         unsafe {
-            let ptr = val.map(|c| c.raw()).unwrap_or_else(ptr::null_mut);
+            let ptr = val.as_ref().map(|c| c.raw()).unwrap_or_else(ptr::null_mut);
             ll::rocks_block_based_table_options_set_block_cache_compressed(self.raw, ptr);
         }
         self
@@ -368,6 +404,16 @@ impl BlockBasedTableOptions {
     /// don't plan to run RocksDB before version 3.10, you should probably use
     /// this.
     ///
+    /// 3 -- Can be read by RocksDB's versions since 5.15. Changes the way we
+    /// encode the keys in index blocks.
+    ///
+    /// 4 -- Can be read by RocksDB's versions since 5.16. Changes the way we
+    /// encode the values in index blocks.
+    ///
+    /// 5 -- Can be read by RocksDB's versions since 6.6.0. Full and partitioned
+    /// filters use a generally faster and more accurate Bloom filter
+    /// implementation, with a different schema.
+    ///
     /// This option only affects newly written tables. When reading exising tables,
     /// the information about version is read from the footer.
     pub fn format_version(self, val: u32) -> Self {
@@ -298,6 +298,10 @@ impl<'a> TableProperties<'a> {
     pub fn num_entries(&self) -> u64 {
         unsafe { ll::rocks_table_props_get_num_entries(self.raw) }
     }
+    /// the number of deletions in the table
+    pub fn num_deletions(&self) -> u64 {
+        unsafe { ll::rocks_table_props_get_num_deletions(self.raw) }
+    }
     /// format version, reserved for backward compatibility
     pub fn format_version(&self) -> u64 {
         unsafe { ll::rocks_table_props_get_format_version(self.raw) }
@@ -306,6 +310,25 @@ impl<'a> TableProperties<'a> {
     pub fn fixed_key_len(&self) -> u64 {
         unsafe { ll::rocks_table_props_get_format_version(self.raw) }
     }
+    /// Estimated size of the data blocks if compressed with a slow algorithm
+    /// (ZSTD). Only set when `sample_for_compression` is enabled, else 0.
+    pub fn slow_compression_estimated_data_size(&self) -> u64 {
+        unsafe { ll::rocks_table_props_get_slow_compression_estimated_data_size(self.raw) }
+    }
+    /// Estimated size of the data blocks if compressed with a fast algorithm
+    /// (LZ4 or Snappy). Only set when `sample_for_compression` is enabled, else 0.
+    pub fn fast_compression_estimated_data_size(&self) -> u64 {
+        unsafe { ll::rocks_table_props_get_fast_compression_estimated_data_size(self.raw) }
+    }
+    /// Estimated compression ratio of the data blocks with the fast algorithm,
+    /// as raw key and value bytes over `fast_compression_estimated_data_size`.
+    /// `None` if sampling was disabled.
+    pub fn estimated_compression_ratio(&self) -> Option<f64> {
+        match self.fast_compression_estimated_data_size() {
+            0 => None,
+            estimated => Some((self.raw_key_size() + self.raw_value_size()) as f64 / estimated as f64),
+        }
+    }
     /// ID of column family for this SST file, corresponding to the CF identified
     /// by column_family_name.
     pub fn column_family_id(&self) -> u32 {
@@ -666,4 +689,36 @@ mod tests {
         counters.dedup(); // assure files returned are all unique
         assert_eq!(counters.len(), 100);
     }
+
+    #[test]
+    fn sample_for_compression() {
+        let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+        let db = DB::open(
+            Options::default()
+                .map_db_options(|db| db.create_if_missing(true))
+                .map_cf_options(|cf| {
+                    cf.compression(CompressionType::NoCompression)
+                        .sample_for_compression(1) // sample every block
+                }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        // highly compressible
+        let value = "0123456789".repeat(100);
+        for i in 0..1000 {
+            let key = format!("k{:04}", i);
+            db.put(&WriteOptions::default(), key.as_bytes(), value.as_bytes()).unwrap();
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+
+        let props = db.get_properties_of_all_tables_cf(&db.default_column_family()).unwrap();
+        assert_eq!(props.len(), 1);
+        for (_, prop) in props.iter() {
+            assert!(prop.fast_compression_estimated_data_size() > 0);
+            assert!(prop.fast_compression_estimated_data_size() < prop.data_size());
+            let ratio = prop.estimated_compression_ratio().unwrap();
+            assert!(ratio > 2.0, "ratio = {}", ratio);
+        }
+    }
 }
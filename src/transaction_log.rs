@@ -59,6 +59,22 @@ pub struct BatchResult {
     pub write_batch: WriteBatch,
 }
 
+/// Options for `DB::get_updates_since_opt()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TransactionLogIteratorOptions {
+    /// If true, all data read from underlying storage will be
+    /// verified against corresponding checksums.
+    ///
+    /// Default: true
+    pub verify_checksums: bool,
+}
+
+impl Default for TransactionLogIteratorOptions {
+    fn default() -> Self {
+        TransactionLogIteratorOptions { verify_checksums: true }
+    }
+}
+
 /// A `TransactionLogIterator` is used to iterate over the transactions in a db.
 /// One run of the iterator is continuous, i.e. the iterator will stop at the
 /// beginning of any gap in sequences
@@ -209,4 +225,114 @@ mod tests {
             assert!(batch.sequence.0 > 20 - 3);
         }
     }
+
+    #[test]
+    fn updates_since_purged_sequence() {
+        use crate::error::Code;
+
+        let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+        // no WAL archive, obsolete WAL files are deleted right after a flush
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        for i in 0..10 {
+            let key = format!("k{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"v").is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        let resume_from = db.get_latest_sequence_number().next();
+        for i in 10..20 {
+            let key = format!("k{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"v").is_ok());
+        }
+
+        let err = db.get_updates_since(SequenceNumber(3)).unwrap_err();
+        assert_eq!(err.code(), Code::Expired);
+
+        let err = db.get_updates_since(db.get_latest_sequence_number() + 100).unwrap_err();
+        assert_eq!(err.code(), Code::NotFound);
+
+        let opts = TransactionLogIteratorOptions {
+            verify_checksums: false,
+        };
+        let batches = db
+            .get_updates_since_opt(resume_from, &opts)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(batches.len(), 10);
+        assert_eq!(batches[0].sequence, resume_from);
+    }
+
+    #[test]
+    fn updates_since_buffered_in_manual_wal_flush() {
+        let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true).manual_wal_flush(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        assert!(db.put(&WriteOptions::default(), b"k0", b"v").is_ok());
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        // only in the WAL buffer, the live WAL file is still empty
+        let seq = db.get_latest_sequence_number().next();
+        assert!(db.put(&WriteOptions::default(), b"k1", b"v").is_ok());
+
+        let batches = db.get_updates_since(seq).unwrap().collect::<Vec<_>>();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].sequence, seq);
+    }
+
+    #[test]
+    fn preserve_deletes() {
+        use crate::debug::ValueType;
+        use crate::write_batch::WriteBatchEntry;
+
+        let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+        let db = DB::open(
+            Options::default().map_db_options(|db| {
+                db.create_if_missing(true)
+                    .preserve_deletes(true)
+                    .wal_ttl_seconds(1000000)
+                    .wal_size_limit_mb(1024)
+            }),
+            &tmp_dir,
+        )
+        .unwrap();
+
+        assert!(db.put(&WriteOptions::default(), b"k1", b"v1").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k2", b"v2").is_ok());
+        let cutoff = db.get_latest_sequence_number();
+        assert!(db.set_preserve_deletes(cutoff));
+        assert!(!db.set_preserve_deletes(cutoff)); // not increasing
+        assert!(db.delete(&WriteOptions::default(), b"k1").is_ok());
+
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db
+            .compact_range(
+                &CompactRangeOptions::default().bottommost_level_compaction(BottommostLevelCompaction::Force),
+                ..
+            )
+            .is_ok());
+
+        // tombstone survives the bottommost compaction
+        let versions = db.get_all_key_versions(b"k1", b"k2", 100).unwrap();
+        assert!(versions.iter().any(|v| v.value_type() == ValueType::Deletion));
+        assert!(db.get(&ReadOptions::default(), b"k1").unwrap_err().is_not_found());
+
+        let mut deleted = vec![];
+        for batch in db.get_updates_since(cutoff).unwrap() {
+            let mut handler = WriteBatchIteratorHandler::default();
+            assert!(batch.write_batch.iterate(&mut handler).is_ok());
+            for entry in handler.entries {
+                if let WriteBatchEntry::Delete { key, .. } = entry {
+                    deleted.push(key);
+                }
+            }
+        }
+        assert_eq!(deleted, vec![b"k1".to_vec()]);
+    }
 }
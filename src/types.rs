@@ -1,12 +1,62 @@
 //! Define all public custom types here.
 
-use std::ops::Deref;
 use std::convert::From;
+use std::fmt;
+use std::ops::{Add, AddAssign, Deref, Sub};
 
 /// Represents a sequence number in a WAL file.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///
+/// Every write gets the next sequence number, so they order writes, e.g. to
+/// keep a replication cursor with `DB::get_updates_since()`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SequenceNumber(pub u64);
 
+impl SequenceNumber {
+    /// The largest valid sequence number, the lower 56 bits of an internal key
+    /// trailer.
+    pub const MAX: SequenceNumber = SequenceNumber((1 << 56) - 1);
+
+    /// The sequence number right after this one.
+    pub fn next(self) -> SequenceNumber {
+        SequenceNumber(self.0 + 1)
+    }
+
+    /// Number of sequence numbers from `earlier` up to this one, `None` if
+    /// `earlier` is actually later.
+    pub fn checked_distance_from(self, earlier: SequenceNumber) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+impl fmt::Display for SequenceNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add<u64> for SequenceNumber {
+    type Output = SequenceNumber;
+
+    fn add(self, rhs: u64) -> SequenceNumber {
+        SequenceNumber(self.0 + rhs)
+    }
+}
+
+impl AddAssign<u64> for SequenceNumber {
+    fn add_assign(&mut self, rhs: u64) {
+        self.0 += rhs;
+    }
+}
+
+/// Number of sequence numbers between two, panics on underflow in debug builds.
+impl Sub for SequenceNumber {
+    type Output = u64;
+
+    fn sub(self, rhs: SequenceNumber) -> u64 {
+        self.0 - rhs.0
+    }
+}
+
 impl From<u64> for SequenceNumber {
     fn from(x: u64) -> SequenceNumber {
         SequenceNumber(x)
@@ -26,3 +76,28 @@ impl Deref for SequenceNumber {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_number_ordering_and_display() {
+        let a = SequenceNumber(41);
+        let b = a.next();
+        assert!(a < b);
+        assert_eq!(a.max(b), SequenceNumber(42));
+        assert_eq!(b - a, 1);
+        assert_eq!(a + 10, SequenceNumber(51));
+        assert_eq!(b.checked_distance_from(a), Some(1));
+        assert_eq!(a.checked_distance_from(b), None);
+        assert!(b < SequenceNumber::MAX);
+        assert_eq!(SequenceNumber::default(), SequenceNumber(0));
+
+        let mut cursor = a;
+        cursor += 2;
+        assert_eq!(cursor, SequenceNumber(43));
+        assert_eq!(cursor.to_string(), "43");
+        assert_eq!(format!("{:?}", cursor), "SequenceNumber(43)");
+    }
+}
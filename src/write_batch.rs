@@ -21,7 +21,8 @@ use std::slice;
 
 use rocks_sys as ll;
 
-use crate::db::ColumnFamilyHandle;
+use crate::db::{ColumnFamilyHandle, DBRef};
+use crate::options::WriteOptions;
 use crate::to_raw::{FromRaw, ToRaw};
 use crate::Result;
 
@@ -86,6 +87,26 @@ impl WriteBatch {
         }
     }
 
+    /// Create a batch that is not allowed to grow past `max_bytes`, 0 means no limit.
+    ///
+    /// Operations that would exceed the limit are not recorded.
+    pub fn with_max_bytes(reserved_bytes: usize, max_bytes: usize) -> WriteBatch {
+        WriteBatch {
+            raw: unsafe { ll::rocks_writebatch_create_with_max_bytes(reserved_bytes, max_bytes) },
+        }
+    }
+
+    /// Create a batch from its serialized form, as returned by `get_data()`,
+    /// or by `TransactionLogIterator`.
+    ///
+    /// The data is not validated here, a malformed batch is reported as
+    /// corruption when written.
+    pub fn from_data(data: &[u8]) -> WriteBatch {
+        WriteBatch {
+            raw: unsafe { ll::rocks_writebatch_create_from(data.as_ptr() as *const _, data.len()) },
+        }
+    }
+
     /// Clear all updates buffered in this batch.
     pub fn clear(&mut self) {
         unsafe {
@@ -292,6 +313,19 @@ impl WriteBatch {
         }
     }
 
+    /// Append all operations of `other` after the ones already in this batch,
+    /// preserving their order.
+    ///
+    /// Save points set on this batch are dropped. If the result would be larger than
+    /// the `max_bytes` of this batch, it is left unchanged and a `MemoryLimit` error is returned.
+    pub fn append(&mut self, other: &WriteBatch) -> Result<()> {
+        let mut status = ptr::null_mut();
+        unsafe {
+            ll::rocks_writebatch_append(self.raw, other.raw, &mut status);
+            FromRaw::from_ll(status)
+        }
+    }
+
     /// Support for iterating over the contents of a batch.
     pub fn iterate<H: WriteBatchHandler>(&self, handler: &mut H) -> Result<()> {
         let mut status = ptr::null_mut();
@@ -312,13 +346,14 @@ impl WriteBatch {
         }
     }
 
-    // FIXME: extra data bytes copied, should use GetDataSize()
+    /// Size of the serialized batch in bytes, including the header.
+    pub fn data_size(&self) -> usize {
+        unsafe { ll::rocks_writebatch_get_data_size(self.raw) }
+    }
+
+    /// Alias of `data_size()`.
     pub fn get_data_size(&self) -> usize {
-        let mut size = 0;
-        unsafe {
-            ll::rocks_writebatch_data(self.raw, &mut size);
-        }
-        size as usize
+        self.data_size()
     }
 
     /// Returns the number of updates in the batch
@@ -372,6 +407,104 @@ impl WriteBatch {
     }
 }
 
+/// Accumulates updates into a `WriteBatch`, writing it to the DB and starting
+/// over whenever its serialized size reaches a threshold.
+///
+/// Useful for bulk loading, where a single batch holding everything would
+/// use an unbounded amount of memory. Updates are only atomic within each
+/// written batch.
+///
+/// Any pending updates are written when the writer is dropped, ignoring
+/// errors. Call `finish()` to write them and observe the result; a batch
+/// that `finish()` failed to write is discarded.
+pub struct BatchWriter<'a> {
+    db: &'a DBRef,
+    options: &'a WriteOptions,
+    threshold: usize,
+    batch: WriteBatch,
+}
+
+impl<'a> BatchWriter<'a> {
+    /// Create a writer that writes its batch once it holds at least
+    /// `threshold` bytes.
+    pub fn new(db: &'a DBRef, options: &'a WriteOptions, threshold: usize) -> BatchWriter<'a> {
+        BatchWriter {
+            db,
+            options,
+            threshold,
+            batch: WriteBatch::new(),
+        }
+    }
+
+    pub fn put(&mut self, key: &[u8], value: &[u8]) -> Result<&mut Self> {
+        self.batch.put(key, value);
+        self.maybe_flush()
+    }
+
+    pub fn put_cf(&mut self, column_family: &ColumnFamilyHandle, key: &[u8], value: &[u8]) -> Result<&mut Self> {
+        self.batch.put_cf(column_family, key, value);
+        self.maybe_flush()
+    }
+
+    pub fn delete(&mut self, key: &[u8]) -> Result<&mut Self> {
+        self.batch.delete(key);
+        self.maybe_flush()
+    }
+
+    pub fn delete_cf(&mut self, column_family: &ColumnFamilyHandle, key: &[u8]) -> Result<&mut Self> {
+        self.batch.delete_cf(column_family, key);
+        self.maybe_flush()
+    }
+
+    pub fn merge(&mut self, key: &[u8], value: &[u8]) -> Result<&mut Self> {
+        self.batch.merge(key, value);
+        self.maybe_flush()
+    }
+
+    pub fn merge_cf(&mut self, column_family: &ColumnFamilyHandle, key: &[u8], value: &[u8]) -> Result<&mut Self> {
+        self.batch.merge_cf(column_family, key, value);
+        self.maybe_flush()
+    }
+
+    /// Size in bytes of the updates not yet written.
+    pub fn pending_size(&self) -> usize {
+        self.batch.data_size()
+    }
+
+    /// Write any pending updates to the DB.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.batch.count() == 0 {
+            return Ok(());
+        }
+        self.db.write(self.options, &self.batch)?;
+        self.batch.clear();
+        Ok(())
+    }
+
+    /// Write any pending updates and consume the writer.
+    ///
+    /// On error the pending updates are discarded, they are not retried when
+    /// the writer is dropped.
+    pub fn finish(mut self) -> Result<()> {
+        let ret = self.flush();
+        self.batch.clear();
+        ret
+    }
+
+    fn maybe_flush(&mut self) -> Result<&mut Self> {
+        if self.batch.data_size() >= self.threshold {
+            self.flush()?;
+        }
+        Ok(self)
+    }
+}
+
+impl<'a> Drop for BatchWriter<'a> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
 /// Support for iterating over the contents of a batch.
 ///
 /// All handler functions in this class provide default implementations so
@@ -644,4 +777,125 @@ mod tests {
         assert_eq!(db.get(&ReadOptions::default(), b"name").unwrap().as_ref(), b"BH1XUW");
         assert_eq!(db.get(&ReadOptions::default(), b"site").unwrap().as_ref(), b"github");
     }
+
+    #[test]
+    fn append_batches() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+        let db = DB::open(opt, &tmp_dir).unwrap();
+
+        let mut first = WriteBatch::new();
+        first.put(b"a", b"1").put(b"b", b"1").delete(b"c");
+        let mut second = WriteBatch::new();
+        second.put(b"b", b"2").delete(b"a").put(b"c", b"2");
+
+        assert!(first.append(&second).is_ok());
+        assert_eq!(first.count(), 6);
+        assert_eq!(second.count(), 3);
+        assert!(first.has_put() && first.has_delete());
+
+        let mut handler = WriteBatchIteratorHandler::default();
+        assert!(first.iterate(&mut handler).is_ok());
+        assert_eq!(handler.entries.len(), 6);
+        assert_eq!(
+            handler.entries[3],
+            WriteBatchEntry::Put {
+                column_family_id: 0,
+                key: b"b".to_vec(),
+                value: b"2".to_vec(),
+            }
+        );
+
+        assert!(db.write(&WriteOptions::default(), &first).is_ok());
+        assert!(db.get(&ReadOptions::default(), b"a").unwrap_err().is_not_found());
+        assert_eq!(db.get(&ReadOptions::default(), b"b").unwrap(), b"2");
+        assert_eq!(db.get(&ReadOptions::default(), b"c").unwrap(), b"2");
+
+        // appending an empty batch is a no-op
+        assert!(first.append(&WriteBatch::new()).is_ok());
+        assert_eq!(first.count(), 6);
+
+        // the size limit of the destination is kept
+        let mut limited = WriteBatch::with_max_bytes(0, 64);
+        limited.put(b"a", b"1");
+        let mut large = WriteBatch::new();
+        large.put(b"b", &[0u8; 100]);
+        let err = limited.append(&large).unwrap_err();
+        assert_eq!(err.code(), crate::error::Code::Aborted);
+        assert_eq!(err.subcode(), crate::error::SubCode::MemoryLimit);
+        assert_eq!(limited.count(), 1);
+
+        assert!(limited.append(&second).is_ok());
+        assert_eq!(limited.count(), 4);
+        limited.put(b"d", &[0u8; 100]);
+        assert_eq!(limited.count(), 4);
+    }
+
+    #[test]
+    fn apply_serialized_batch() {
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+
+        let primary_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let primary = DB::open(&opt, &primary_dir).unwrap();
+        let mut batch = WriteBatch::new();
+        batch.put(b"a", b"1").put(b"b", b"2").delete(b"c");
+        let seq = primary.write_with_seq(&WriteOptions::default(), &batch).unwrap();
+        assert_eq!(seq.0, 1);
+        let data = batch.get_data().to_vec();
+
+        let replica_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let replica = DB::open(&opt, &replica_dir).unwrap();
+        assert!(replica.put(&WriteOptions::default(), b"x", b"y").is_ok());
+        let before = replica.get_latest_sequence_number();
+
+        let batch = WriteBatch::from_data(&data);
+        assert_eq!(batch.count(), 3);
+        let seq = replica.write_with_seq(&WriteOptions::default(), &batch).unwrap();
+        assert_eq!(seq.0, before.0 + 1);
+        assert_eq!(replica.get_latest_sequence_number().0, before.0 + 3);
+        assert_eq!(replica.get(&ReadOptions::default(), b"b").unwrap(), b"2");
+    }
+
+    #[test]
+    fn batch_writer_auto_flush() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+        let db = DB::open(opt, &tmp_dir).unwrap();
+
+        let wopts = WriteOptions::default();
+        let mut writer = BatchWriter::new(&db, &wopts, 1 << 20);
+        for i in 0..100_000 {
+            let key = format!("key{:06}", i);
+            writer.put(key.as_bytes(), b"some-value-of-moderate-length").unwrap();
+            assert!(writer.pending_size() < (1 << 20));
+        }
+        // earlier batches already landed
+        assert!(db.get(&ReadOptions::default(), b"key000000").is_ok());
+        assert!(writer.finish().is_ok());
+
+        let mut n = 0;
+        for (i, (key, _)) in db.new_iterator(&ReadOptions::default()).enumerate() {
+            assert_eq!(key, format!("key{:06}", i).as_bytes());
+            n += 1;
+        }
+        assert_eq!(n, 100_000);
+        assert_eq!(
+            db.get(&ReadOptions::default(), b"key099999").unwrap(),
+            &b"some-value-of-moderate-length"[..]
+        );
+    }
+
+    #[test]
+    fn batch_writer_finish_error() {
+        let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+        let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+        drop(DB::open(&opt, &tmp_dir).unwrap());
+        let db = DB::open_for_readonly(&opt, &tmp_dir, false).unwrap();
+
+        let wopts = WriteOptions::default();
+        let mut writer = BatchWriter::new(&db, &wopts, 1 << 20);
+        writer.put(b"key", b"value").unwrap();
+        // the failed batch is gone, dropping the writer doesn't write it again
+        assert!(writer.finish().is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use rocks::filter_policy::FilterPolicy;
 use rocks::prelude::*;
 use std::collections::HashMap;
 use tempdir::TempDir;
@@ -75,6 +76,51 @@ fn test_list_cfs() {
     }
 }
 
+#[test]
+fn open_many_column_families() {
+    use std::time::{Duration, Instant};
+
+    let tmp_dir = TempDir::new_in(".", "rocks").unwrap();
+    {
+        let db = DB::open(
+            Options::default().map_db_options(|db| db.create_if_missing(true)),
+            &tmp_dir,
+        )
+        .unwrap();
+        for i in 0..200 {
+            let cf = db
+                .create_column_family(&ColumnFamilyOptions::default(), &format!("cf{:03}", i))
+                .unwrap();
+            assert!(cf
+                .put(&WriteOptions::default(), b"key", format!("{}", i).as_bytes())
+                .is_ok());
+            // flushes the memtable to a table file
+            assert!(cf.compact_range(&Default::default(), ..).is_ok());
+        }
+    }
+
+    let cfs = DB::list_column_families(&Options::default(), &tmp_dir).unwrap();
+    assert_eq!(cfs.len(), 201);
+
+    // table files are opened in parallel, within each column family
+    let opt = DBOptions::default()
+        .max_open_files(-1)
+        .max_file_opening_threads(8)
+        .skip_stats_update_on_db_open(true)
+        .skip_checking_sst_file_sizes_on_db_open(true);
+    let start = Instant::now();
+    let (db, handles) = DB::open_with_column_families(&opt, &tmp_dir, cfs).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(60));
+
+    assert_eq!(handles.len(), 201);
+    for cf in handles.iter().filter(|cf| cf.name() != "default") {
+        let expected = cf.name()[2..].parse::<u32>().unwrap().to_string();
+        assert_eq!(cf.get(&ReadOptions::default(), b"key").unwrap(), expected.as_bytes());
+    }
+    drop(handles);
+    drop(db);
+}
+
 #[test]
 fn test_db_get() {
     let tmp_dir = TempDir::new_in(".", "rocks").unwrap();
@@ -201,6 +247,150 @@ fn test_ingest_sst_file() {
     drop(tmp_db_dir);
 }
 
+#[test]
+fn ingest_sst_file_with_range_deletion() {
+    use rocks::sst_file_writer::SstFileWriter;
+
+    let sst_dir = ::tempdir::TempDir::new_in(".", "rocks.sst").unwrap();
+    let tmp_db_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_db_dir,
+    )
+    .unwrap();
+    for key in &[b"b", b"c", b"d", b"x"] {
+        assert!(db.put(&WriteOptions::default(), *key, b"old").is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    let writer = SstFileWriter::builder().build();
+    writer.open(sst_dir.path().join("replace.sst")).unwrap();
+    writer.put(b"c", b"new").unwrap();
+    writer.put(b"e", b"new").unwrap();
+    writer.delete_range(b"a", b"m").unwrap();
+    writer.finish().unwrap();
+
+    let ret = db.ingest_external_file(
+        &[sst_dir.path().join("replace.sst")],
+        &IngestExternalFileOptions::default(),
+    );
+    assert!(ret.is_ok(), "ingest external file: {:?}", ret);
+
+    assert!(db.get(&ReadOptions::default(), b"b").unwrap_err().is_not_found());
+    assert!(db.get(&ReadOptions::default(), b"d").unwrap_err().is_not_found());
+    assert_eq!(db.get(&ReadOptions::default(), b"c").unwrap(), b"new");
+    assert_eq!(db.get(&ReadOptions::default(), b"e").unwrap(), b"new");
+    assert_eq!(db.get(&ReadOptions::default(), b"x").unwrap(), b"old");
+}
+
+#[test]
+fn ingest_sst_file_move_across_filesystems() {
+    use rocks::sst_file_writer::SstFileWriter;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    // stage SST files on a tmpfs if there is one, so that hard-linking them
+    // into the DB directory fails and ingestion has to fall back to copying
+    let staging = if Path::new("/dev/shm").is_dir() {
+        ::tempdir::TempDir::new_in("/dev/shm", "rocks.sst").unwrap()
+    } else {
+        ::tempdir::TempDir::new("rocks.sst").unwrap()
+    };
+    let tmp_db_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let cross_device = staging.path().metadata().unwrap().dev() != tmp_db_dir.path().metadata().unwrap().dev();
+
+    let write_sst = |name: &str| {
+        let path = staging.path().join(name);
+        let writer = SstFileWriter::builder().build();
+        writer.open(&path).unwrap();
+        for i in 0..100 {
+            let key = format!("{}{:05}", name, i);
+            writer.put(key.as_bytes(), b"value").unwrap();
+        }
+        writer.finish().unwrap();
+        path
+    };
+
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_db_dir,
+    )
+    .unwrap();
+
+    if cross_device {
+        let ret = db.ingest_external_file(
+            &[write_sst("A")],
+            &IngestExternalFileOptions::default()
+                .move_files(true)
+                .failed_move_fall_back_to_copy(false),
+        );
+        assert!(ret.is_err());
+    }
+
+    let ret = db.ingest_external_file(
+        &[write_sst("B")],
+        &IngestExternalFileOptions::default()
+            .move_files(true)
+            .failed_move_fall_back_to_copy(true),
+    );
+    assert!(ret.is_ok(), "ingest external file: {:?}", ret);
+    assert_eq!(db.get(&ReadOptions::default(), b"B00042").unwrap(), b"value");
+}
+
+#[test]
+fn ingest_sst_file_fast_move() {
+    use rocks::sst_file_writer::SstFileWriter;
+    use std::os::unix::fs::MetadataExt;
+    use std::thread;
+    use std::time::Duration;
+
+    // SST staged inside the DB's filesystem, so it can be hard-linked
+    let sst_dir = ::tempdir::TempDir::new_in(".", "rocks.sst").unwrap();
+    let tmp_db_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_db_dir,
+    )
+    .unwrap();
+    // overlapping key, so that the ingested file gets a non-zero global seqno
+    assert!(db.put(&WriteOptions::default(), b"K00042", b"old").is_ok());
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    let path = sst_dir.path().join("fast.sst");
+    let writer = SstFileWriter::builder().build();
+    writer.open(&path).unwrap();
+    for i in 0..1000 {
+        let key = format!("K{:05}", i);
+        writer.put(key.as_bytes(), b"new").unwrap();
+    }
+    writer.finish().unwrap();
+
+    let meta = path.metadata().unwrap();
+    // make any rewrite of the file observable through its mtime
+    thread::sleep(Duration::from_millis(50));
+
+    let ret = db.ingest_external_file(&[&path], &IngestExternalFileOptions::fast_move());
+    assert!(ret.is_ok(), "ingest external file: {:?}", ret);
+    // original link is removed after a successful move
+    assert!(!path.exists());
+
+    let ingested = tmp_db_dir
+        .path()
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|p| p.metadata().unwrap().ino() == meta.ino())
+        .expect("ingested file is hard-linked into the DB");
+    let ingested_meta = ingested.metadata().unwrap();
+    assert_eq!(ingested_meta.len(), meta.len());
+    assert_eq!(ingested_meta.modified().unwrap(), meta.modified().unwrap());
+
+    assert_eq!(db.get(&ReadOptions::default(), b"K00042").unwrap(), b"new");
+    assert_eq!(db.get(&ReadOptions::default(), b"K00999").unwrap(), b"new");
+}
+
 #[test]
 fn compact_range() {
     let s = b"123123123";
@@ -269,6 +459,49 @@ fn multi_get() {
     assert!(ret[5].as_ref().unwrap_err().is_not_found());
 }
 
+#[test]
+fn batched_multi_get_with_sorted_input() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.default_column_family();
+
+    for i in 0..100 {
+        if i % 7 == 3 {
+            continue;
+        }
+        let key = format!("key-{:03}", i);
+        let value = format!("{}", i);
+        assert!(db.put(&Default::default(), key.as_bytes(), value.as_bytes()).is_ok());
+        // spread the keys over memtable and several table files
+        if i % 30 == 29 {
+            assert!(db.flush(&Default::default()).is_ok());
+        }
+    }
+
+    let keys = (0..100).map(|i| format!("key-{:03}", i)).collect::<Vec<_>>();
+    let keys = keys.iter().map(|k| k.as_bytes()).collect::<Vec<_>>();
+
+    let unsorted = db.batched_multi_get_cf(&ReadOptions::default(), &cf, &keys, false);
+    let sorted = db.batched_multi_get_cf(&ReadOptions::default(), &cf, &keys, true);
+    let plain = db.multi_get(&ReadOptions::default(), &keys);
+    assert_eq!(sorted.len(), 100);
+    for i in 0..100 {
+        if i % 7 == 3 {
+            assert!(sorted[i].as_ref().unwrap_err().is_not_found());
+            assert!(unsorted[i].as_ref().unwrap_err().is_not_found());
+        } else {
+            let expected = format!("{}", i);
+            assert_eq!(sorted[i].as_ref().unwrap(), expected.as_bytes());
+            assert_eq!(unsorted[i].as_ref().unwrap(), expected.as_bytes());
+            assert_eq!(plain[i].as_ref().unwrap(), expected.as_bytes());
+        }
+    }
+}
+
 #[test]
 fn multi_get_cf() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -316,6 +549,41 @@ fn multi_get_cf() {
     // mem::forget(def);
 }
 
+#[test]
+fn multi_get_cf_with_snapshot() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let def = db.default_column_family();
+    let cf1 = db.create_column_family(&Default::default(), "cf1").unwrap();
+
+    assert!(def.put(&WriteOptions::default(), b"row1", b"name-old").is_ok());
+    assert!(cf1.put(&WriteOptions::default(), b"row1", b"email-old").is_ok());
+
+    let snap = db.get_snapshot();
+
+    assert!(def.put(&WriteOptions::default(), b"row1", b"name-new").is_ok());
+    assert!(cf1.put(&WriteOptions::default(), b"row1", b"email-new").is_ok());
+    assert!(cf1.put(&WriteOptions::default(), b"row2", b"email").is_ok());
+
+    let ret = db.multi_get_cf(
+        &ReadOptions::default().snapshot(snap.as_ref()),
+        &[&def, &cf1, &cf1],
+        &[b"row1", b"row1", b"row2"],
+    );
+    assert_eq!(ret[0].as_ref().unwrap(), b"name-old".as_ref());
+    assert_eq!(ret[1].as_ref().unwrap(), b"email-old".as_ref());
+    assert!(ret[2].as_ref().unwrap_err().is_not_found());
+
+    let ret = db.multi_get_cf(&ReadOptions::default(), &[&def, &cf1], &[b"row1", b"row1"]);
+    assert_eq!(ret[0].as_ref().unwrap(), b"name-new".as_ref());
+    assert_eq!(ret[1].as_ref().unwrap(), b"email-new".as_ref());
+}
+
 #[test]
 fn db_paths() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -428,6 +696,73 @@ fn get_prop() {
     db.release_snapshot(snap.unwrap());
 }
 
+#[test]
+fn aggregated_int_property() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let cf1 = db.create_column_family(&Default::default(), "cf1").unwrap();
+    let cf2 = db.create_column_family(&Default::default(), "cf2").unwrap();
+
+    assert!(db.put(&Default::default(), b"key", &vec![b'A'; 100 * 1024]).is_ok());
+    assert!(cf1.put(&Default::default(), b"key", &vec![b'B'; 300 * 1024]).is_ok());
+    assert!(cf2.put(&Default::default(), b"key", &vec![b'C'; 500 * 1024]).is_ok());
+
+    for prop in &["rocksdb.size-all-mem-tables", "rocksdb.num-entries-active-mem-table"] {
+        let sum = db.get_int_property(prop).unwrap()
+            + cf1.get_int_property(prop).unwrap()
+            + cf2.get_int_property(prop).unwrap();
+        assert_eq!(db.get_aggregated_int_property(prop), Some(sum));
+    }
+    assert_eq!(
+        db.get_aggregated_int_property("rocksdb.num-entries-active-mem-table"),
+        Some(3)
+    );
+    assert_eq!(db.get_aggregated_int_property("rocksdb.no-such-property"), None);
+}
+
+#[test]
+fn get_map_property() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf1 = db.create_column_family(&Default::default(), "cf1").unwrap();
+
+    for i in 0..100 {
+        let key = format!("k{:03}", i);
+        assert!(db.put(&Default::default(), key.as_bytes(), b"value").is_ok());
+        assert!(cf1.put(&Default::default(), key.as_bytes(), b"value").is_ok());
+    }
+    assert!(db.flush(&Default::default()).is_ok());
+
+    // only some properties have a map form, the text-only ones are not found
+    assert!(db.get_property("rocksdb.cfstats-no-file-histogram").is_some());
+    assert!(db.get_map_property("rocksdb.cfstats-no-file-histogram").is_none());
+
+    let stats = db.get_map_property("rocksdb.cfstats").unwrap();
+    assert!(stats.contains_key("io_stalls.total_stop"), "stats: {:?}", stats);
+    assert!(stats.keys().any(|k| k.starts_with("compaction.")));
+
+    let stats = db.get_map_property_cf(&cf1, "rocksdb.cfstats").unwrap();
+    assert!(stats.contains_key("io_stalls.total_stop"));
+    assert_eq!(
+        cf1.get_map_property("rocksdb.cfstats").map(|m| m.len()),
+        Some(stats.len())
+    );
+
+    let props = db.get_map_property("rocksdb.aggregated-table-properties").unwrap();
+    assert_eq!(props["num_entries"], "100");
+
+    assert!(db.get_map_property("rocksdb.no-such-property").is_none());
+}
+
 #[test]
 fn misc_functions() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -461,6 +796,23 @@ fn misc_functions() {
     println!("id => {:?}", db.get_db_identity());
 }
 
+#[test]
+fn db_identity_across_reopen() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+
+    let (identity, session_id) = {
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        (db.get_db_identity().unwrap(), db.get_db_session_id().unwrap())
+    };
+    assert!(!identity.is_empty());
+    assert!(!session_id.is_empty());
+
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+    assert_eq!(db.get_db_identity().unwrap(), identity);
+    assert_ne!(db.get_db_session_id().unwrap(), session_id);
+}
+
 #[test]
 fn flush() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -488,6 +840,56 @@ fn flush() {
     assert_eq!(*db.get_latest_sequence_number(), 4);
 }
 
+#[test]
+fn write_returns_sequence_number() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let mut last = db.get_latest_sequence_number();
+    for i in 0..10 {
+        let key = format!("key{}", i);
+        let seq = db.put(&WriteOptions::default(), key.as_bytes(), b"value").unwrap();
+        assert!(*seq > *last, "{:?} after {:?}", seq, last);
+        assert_eq!(seq, db.get_latest_sequence_number());
+        last = seq;
+    }
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"a", b"1").put(b"b", b"2").delete(b"key0");
+    let seq = db.write(&WriteOptions::default(), &batch).unwrap();
+    assert_eq!(*seq, *last + 1);
+    assert_eq!(*db.get_latest_sequence_number(), *seq + 2);
+
+    let seq = db.put(&WriteOptions::default(), b"c", b"3").unwrap();
+    assert_eq!(*seq, *last + 4);
+
+    // an empty batch consumes no sequence number
+    let seq = db.write(&WriteOptions::default(), &WriteBatch::new()).unwrap();
+    assert_eq!(*seq, *last + 4);
+}
+
+#[test]
+fn write_returns_sequence_number_with_ttl() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+    // the TTL wrapper writes a copy of each batch
+    let db = DB::open_with_ttl(&opt, &tmp_dir, 3600).unwrap();
+
+    let first = db.put(&WriteOptions::default(), b"a", b"1").unwrap();
+    assert!(*first > 0);
+    assert_eq!(first, db.get_latest_sequence_number());
+
+    let mut batch = WriteBatch::new();
+    batch.put(b"b", b"2").put(b"c", b"3");
+    let seq = db.write(&WriteOptions::default(), &batch).unwrap();
+    assert_eq!(*seq, *first + 1);
+    assert_eq!(*db.get_latest_sequence_number(), *seq + 1);
+}
+
 #[test]
 fn livemetadata() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -526,6 +928,52 @@ fn livemetadata() {
     assert_eq!(meta[0].level, 1);
 }
 
+#[test]
+fn live_files_metadata_fields() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.create_column_family(&Default::default(), "meta").unwrap();
+
+    for key in &[b"a", b"b", b"c"] {
+        assert!(db.put(&Default::default(), *key, b"value").is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    // flushed, then moved down by the manual compaction
+    assert!(cf.put(&Default::default(), b"x", b"value").is_ok());
+    assert!(cf.put(&Default::default(), b"y", b"value").is_ok());
+    assert!(cf
+        .compact_range(&CompactRangeOptions::default().change_level(true).target_level(3), ..)
+        .is_ok());
+
+    let mut meta = db.get_live_files_metadata();
+    meta.sort_by(|a, b| a.column_family_name.cmp(&b.column_family_name));
+    assert_eq!(meta.len(), 2);
+
+    assert_eq!(meta[0].column_family_name, "default");
+    assert_eq!(meta[0].level, 0);
+    assert_eq!(meta[0].smallestkey, b"a");
+    assert_eq!(meta[0].largestkey, b"c");
+    assert_eq!(*meta[0].smallest_seqno, 1);
+    assert_eq!(*meta[0].largest_seqno, 3);
+
+    assert_eq!(meta[1].column_family_name, "meta");
+    assert_eq!(meta[1].level, 3);
+    assert_eq!(meta[1].smallestkey, b"x");
+    assert_eq!(meta[1].largestkey, b"y");
+
+    for file in &meta {
+        assert!(!file.being_compacted);
+        let path = std::path::Path::new(&file.db_path).join(file.name.trim_start_matches('/'));
+        assert_eq!(file.size, path.metadata().unwrap().len());
+    }
+}
+
 #[test]
 fn column_family_meta() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -651,6 +1099,86 @@ fn change_options() {
     assert!(format!("{:?}", ret).contains("Unrecognized option"));
 }
 
+#[test]
+fn no_slowdown_write_stall() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| {
+                cf.disable_auto_compactions(true)
+                    .level0_file_num_compaction_trigger(2)
+                    .level0_slowdown_writes_trigger(2)
+                    .level0_stop_writes_trigger(3)
+            }),
+        &tmp_dir,
+    )
+    .unwrap();
+    let no_slowdown = WriteOptions::default().no_slowdown(true);
+
+    for i in 0..3 {
+        let key = format!("key{}", i);
+        assert!(db.put(&no_slowdown, key.as_bytes(), b"value").is_ok());
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    }
+
+    // L0 stall triggers only apply with auto compactions, keep those from
+    // running and resolving the stall
+    assert!(db.pause_background_work().is_ok());
+    assert!(db
+        .set_options(&db.default_column_family(), vec![("disable_auto_compactions", "false")])
+        .is_ok());
+
+    let ret = db.put(&no_slowdown, b"stalled", b"value");
+    let err = ret.unwrap_err();
+    assert_eq!(err.code(), rocks::error::Code::Incomplete);
+    assert!(err.is_incomplete());
+    assert!(db.get(&ReadOptions::default(), b"stalled").unwrap_err().is_not_found());
+
+    assert!(db.continue_background_work().is_ok());
+    assert!(db.put(&WriteOptions::default(), b"stalled", b"value").is_ok());
+}
+
+#[test]
+fn set_bytes_per_sync_dynamically() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true).bytes_per_sync(0)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let new_opt: HashMap<&str, &str> = [("bytes_per_sync", "1048576"), ("wal_bytes_per_sync", "524288")]
+        .iter()
+        .cloned()
+        .collect();
+    assert!(db.set_db_options(&new_opt).is_ok());
+
+    // the new values are persisted to the latest OPTIONS file
+    let options_file = tmp_dir
+        .path()
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().unwrap().to_str().unwrap().starts_with("OPTIONS-"))
+        .max()
+        .unwrap();
+    let content = ::std::fs::read_to_string(options_file).unwrap();
+    assert!(content.contains("  bytes_per_sync=1048576\n"));
+    assert!(content.contains("  wal_bytes_per_sync=524288\n"));
+
+    // subsequent writes and flushes work with the new values
+    for i in 0..1000 {
+        let key = format!("k{:04}", i);
+        assert!(db.put(&Default::default(), key.as_bytes(), &[b'A'; 1024]).is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    assert_eq!(db.get(&ReadOptions::default(), b"k0999").unwrap(), &[b'A'; 1024][..]);
+
+    let new_opt: HashMap<&str, &str> = [("bytes_per_sync", "not-a-number")].iter().cloned().collect();
+    assert!(db.set_db_options(&new_opt).is_err());
+}
+
 #[test]
 fn approximate_sizes() {
     let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
@@ -791,6 +1319,44 @@ fn get_properties_of_all_tables() {
     assert!(vals.len() > 4);
 }
 
+#[test]
+fn get_properties_of_tables_in_range() {
+    let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    // one table file for each key prefix
+    for (prefix, n) in &[("a", 10), ("z", 20)] {
+        for i in 0..*n {
+            let key = format!("{}{:02}", prefix, i);
+            db.put(WriteOptions::default_instance(), key.as_bytes(), b"value")
+                .unwrap();
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    }
+
+    let all = db.get_properties_of_all_tables_cf(&db.default_column_family()).unwrap();
+    assert_eq!(all.len(), 2);
+
+    let props = db
+        .get_properties_of_tables_in_range(&db.default_column_family(), &[b"z".as_ref()..b"zz".as_ref()])
+        .unwrap();
+    assert_eq!(props.len(), 1);
+    let (file, prop) = props.iter().next().unwrap();
+    assert!(file.ends_with(".sst"));
+    assert_eq!(prop.num_entries(), 20);
+
+    let props = db
+        .get_properties_of_tables_in_range(&db.default_column_family(), &[b"b".as_ref()..b"c".as_ref()])
+        .unwrap();
+    assert!(props.is_empty());
+}
+
 #[test]
 fn delete_files_in_range() {
     let tmp_dir = ::tempdir::TempDir::new_in("", "rocks").unwrap();
@@ -827,3 +1393,1313 @@ fn delete_files_in_range() {
         assert!(old_files.contains(f));
     }
 }
+
+#[test]
+fn follow_primary() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let secondary_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+    let primary = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    assert!(primary.put(&Default::default(), b"k0", b"v0").is_ok());
+
+    let secondary = DB::open_as_secondary(
+        &Options::default().map_db_options(|db| db.max_open_files(-1)),
+        &tmp_dir,
+        &secondary_dir,
+    )
+    .unwrap();
+    assert_eq!(secondary.get(&ReadOptions::default(), b"k0").unwrap(), b"v0");
+
+    let secondary = Arc::new(secondary);
+    let stop = Arc::new(AtomicBool::new(false));
+    let follower = {
+        let secondary = secondary.clone();
+        let stop = stop.clone();
+        thread::spawn(move || secondary.follow_primary(Duration::from_millis(10), &stop).is_ok())
+    };
+
+    for i in 1..100 {
+        let key = format!("k{}", i);
+        assert!(primary.put(&Default::default(), key.as_bytes(), b"new").is_ok());
+    }
+
+    let mut seen = false;
+    for _ in 0..500 {
+        if secondary.get(&ReadOptions::default(), b"k99").is_ok() {
+            seen = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    stop.store(true, Ordering::Release);
+    assert!(follower.join().unwrap());
+    assert!(seen, "secondary should see keys written by primary");
+}
+
+#[test]
+fn catch_up_with_primary_reports_changed_column_families() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let secondary_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+
+    let primary = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf_a = primary.create_column_family(&Default::default(), "a").unwrap();
+    let cf_b = primary.create_column_family(&Default::default(), "b").unwrap();
+    assert!(cf_a.put(&WriteOptions::default(), b"k0", b"a0").is_ok());
+    assert!(cf_b.put(&WriteOptions::default(), b"k0", b"b0").is_ok());
+
+    let (secondary, cfs) = DB::open_as_secondary_with_column_families(
+        &DBOptions::default().max_open_files(-1),
+        &tmp_dir,
+        &secondary_dir,
+        vec!["default", "a", "b"],
+    )
+    .unwrap();
+    assert_eq!(cfs[2].get(&ReadOptions::default(), b"k0").unwrap(), b"b0");
+    let handles = [&*cfs[0], &*cfs[1], &*cfs[2]];
+
+    // nothing new on the primary
+    let report = secondary.try_catch_up_with_primary_cf(&handles).unwrap();
+    assert!(!report.has_new_writes());
+    assert!(report.changed_column_families.is_empty());
+
+    for i in 1..10 {
+        let key = format!("k{}", i);
+        assert!(cf_a.put(&WriteOptions::default(), key.as_bytes(), b"new").is_ok());
+    }
+
+    let report = secondary.try_catch_up_with_primary_cf(&handles).unwrap();
+    assert!(report.has_new_writes());
+    assert_eq!(report.sequence_after, primary.get_latest_sequence_number());
+    assert_eq!(report.changed_column_families, vec!["a".to_owned()]);
+    assert!(report.is_changed("a"));
+    assert!(!report.is_changed("b"));
+    assert_eq!(cfs[1].get(&ReadOptions::default(), b"k9").unwrap(), b"new");
+}
+
+#[test]
+fn read_deadline_exceeded() {
+    use rocks::error::Code;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let env: &'static Env = Box::leak(Box::new(Env::new_fault_injection(Env::default())));
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true).env(env))
+            .map_cf_options(|cf| {
+                cf.disable_auto_compactions(true)
+                    .table_factory_block_based(BlockBasedTableOptions::default().no_block_cache(true))
+            }),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    // the key only lives in the oldest L0 file, every newer file overlaps it
+    // and has to be read first
+    assert!(db.put(&WriteOptions::default(), b"k0500", b"value").is_ok());
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+    for _ in 0..4 {
+        assert!(db.put(&WriteOptions::default(), b"k0000", b"other").is_ok());
+        assert!(db.put(&WriteOptions::default(), b"k0999", b"other").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+    }
+    assert_eq!(db.get_property("rocksdb.num-files-at-level0").unwrap(), "5");
+
+    let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    env.set_read_delay(Duration::from_millis(100));
+
+    let ret = db.get(
+        &ReadOptions::default()
+            .deadline(Some(now() + Duration::from_secs(60)))
+            .io_timeout(Some(Duration::from_secs(60))),
+        b"k0500",
+    );
+    assert_eq!(ret.unwrap().as_ref(), b"value");
+
+    // the first file read alone takes longer than the deadline
+    let ret = db.get(
+        &ReadOptions::default().deadline(Some(now() + Duration::from_millis(50))),
+        b"k0500",
+    );
+    assert!(ret.is_err());
+    assert_eq!(ret.unwrap_err().code(), Code::TimedOut);
+}
+
+#[test]
+fn async_io_scan() {
+    // same results whether or not the linked RocksDB reads asynchronously
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.write_buffer_size(64 << 10)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    for i in 0..10000 {
+        let key = format!("k{:05}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), key.as_bytes()).is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    let it = db.new_iterator(&ReadOptions::default().async_io(true).readahead_size(256 << 10));
+    let mut n = 0;
+    for (i, (key, value)) in it.enumerate() {
+        assert_eq!(key, format!("k{:05}", i).as_bytes());
+        assert_eq!(key, value);
+        n += 1;
+    }
+    assert_eq!(n, 10000);
+
+    let keys: Vec<String> = (0..10000).step_by(97).map(|i| format!("k{:05}", i)).collect();
+    let keys: Vec<&[u8]> = keys.iter().map(|k| k.as_bytes()).collect();
+    let values = db.multi_get(&ReadOptions::default().async_io(true), &keys);
+    assert_eq!(values.len(), keys.len());
+    for (key, value) in keys.iter().zip(values) {
+        assert_eq!(value.unwrap().as_ref(), *key);
+    }
+}
+
+#[test]
+fn get_raw() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let value = vec![b'x'; 4096];
+    for i in 0..1000 {
+        let key = format!("k{:04}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    for i in 0..1000 {
+        let key = format!("k{:04}", i);
+        let val = db.get_raw(&ReadOptions::default(), key.as_bytes()).unwrap();
+        assert_eq!(val.as_ref(), &value[..]);
+    }
+
+    let ret = db.get_raw(&ReadOptions::default(), b"not-exist");
+    assert!(ret.is_err());
+    assert!(ret.unwrap_err().is_not_found());
+}
+
+#[test]
+fn verify_checksum() {
+    use rocks::error::Code;
+    use std::fs::OpenOptions;
+    use std::io::{Seek, SeekFrom, Write};
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default()
+        .map_db_options(|db| db.create_if_missing(true))
+        .map_cf_options(|cf| cf.disable_auto_compactions(true));
+
+    {
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        for i in 0..1000 {
+            let key = format!("k{:04}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+        assert!(db.verify_checksum(&ReadOptions::default()).is_ok());
+    }
+
+    // flip some bytes inside the first data block of every sst file
+    for entry in tmp_dir.path().read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "sst") {
+            let mut f = OpenOptions::new().write(true).open(&path).unwrap();
+            f.seek(SeekFrom::Start(64)).unwrap();
+            f.write_all(b"corrupted").unwrap();
+        }
+    }
+
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+    let ret = db.verify_checksum(&ReadOptions::default());
+    assert!(ret.is_err());
+    assert_eq!(ret.unwrap_err().code(), Code::Corruption);
+}
+
+#[test]
+fn blob_files() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| {
+                cf.enable_blob_files(true)
+                    .min_blob_size(1024)
+                    .blob_file_size(1 << 20)
+                    .blob_compression_type(CompressionType::NoCompression)
+                    .enable_blob_garbage_collection(true)
+            }),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let small = vec![b's'; 100];
+    let large = vec![b'l'; 4096];
+    for i in 0..100 {
+        let key = format!("k{:03}", i);
+        let value = if i % 2 == 0 { &small } else { &large };
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), value).is_ok());
+    }
+    assert_eq!(db.get_int_property("rocksdb.num-blob-files"), Some(0));
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    assert!(db.get_int_property("rocksdb.num-blob-files").unwrap() > 0);
+    assert!(db.get_int_property("rocksdb.total-blob-file-size").unwrap() >= 50 * 4096);
+    assert!(tmp_dir.path().read_dir().unwrap().any(|entry| entry
+        .unwrap()
+        .path()
+        .extension()
+        .is_some_and(|ext| ext == "blob")));
+
+    assert_eq!(db.get(&ReadOptions::default(), b"k000").unwrap(), &small[..]);
+    assert_eq!(db.get(&ReadOptions::default(), b"k001").unwrap(), &large[..]);
+}
+
+#[test]
+fn best_efforts_recovery() {
+    use std::fs;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default()
+        .map_db_options(|db| db.create_if_missing(true))
+        .map_cf_options(|cf| cf.disable_auto_compactions(true));
+
+    let mut table_files = vec![];
+    {
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        for batch in &["a", "b"] {
+            for i in 0..100 {
+                let key = format!("{}{:03}", batch, i);
+                assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+            }
+            assert!(db.flush(&FlushOptions::default()).is_ok());
+            table_files = db
+                .get_live_files(false)
+                .unwrap()
+                .1
+                .into_iter()
+                .filter(|f| f.ends_with(".sst"))
+                .collect();
+        }
+    }
+    assert_eq!(table_files.len(), 2);
+
+    // damage the DB: lose CURRENT and the newest table file
+    fs::remove_file(tmp_dir.path().join("CURRENT")).unwrap();
+    table_files.sort();
+    fs::remove_file(tmp_dir.path().join(table_files[1].trim_start_matches('/'))).unwrap();
+
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(false));
+    assert!(DB::open(&opt, &tmp_dir).is_err());
+
+    let opt = opt.map_db_options(|db| db.best_efforts_recovery(true));
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+    assert_eq!(db.get(&ReadOptions::default(), b"a042").unwrap(), &b"value"[..]);
+}
+
+#[test]
+fn track_and_verify_wals_in_manifest() {
+    use std::fs;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    // recovery must not flush, that would make the old WALs obsolete
+    let db_opt = DBOptions::default()
+        .track_and_verify_wals_in_manifest(true)
+        .avoid_flush_during_recovery(true);
+    let write_opt = WriteOptions::default().sync(true);
+    {
+        let opt =
+            Options::default().map_db_options(|db| db.create_if_missing(true).track_and_verify_wals_in_manifest(true));
+        let db = DB::open(&opt, &tmp_dir).unwrap();
+        let cf = db
+            .create_column_family(&ColumnFamilyOptions::default(), "side")
+            .unwrap();
+        assert!(cf.put(&write_opt, b"side-key", b"value").is_ok());
+        assert!(db.put(&write_opt, b"key", b"value").is_ok());
+        // switches to a new WAL, the old one is still needed by "side"
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+        assert!(db.put(&write_opt, b"key2", b"value").is_ok());
+    }
+
+    // normal reopen
+    {
+        let (db, cfs) = DB::open_with_column_families(&db_opt, &tmp_dir, vec!["default", "side"]).unwrap();
+        assert_eq!(cfs[1].get(&ReadOptions::default(), b"side-key").unwrap(), b"value");
+        assert_eq!(db.get(&ReadOptions::default(), b"key2").unwrap(), b"value");
+    }
+
+    let mut wals = tmp_dir
+        .path()
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .collect::<Vec<_>>();
+    wals.sort();
+    assert!(wals.len() >= 2, "wals => {:?}", wals);
+    fs::remove_file(&wals[0]).unwrap();
+
+    // the missing WAL is detected instead of silently losing "side-key"
+    let ret = DB::open_with_column_families(&db_opt, &tmp_dir, vec!["default", "side"]);
+    assert!(ret.is_err());
+}
+
+#[test]
+fn promote_l0() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    // each flush creates an L0 file, key ranges do not overlap
+    for prefix in &["a", "b", "c", "d"] {
+        for i in 0..100 {
+            let key = format!("{}{:03}", prefix, i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+    }
+    assert_eq!(db.get_property("rocksdb.num-files-at-level0"), Some("4".to_string()));
+
+    let cf = db.default_column_family();
+    assert!(db.promote_l0(&cf, 3).is_ok());
+    assert_eq!(db.get_property("rocksdb.num-files-at-level0"), Some("0".to_string()));
+    assert_eq!(db.get_property("rocksdb.num-files-at-level3"), Some("4".to_string()));
+    assert_eq!(db.get(&ReadOptions::default(), b"c042").unwrap(), &b"value"[..]);
+
+    // overlapping L0 files can not be promoted
+    for _ in 0..2 {
+        assert!(db.put(&WriteOptions::default(), b"b050", b"new").is_ok());
+        assert!(db.flush(&FlushOptions::default()).is_ok());
+    }
+    assert!(db.promote_l0(&cf, 1).is_err());
+}
+
+#[test]
+fn suggest_compact_range() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    for i in 0..1000 {
+        let key = format!("k{:04}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+    for i in 0..900 {
+        let key = format!("k{:04}", i);
+        assert!(db.delete(&WriteOptions::default(), key.as_bytes()).is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    let cf = db.default_column_family();
+    assert!(db.suggest_compact_range(&cf, Some(b"k0000"), Some(b"k0899")).is_ok());
+    assert!(db.suggest_compact_range(&cf, None, None).is_ok());
+
+    assert!(db.get(&ReadOptions::default(), b"k0042").is_err());
+    assert_eq!(db.get(&ReadOptions::default(), b"k0942").unwrap(), &b"value"[..]);
+}
+
+#[test]
+fn open_with_ttl() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+    let db = DB::open_with_ttl(&opt, &tmp_dir, 1).unwrap();
+
+    for i in 0..100 {
+        let key = format!("k{:03}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+    }
+    // timestamps are stripped on read
+    assert_eq!(db.get(&ReadOptions::default(), b"k042").unwrap(), &b"value"[..]);
+
+    ::std::thread::sleep(::std::time::Duration::from_secs(2));
+    assert!(db.compact_range(&CompactRangeOptions::default(), ..).is_ok());
+    assert!(db.get(&ReadOptions::default(), b"k042").unwrap_err().is_not_found());
+}
+
+#[test]
+fn open_with_column_families_and_ttl() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = DBOptions::default()
+        .create_if_missing(true)
+        .create_missing_column_families(true);
+    let (db, cfs) = DB::open_with_column_families_and_ttl(
+        &opt,
+        &tmp_dir,
+        vec![
+            (
+                ColumnFamilyDescriptor::new(DEFAULT_COLUMN_FAMILY_NAME, ColumnFamilyOptions::default()),
+                0,
+            ),
+            (ColumnFamilyDescriptor::new("short", ColumnFamilyOptions::default()), 1),
+        ],
+    )
+    .unwrap();
+
+    for cf in &cfs {
+        assert!(cf.put(&WriteOptions::default(), b"key", b"value").is_ok());
+    }
+
+    ::std::thread::sleep(::std::time::Duration::from_secs(2));
+    for cf in &cfs {
+        assert!(cf.compact_range(&CompactRangeOptions::default(), ..).is_ok());
+    }
+    assert_eq!(cfs[0].get(&ReadOptions::default(), b"key").unwrap(), &b"value"[..]);
+    assert!(cfs[1].get(&ReadOptions::default(), b"key").unwrap_err().is_not_found());
+    assert!(db.get(&ReadOptions::default(), b"key").is_ok());
+}
+
+#[test]
+fn estimate_num_keys_and_deletes() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.default_column_family();
+
+    for i in 0..1000 {
+        let key = format!("k{:04}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+    assert_eq!(db.estimate_num_keys(&cf), 1000);
+    assert_eq!(db.estimate_num_deletes(&cf), Ok(0));
+
+    for i in 0..300 {
+        let key = format!("k{:04}", i);
+        assert!(db.delete(&WriteOptions::default(), key.as_bytes()).is_ok());
+    }
+    assert_eq!(db.estimate_num_deletes(&cf), Ok(300));
+    assert!(db.estimate_num_keys(&cf) < 1000);
+
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+    assert_eq!(db.estimate_num_deletes(&cf), Ok(300));
+    assert!(db.estimate_num_keys(&cf) < 1000);
+
+    // compaction drops the tombstones along with the deleted keys
+    assert!(db.compact_range(&CompactRangeOptions::default(), ..).is_ok());
+    assert_eq!(db.estimate_num_deletes(&cf), Ok(0));
+    assert_eq!(db.estimate_num_keys(&cf), 700);
+}
+
+#[test]
+fn close_db() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(true));
+
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+    // not in the WAL, only persisted by the flush on close
+    assert!(db
+        .put(&WriteOptions::default().disable_wal(true), b"key", b"value")
+        .is_ok());
+    assert!(db.close().is_ok());
+
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+    assert_eq!(db.get(&ReadOptions::default(), b"key").unwrap(), &b"value"[..]);
+
+    // a handle keeps the DB open, and usable
+    let cf = db.default_column_family();
+    assert_eq!(db.close().unwrap_err().code(), rocks::error::Code::Busy);
+    assert_eq!(cf.get(&ReadOptions::default(), b"key").unwrap(), &b"value"[..]);
+}
+
+#[test]
+fn open_and_report_recovery() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = Options::default().map_db_options(|db| {
+        db.create_if_missing(true)
+            .wal_recovery_mode(WALRecoveryMode::PointInTimeRecovery)
+    });
+
+    {
+        let (db, report) = DB::open_and_report_recovery(&opt, &tmp_dir).unwrap();
+        assert_eq!(report, RecoveryReport::default());
+        let value = vec![b'x'; 1000];
+        for i in 0..100 {
+            let key = format!("key-{:03}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+        }
+        // dropped without a flush, every write is only in the WAL
+    }
+
+    // corrupt some bytes in the middle of the WAL
+    let wal = tmp_dir
+        .path()
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "log"))
+        .unwrap();
+    let mut content = std::fs::read(&wal).unwrap();
+    let mid = content.len() / 2;
+    for b in &mut content[mid..mid + 64] {
+        *b = 0xab;
+    }
+    std::fs::write(&wal, content).unwrap();
+
+    let (db, report) = DB::open_and_report_recovery(&opt, &tmp_dir).unwrap();
+    assert!(report.has_skipped_records());
+    assert!(report.records_replayed > 0);
+    assert!(report.records_replayed < 100);
+    assert!(db.get(&ReadOptions::default(), b"key-000").is_ok());
+    assert!(db.get(&ReadOptions::default(), b"key-099").unwrap_err().is_not_found());
+}
+
+#[test]
+fn get_sanitized_options() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.write_buffer_size(4 << 20).arena_block_size(0)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    let cf_opts = format!("{:?}", db.get_options(&db.default_column_family()));
+    let arena_block_size = cf_opts
+        .split(';')
+        .find_map(|kv| kv.trim().strip_prefix("arena_block_size="))
+        .unwrap();
+    assert_ne!(arena_block_size.parse::<u64>().unwrap(), 0);
+
+    let db_opts = format!("{:?}", db.get_db_options());
+    assert!(db_opts.contains("create_if_missing=true"));
+}
+
+#[test]
+fn partitioned_filters_and_two_level_index() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let table_opt = BlockBasedTableOptions::default()
+        .format_version(5)
+        .block_size(1024)
+        .metadata_block_size(1024)
+        .index_type(IndexType::TwoLevelIndexSearch)
+        .partition_filters(true)
+        .filter_policy(Some(FilterPolicy::new_bloom_filter(10, false)))
+        .cache_index_and_filter_blocks(true)
+        .pin_l0_filter_and_index_blocks_in_cache(true)
+        .data_block_index_type(DataBlockIndexType::BinarySearchAndHash);
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.table_factory_block_based(table_opt)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    for i in 0..10000 {
+        let key = format!("key-{:05}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), b"value").is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default()).is_ok());
+
+    for i in 0..10000 {
+        let key = format!("key-{:05}", i);
+        assert_eq!(db.get(&ReadOptions::default(), key.as_bytes()).unwrap(), b"value");
+    }
+    assert!(db
+        .get(&ReadOptions::default(), b"key-99999")
+        .unwrap_err()
+        .is_not_found());
+
+    let props = db.get_properties_of_all_tables_cf(&db.default_column_family()).unwrap();
+    for (_, prop) in props.iter() {
+        assert_eq!(prop.format_version(), 5);
+        assert_eq!(prop.filter_policy_name(), Some("rocksdb.BuiltinBloomFilter"));
+    }
+}
+
+#[test]
+fn ingest_external_files_into_column_families() {
+    use rocks::sst_file_writer::SstFileWriter;
+
+    let sst_dir = ::tempdir::TempDir::new_in(".", "rocks.sst").unwrap();
+    for name in &["a", "b"] {
+        let writer = SstFileWriter::builder().build();
+        writer.open(sst_dir.path().join(format!("{}.sst", name))).unwrap();
+        for i in 0..100 {
+            let key = format!("{}{:03}", name, i);
+            writer.put(key.as_bytes(), b"value").unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let (db, cfs) = DB::open_with_column_families(
+        &DBOptions::default()
+            .create_if_missing(true)
+            .create_missing_column_families(true),
+        &tmp_dir,
+        vec![
+            ColumnFamilyDescriptor::from(DEFAULT_COLUMN_FAMILY_NAME),
+            ColumnFamilyDescriptor::from("a"),
+            ColumnFamilyDescriptor::from("b"),
+        ],
+    )
+    .unwrap();
+
+    // a missing file fails the whole ingestion, nothing lands in "a"
+    let ret = db.ingest_external_files(
+        &[
+            IngestExternalFileArg::new(&cfs[1], &[sst_dir.path().join("a.sst")]),
+            IngestExternalFileArg::new(&cfs[2], &[sst_dir.path().join("missing.sst")]),
+        ],
+        &IngestExternalFileOptions::default(),
+    );
+    assert!(ret.is_err());
+    assert!(cfs[1].get(&ReadOptions::default(), b"a000").unwrap_err().is_not_found());
+
+    let ret = db.ingest_external_files(
+        &[
+            IngestExternalFileArg::new(&cfs[1], &[sst_dir.path().join("a.sst")]),
+            IngestExternalFileArg::new(&cfs[2], &[sst_dir.path().join("b.sst")]),
+        ],
+        &IngestExternalFileOptions::default(),
+    );
+    assert!(ret.is_ok(), "ingest external files: {:?}", ret);
+    assert_eq!(cfs[1].get(&ReadOptions::default(), b"a099").unwrap(), b"value");
+    assert_eq!(cfs[2].get(&ReadOptions::default(), b"b000").unwrap(), b"value");
+    assert!(cfs[1].get(&ReadOptions::default(), b"b000").unwrap_err().is_not_found());
+}
+
+#[test]
+fn db_write_buffer_size_triggers_flush() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let cf_opt = || {
+        ColumnFamilyOptions::default()
+            .write_buffer_size(16 << 20)
+            .arena_block_size(64 << 10)
+    };
+    let opt = DBOptions::default()
+        .create_if_missing(true)
+        .create_missing_column_families(true)
+        .db_write_buffer_size(512 << 10);
+    let (db, cfs) = DB::open_with_column_families(
+        &opt,
+        &tmp_dir,
+        vec![
+            ColumnFamilyDescriptor::new(DEFAULT_COLUMN_FAMILY_NAME, cf_opt()),
+            ColumnFamilyDescriptor::new("a", cf_opt()),
+            ColumnFamilyDescriptor::new("b", cf_opt()),
+        ],
+    )
+    .unwrap();
+
+    // 1MB per column family, far below its own write_buffer_size
+    let value = vec![b'x'; 1024];
+    for i in 0..1024 {
+        let key = format!("k{:04}", i);
+        assert!(cfs[1].put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+        assert!(cfs[2].put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+    }
+
+    let num_l0_files = || {
+        cfs[1..]
+            .iter()
+            .map(|cf| {
+                cf.get_property("rocksdb.num-files-at-level0")
+                    .unwrap()
+                    .parse::<u32>()
+                    .unwrap()
+            })
+            .sum::<u32>()
+    };
+    for _ in 0..50 {
+        if num_l0_files() > 0 {
+            break;
+        }
+        ::std::thread::sleep(::std::time::Duration::from_millis(100));
+    }
+    assert!(num_l0_files() > 0);
+    assert!(
+        db.get_aggregated_int_property("rocksdb.cur-size-all-mem-tables")
+            .unwrap()
+            < 2 << 20
+    );
+}
+
+#[test]
+fn paranoid_checks_switch_to_read_only_on_write_failure() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let env: &'static Env = Box::leak(Box::new(Env::new_fault_injection(Env::default())));
+    let opt = Options::default().map_db_options(|db| db.create_if_missing(true).paranoid_checks(true).env(env));
+    let db = DB::open(&opt, &tmp_dir).unwrap();
+
+    assert!(db.put(&WriteOptions::default(), b"before", b"value").is_ok());
+
+    // WAL append fails, which puts the DB into background error state
+    env.set_filesystem_active(false);
+    assert!(db.put(&WriteOptions::default(), b"during", b"value").is_err());
+    env.set_filesystem_active(true);
+
+    // writes keep failing even though the filesystem is healthy again
+    assert!(db.put(&WriteOptions::default(), b"after", b"value").is_err());
+    assert!(db.delete(&WriteOptions::default(), b"before").is_err());
+
+    // reads still work
+    assert_eq!(db.get(&ReadOptions::default(), b"before").unwrap(), b"value");
+    assert!(db.get(&ReadOptions::default(), b"after").unwrap_err().is_not_found());
+}
+
+#[test]
+fn max_total_wal_size_forces_flush_of_oldest_wal() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = DBOptions::default()
+        .create_if_missing(true)
+        .create_missing_column_families(true);
+    let (db, cfs) = DB::open_with_column_families(
+        &opt,
+        &tmp_dir,
+        vec![
+            ColumnFamilyDescriptor::new(DEFAULT_COLUMN_FAMILY_NAME, ColumnFamilyOptions::default()),
+            ColumnFamilyDescriptor::new("a", ColumnFamilyOptions::default()),
+        ],
+    )
+    .unwrap();
+    let num_l0_files = |cf: &ColumnFamily| {
+        cf.get_property("rocksdb.num-files-at-level0")
+            .unwrap()
+            .parse::<u32>()
+            .unwrap()
+    };
+
+    // "a" keeps the first WAL alive, flushing the default CF switches to a new one
+    assert!(cfs[1].put(&WriteOptions::default(), b"k1", b"value").is_ok());
+    assert!(cfs[0].put(&WriteOptions::default(), b"k1", b"value").is_ok());
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    assert_eq!(num_l0_files(&cfs[0]), 1);
+    assert_eq!(num_l0_files(&cfs[1]), 0);
+
+    let new_opt: HashMap<&str, &str> = [("max_total_wal_size", "1")].iter().cloned().collect();
+    assert!(db.set_db_options(&new_opt).is_ok());
+
+    // the next write finds the WALs too large, and flushes the CF holding the oldest one
+    assert!(cfs[0].put(&WriteOptions::default(), b"k2", b"value").is_ok());
+    for _ in 0..50 {
+        if num_l0_files(&cfs[1]) > 0 {
+            break;
+        }
+        ::std::thread::sleep(::std::time::Duration::from_millis(100));
+    }
+    assert_eq!(num_l0_files(&cfs[1]), 1);
+    // the default CF does not hold the oldest WAL, its new write stays in the memtable
+    assert_eq!(num_l0_files(&cfs[0]), 1);
+    assert_eq!(cfs[0].get(&ReadOptions::default(), b"k2").unwrap(), b"value");
+}
+
+#[test]
+fn thread_list_reports_running_compaction() {
+    use rocks::compaction_filter::{CompactionFilter, Decision, ValueType};
+    use rocks::thread_status::OperationType;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    // slows down compaction so that it can be observed
+    struct SlowFilter;
+
+    impl CompactionFilter for SlowFilter {
+        fn filter(&mut self, _level: i32, _key: &[u8], _value_type: ValueType, _existing_value: &[u8]) -> Decision {
+            thread::sleep(Duration::from_millis(2));
+            Decision::Keep
+        }
+    }
+
+    static SLOW_FILTER: SlowFilter = SlowFilter;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true).enable_thread_tracking(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true).compaction_filter(&SLOW_FILTER)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    for i in 0..2 {
+        for j in 0..500 {
+            let key = format!("key-{:03}", j);
+            let val = format!("value-{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), val.as_bytes()).is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    }
+
+    let db = Arc::new(db);
+    let compaction = {
+        let db = db.clone();
+        thread::spawn(move || db.compact_range(&CompactRangeOptions::default(), ..).is_ok())
+    };
+
+    let mut seen = false;
+    for _ in 0..200 {
+        if db
+            .get_thread_list()
+            .iter()
+            .any(|ts| ts.operation_type() == OperationType::Compaction)
+        {
+            seen = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(compaction.join().unwrap());
+    assert!(seen);
+}
+
+#[test]
+fn num_running_background_jobs_under_load() {
+    use rocks::compaction_filter::{CompactionFilter, Decision, ValueType};
+    use std::thread;
+    use std::time::Duration;
+
+    // keeps compactions running long enough to be counted
+    struct SlowFilter;
+
+    impl CompactionFilter for SlowFilter {
+        fn filter(&mut self, _level: i32, _key: &[u8], _value_type: ValueType, _existing_value: &[u8]) -> Decision {
+            thread::sleep(Duration::from_millis(1));
+            Decision::Keep
+        }
+    }
+
+    static SLOW_FILTER: SlowFilter = SlowFilter;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true).max_background_jobs(4))
+            .map_cf_options(|cf| {
+                cf.write_buffer_size(64 * 1024)
+                    .level0_file_num_compaction_trigger(2)
+                    .compaction_filter(&SLOW_FILTER)
+            }),
+        &tmp_dir,
+    )
+    .unwrap();
+    assert_eq!(db.num_running_compactions(), 0);
+    assert_eq!(db.num_running_flushes(), 0);
+
+    let value = vec![b'x'; 1024];
+    let mut max_compactions = 0;
+    for i in 0..5_000 {
+        let key = format!("key-{:06}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+        max_compactions = max_compactions.max(db.num_running_compactions());
+        if max_compactions > 0 {
+            break;
+        }
+    }
+    assert!(max_compactions > 0);
+}
+
+#[test]
+fn num_running_flushes_while_flushing() {
+    use rocks::listener::{EventListener, FlushJobInfo};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::thread;
+    use std::time::Duration;
+
+    // holds the flush open until released, it's counted as running by then
+    struct FlushGate {
+        started: Sender<()>,
+        release: Receiver<()>,
+    }
+
+    impl EventListener for FlushGate {
+        fn on_flush_begin(&mut self, _db: &DBRef, _flush_job_info: &FlushJobInfo) {
+            let _ = self.started.send(());
+            let _ = self.release.recv();
+        }
+    }
+
+    let (started_tx, started) = mpsc::channel();
+    let (release, release_rx) = mpsc::channel();
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| {
+            db.create_if_missing(true).add_listener(FlushGate {
+                started: started_tx,
+                release: release_rx,
+            })
+        }),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+    assert!(db.flush(&FlushOptions::default().wait(false)).is_ok());
+    started.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(db.num_running_flushes(), 1);
+
+    release.send(()).unwrap();
+    // later flushes pass the gate right away
+    drop(release);
+    for _ in 0..1000 {
+        if db.num_running_flushes() == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(db.num_running_flushes(), 0);
+}
+
+#[test]
+fn key_exists_with_snapshot() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let opt = DBOptions::default()
+        .create_if_missing(true)
+        .create_missing_column_families(true);
+    let (db, cfs) = DB::open_with_column_families(
+        &opt,
+        &tmp_dir,
+        vec![
+            ColumnFamilyDescriptor::new(DEFAULT_COLUMN_FAMILY_NAME, ColumnFamilyOptions::default()),
+            ColumnFamilyDescriptor::new("a", ColumnFamilyOptions::default()),
+        ],
+    )
+    .unwrap();
+
+    assert!(cfs[1].put(&WriteOptions::default(), b"old", b"value").is_ok());
+    assert!(cfs[1].put(&WriteOptions::default(), b"deleted", b"value").is_ok());
+
+    let snap = db.get_snapshot();
+    assert!(cfs[1].put(&WriteOptions::default(), b"new", b"value").is_ok());
+    assert!(cfs[1].delete(&WriteOptions::default(), b"deleted").is_ok());
+
+    let latest = ReadOptions::default();
+    assert!(db.key_exists(&latest, &cfs[1], b"old"));
+    assert!(db.key_exists(&latest, &cfs[1], b"new"));
+    assert!(!db.key_exists(&latest, &cfs[1], b"deleted"));
+    assert!(!db.key_exists(&latest, &cfs[1], b"missing"));
+    // column family scoped
+    assert!(!db.key_exists(&latest, &cfs[0], b"old"));
+
+    let at_snap = ReadOptions::default().snapshot(snap.as_ref());
+    assert!(db.key_exists(&at_snap, &cfs[1], b"old"));
+    assert!(!db.key_exists(&at_snap, &cfs[1], b"new"));
+    assert!(db.key_exists(&at_snap, &cfs[1], b"deleted"));
+    assert!(!db.key_exists(&at_snap, &cfs[1], b"missing"));
+}
+
+#[test]
+fn compact_range_and_report_rewrite_and_trivial_move() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.default_column_family();
+
+    for i in 0..3 {
+        for j in 0..100 {
+            let key = format!("key-{:03}", j);
+            let val = format!("value-{}", i);
+            assert!(db.put(&WriteOptions::default(), key.as_bytes(), val.as_bytes()).is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    }
+
+    // overlapping L0 files are merged into one, a full rewrite
+    let result = db
+        .compact_range_and_report(&CompactRangeOptions::default(), &cf, ..)
+        .unwrap();
+    assert_eq!(result.level_files_before[0], 3);
+    assert_eq!(result.level_files_after[0], 0);
+    assert_eq!(result.level_files_after.iter().sum::<usize>(), 1);
+    assert_eq!(result.input_files, 3);
+    assert_eq!(result.output_files, 1);
+    assert!(result.bytes_read > result.bytes_written);
+    assert!(result.bytes_written > 0);
+    assert!(!result.is_trivial_move());
+
+    // already compacted, change_level only moves the file down
+    let result = db
+        .compact_range_and_report(
+            &CompactRangeOptions::default().change_level(true).target_level(3),
+            &cf,
+            ..,
+        )
+        .unwrap();
+    assert_eq!(result.level_files_before.iter().sum::<usize>(), 1);
+    assert_eq!(result.level_files_after[3], 1);
+    assert_eq!(result.files_moved, 1);
+    assert_eq!(result.input_files, 0);
+    assert_eq!(result.output_files, 0);
+    assert!(result.is_trivial_move());
+    assert_eq!(db.get(&ReadOptions::default(), b"key-042").unwrap(), b"value-2");
+}
+
+#[test]
+fn direct_io_on_unsupporting_filesystem() {
+    use rocks::error::Code;
+
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let wal_dir = tmp_dir.path().join("wal");
+    let data_dir = tmp_dir.path().join("data");
+    let env: &'static Env = Box::leak(Box::new(Env::new_fault_injection(Env::default())));
+    let opt = Options::default().map_db_options(|db| {
+        db.create_if_missing(true)
+            .use_direct_reads(true)
+            .env(env)
+            .wal_dir(&wal_dir)
+            .db_paths(vec![&data_dir])
+    });
+
+    env.set_direct_io_supported(false);
+    let err = DB::open(&opt, &tmp_dir).unwrap_err();
+    assert_eq!(err.code(), Code::NotSupported);
+    assert!(err.state().contains("direct IO is not supported"), "err => {}", err);
+    // the probe file is cleaned up
+    assert!(!tmp_dir.path().join("DIRECT_IO_PROBE").exists());
+
+    // every directory of the DB is probed and the probes are cleaned up
+    env.set_direct_io_supported(true);
+    let db = match DB::open(&opt, &tmp_dir) {
+        Ok(db) => db,
+        // the filesystem of the test directory really lacks O_DIRECT
+        Err(ref e) if e.code() == Code::NotSupported => return,
+        Err(e) => panic!("open => {}", e),
+    };
+    for dir in &[tmp_dir.path(), &wal_dir, &data_dir] {
+        assert!(dir.is_dir());
+        assert!(!dir.join("DIRECT_IO_PROBE").exists());
+    }
+    assert!(db.put(&WriteOptions::default(), b"key", b"value").is_ok());
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    assert_eq!(db.get(&ReadOptions::default(), b"key").unwrap(), b"value");
+}
+
+#[test]
+fn lower_soft_pending_compaction_limit_at_runtime() {
+    use rocks::listener::{EventListener, WriteStallCondition, WriteStallInfo};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct StallRecorder {
+        conditions: Vec<WriteStallCondition>,
+    }
+
+    impl EventListener for StallRecorder {
+        fn on_stall_conditions_changed(&mut self, info: &WriteStallInfo) {
+            assert_eq!(info.cf_name(), "default");
+            self.conditions.push(info.cur());
+        }
+    }
+
+    let recorder = Arc::new(Mutex::new(StallRecorder::default()));
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true).add_listener(recorder.clone()))
+            .map_cf_options(|cf| {
+                cf.disable_auto_compactions(true)
+                    .level0_file_num_compaction_trigger(2)
+                    .level0_slowdown_writes_trigger(20)
+                    .level0_stop_writes_trigger(40)
+            }),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.default_column_family();
+
+    // build a compaction backlog in L0
+    for i in 0..4 {
+        for j in 0..100 {
+            let key = format!("key-{:03}", j);
+            let value = format!("value-{}-{}", i, j);
+            assert!(db
+                .put(&WriteOptions::default(), key.as_bytes(), value.as_bytes())
+                .is_ok());
+        }
+        assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    }
+    let pending = db
+        .get_int_property_cf(&cf, "rocksdb.estimate-pending-compaction-bytes")
+        .unwrap();
+    assert!(pending > 0);
+    assert!(recorder.lock().unwrap().conditions.is_empty());
+
+    // pending compaction limits only apply with auto compactions, keep those
+    // from running and draining the backlog
+    assert!(db.pause_background_work().is_ok());
+    assert!(db
+        .set_options(
+            &cf,
+            vec![
+                ("disable_auto_compactions", "false"),
+                ("soft_pending_compaction_bytes_limit", "1"),
+            ],
+        )
+        .is_ok());
+
+    assert_eq!(recorder.lock().unwrap().conditions, vec![WriteStallCondition::Delayed]);
+    assert_eq!(db.get_int_property("rocksdb.is-write-stopped"), Some(0));
+    assert!(db.get_int_property("rocksdb.actual-delayed-write-rate").unwrap() > 0);
+    assert!(db.put(&WriteOptions::default(), b"slowed", b"value").is_ok());
+
+    // raising the limit back lifts the slowdown
+    assert!(db
+        .set_options(&cf, vec![("soft_pending_compaction_bytes_limit", "68719476736")])
+        .is_ok());
+    assert_eq!(
+        recorder.lock().unwrap().conditions,
+        vec![WriteStallCondition::Delayed, WriteStallCondition::Normal]
+    );
+    assert_eq!(db.get_int_property("rocksdb.actual-delayed-write-rate"), Some(0));
+
+    assert!(db.continue_background_work().is_ok());
+}
+
+#[test]
+fn sync_wal_rejected_with_mmap_writes() {
+    use rocks::error::Code;
+
+    let tmp_dir = TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true).allow_mmap_writes(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    assert!(db.put(&WriteOptions::default(), b"k", b"v").is_ok());
+    let err = db.sync_wal().unwrap_err();
+    assert_eq!(err.code(), Code::NotSupported);
+    drop(db);
+
+    let tmp_dir = TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    assert!(db.put(&WriteOptions::default(), b"k", b"v").is_ok());
+    assert!(db.sync_wal().is_ok());
+}
+
+#[test]
+fn flush_wal_with_and_without_sync() {
+    use std::fs;
+    use std::path::Path;
+
+    // what a process crash leaves behind: only data already handed to the OS
+    fn crash_copy(from: &Path) -> TempDir {
+        let to = TempDir::new_in(".", "rocks").unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            assert!(fs::copy(entry.path(), to.path().join(entry.file_name())).is_ok());
+        }
+        to
+    }
+
+    fn reopen_and_get(path: &Path, key: &[u8]) -> Option<Vec<u8>> {
+        let db = DB::open(&Options::default(), path).unwrap();
+        db.get(&ReadOptions::default(), key).ok().map(|v| v.to_vec())
+    }
+
+    let tmp_dir = TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default().map_db_options(|db| db.create_if_missing(true).manual_wal_flush(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+
+    // still buffered in the process
+    assert!(db.put(&WriteOptions::default(), b"k1", b"v1").is_ok());
+    let crashed = crash_copy(tmp_dir.path());
+    assert_eq!(reopen_and_get(crashed.path(), b"k1"), None);
+
+    // handed to the OS
+    assert!(db.flush_wal(false).is_ok());
+    let crashed = crash_copy(tmp_dir.path());
+    assert_eq!(reopen_and_get(crashed.path(), b"k1"), Some(b"v1".to_vec()));
+
+    // also fsynced, a machine crash can't be simulated here
+    assert!(db.put(&WriteOptions::default(), b"k2", b"v2").is_ok());
+    assert!(db.flush_wal(true).is_ok());
+    let crashed = crash_copy(tmp_dir.path());
+    assert_eq!(reopen_and_get(crashed.path(), b"k2"), Some(b"v2".to_vec()));
+
+    drop(db);
+    assert_eq!(reopen_and_get(tmp_dir.path(), b"k2"), Some(b"v2".to_vec()));
+}
+
+#[test]
+fn full_compact_reclaims_deleted_space() {
+    let tmp_dir = ::tempdir::TempDir::new_in(".", "rocks").unwrap();
+    let db = DB::open(
+        Options::default()
+            .map_db_options(|db| db.create_if_missing(true))
+            .map_cf_options(|cf| cf.disable_auto_compactions(true)),
+        &tmp_dir,
+    )
+    .unwrap();
+    let cf = db.default_column_family();
+    let live_data_size = || db.get_int_property_cf(&cf, "rocksdb.estimate-live-data-size").unwrap();
+
+    let value = vec![b'v'; 1000];
+    for i in 0..2000 {
+        let key = format!("key-{:05}", i);
+        assert!(db.put(&WriteOptions::default(), key.as_bytes(), &value).is_ok());
+    }
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    assert!(db.full_compact(&cf, 1).is_ok());
+    let before = live_data_size();
+
+    // mass delete, the tombstones only add to the table files
+    for i in 0..2000 {
+        if i % 10 != 0 {
+            let key = format!("key-{:05}", i);
+            assert!(db.delete(&WriteOptions::default(), key.as_bytes()).is_ok());
+        }
+    }
+    assert!(db.flush(&FlushOptions::default().wait(true)).is_ok());
+    assert!(live_data_size() >= before);
+
+    assert!(db.full_compact(&cf, 4).is_ok());
+    let after = live_data_size();
+    assert!(after < before / 5, "{} => {}", before, after);
+
+    assert!(db.get(&ReadOptions::default(), b"key-00010").is_ok());
+    assert!(db
+        .get(&ReadOptions::default(), b"key-00011")
+        .unwrap_err()
+        .is_not_found());
+}